@@ -18,6 +18,8 @@ class BinaryOperator(Enum):
     DIV = auto()
     MOD = auto()
     POW = auto()
+    SHL = auto()
+    SHR = auto()
     GT = auto()
     LT = auto()
     GE = auto()
@@ -36,6 +38,17 @@ class UnaryOperator(Enum):
     NEGATE = auto()
     POSITIVE = auto()
     NOT = auto()
+    BIT_NOT = auto()
+
+
+class UpdateOperator(Enum):
+    INCREMENT = "++"
+    DECREMENT = "--"
+
+
+class Visibility(Enum):
+    PUBLICUS = "publicus"
+    PRIVATUS = "privatus"
 
 
 @dataclass(slots=True)
@@ -64,6 +77,11 @@ class TypeAnnotation(Node):
     name: str
 
 
+@dataclass(slots=True)
+class TypeParameter(Node):
+    name: str
+
+
 @dataclass(slots=True)
 class Parameter(Node):
     name: str
@@ -77,6 +95,9 @@ class FunctionDeclaration(Declaration):
     parameters: List[Parameter]
     return_type: Optional[TypeAnnotation]
     body: "BlockStatement"
+    visibility: Optional[Visibility] = None
+    type_parameters: List[TypeParameter] = field(default_factory=list)
+    doc: List[str] = field(default_factory=list)
 
 
 @dataclass(slots=True)
@@ -86,6 +107,32 @@ class VariableDeclaration(Declaration):
     type_annotation: Optional[TypeAnnotation]
     initializer: Optional["Expression"]
     is_global: bool = False
+    visibility: Optional[Visibility] = None
+    doc: List[str] = field(default_factory=list)
+
+
+@dataclass(slots=True)
+class DestructuringDeclaration(Declaration):
+    """`constans (q, r) = divmod(7, 2);` binds each name to the element at its position."""
+
+    mutable: bool
+    names: List[str]
+    initializer: "Expression"
+
+
+@dataclass(slots=True)
+class ImportSpecifier(Node):
+    name: str
+    alias: Optional[str] = None
+
+
+@dataclass(slots=True)
+class ImportDeclaration(Declaration):
+    path: str
+    # `importa "m.stm" ut m;` binds the whole module to `alias`.
+    alias: Optional[str] = None
+    # `importa { f ut g } de "m.stm";` imports only the listed names.
+    names: List[ImportSpecifier] = field(default_factory=list)
 
 
 @dataclass(slots=True)
@@ -109,6 +156,7 @@ class IfStatement(Statement):
 class WhileStatement(Statement):
     condition: "Expression"
     body: Statement
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
@@ -123,6 +171,18 @@ class ForStatement(Statement):
     target: ForTarget
     iterable: "Expression"
     body: Statement
+    label: Optional[str] = None
+
+
+@dataclass(slots=True)
+class CStyleForStatement(Statement):
+    """`pro (init; condition; update) body`; each of the three header parts may be left empty."""
+
+    init: Optional[Statement]
+    condition: Optional["Expression"]
+    update: Optional["Expression"]
+    body: Statement
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
@@ -132,12 +192,12 @@ class ReturnStatement(Statement):
 
 @dataclass(slots=True)
 class BreakStatement(Statement):
-    pass
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
 class ContinueStatement(Statement):
-    pass
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
@@ -154,6 +214,8 @@ class Identifier(Expression):
 class Literal(Expression):
     value: object
     raw: str
+    # Number literals only: the `i`/`f` suffix written after the digits, kept in `raw` as well.
+    suffix: Optional[str] = None
 
 
 @dataclass(slots=True)
@@ -169,6 +231,26 @@ class BinaryExpression(Expression):
     right: Expression
 
 
+@dataclass(slots=True)
+class UpdateExpression(Expression):
+    operator: UpdateOperator
+    operand: Expression
+    prefix: bool
+
+
+@dataclass(slots=True)
+class CastExpression(Expression):
+    expression: Expression
+    target_type: TypeAnnotation
+
+
+@dataclass(slots=True)
+class RangeExpression(Expression):
+    start: Expression
+    end: Expression
+    inclusive: bool = False
+
+
 @dataclass(slots=True)
 class AssignmentExpression(Expression):
     target: Expression
@@ -186,18 +268,25 @@ class ConditionalExpression(Expression):
 class CallExpression(Expression):
     callee: Expression
     arguments: List[Expression]
+    type_arguments: List[TypeAnnotation] = field(default_factory=list)
+    # The last argument was written as `f(...) functio () { ... }`.
+    trailing_lambda: bool = False
 
 
 @dataclass(slots=True)
 class MemberExpression(Expression):
     object: Expression
     property: str
+    # Written `obj?.property`: a nullum `obj` makes the whole chain nullum.
+    optional: bool = False
 
 
 @dataclass(slots=True)
 class IndexExpression(Expression):
     collection: Expression
     index: Expression
+    # Written `items?[i]`: a nullum `items` makes the whole chain nullum, as with `?.`.
+    optional: bool = False
 
 
 @dataclass(slots=True)
@@ -205,10 +294,18 @@ class ArrayLiteral(Expression):
     elements: List[Expression]
 
 
+@dataclass(slots=True)
+class TupleLiteral(Expression):
+    # At least two elements: `(x)` is only a grouping.
+    elements: List[Expression]
+
+
 @dataclass(slots=True)
 class ObjectProperty(Node):
     key: str
     value: Expression
+    # Text of a `//` comment ending the property's line, which the formatter keeps next to it.
+    comment: Optional[str] = None
 
 
 @dataclass(slots=True)
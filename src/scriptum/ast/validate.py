@@ -0,0 +1,122 @@
+"""Structural invariants of the AST, checked after parsing."""
+
+from __future__ import annotations
+
+import types
+import typing
+from dataclasses import dataclass, fields
+from functools import lru_cache
+from typing import Any, Dict, List, Optional, Set
+
+from ..text import Span
+from . import nodes
+
+
+@dataclass(slots=True)
+class AstProblem:
+    message: str
+    span: Optional[Span]
+
+
+def validate_ast(module: nodes.Module) -> List[AstProblem]:
+    """
+    Check *module* for nodes the parser never builds.
+
+    Returns an empty list for a well-formed tree. Every field must hold what its annotation
+    declares (an `aliter` branch is a statement, a required child is never missing), the
+    `aliter` of a `si` is a block or another `si`, node ids are unique and each child's span
+    lies within its parent's. Trees assembled by hand, or by error recovery, are the ones
+    that can break these rules.
+    """
+
+    return _AstValidator().run(module)
+
+
+class _AstValidator:
+    def __init__(self) -> None:
+        self.problems: List[AstProblem] = []
+        self.seen_ids: Set[int] = set()
+
+    def run(self, module: nodes.Module) -> List[AstProblem]:
+        self._check_node(module, None)
+        return self.problems
+
+    def _check_node(self, node: nodes.Node, parent: Optional[nodes.Node]) -> None:
+        name = type(node).__name__
+        if node.node_id in self.seen_ids:
+            self._problem(f"{name} reuses node id {node.node_id}", node.span)
+        self.seen_ids.add(node.node_id)
+        if parent is not None and not _contains(parent.span, node.span):
+            self._problem(f"{name} at {node.span} lies outside its parent {type(parent).__name__}", node.span)
+        if isinstance(node, nodes.IfStatement) and node.else_branch is not None:
+            if not isinstance(node.else_branch, (nodes.BlockStatement, nodes.IfStatement)):
+                self._problem(
+                    f"'aliter' branch must be a block or 'si', got {type(node.else_branch).__name__}",
+                    node.else_branch.span,
+                )
+        hints = _field_types(type(node))
+        for item in fields(node):
+            if item.name in {"node_id", "span"}:
+                continue
+            value = getattr(node, item.name)
+            expected = hints.get(item.name, Any)
+            if not _matches(value, expected):
+                self._problem(
+                    f"{name}.{item.name} should be {_describe(expected)}, got {_describe_value(value)}",
+                    node.span,
+                )
+                continue
+            for child in _child_nodes(value):
+                self._check_node(child, node)
+
+    def _problem(self, message: str, span: Optional[Span]) -> None:
+        self.problems.append(AstProblem(message, span))
+
+
+@lru_cache(maxsize=None)
+def _field_types(node_type: type) -> Dict[str, Any]:
+    return typing.get_type_hints(node_type, vars(nodes))
+
+
+def _matches(value: Any, expected: Any) -> bool:
+    if expected is Any:
+        return True
+    origin = typing.get_origin(expected)
+    if origin in (typing.Union, types.UnionType):
+        return any(_matches(value, option) for option in typing.get_args(expected))
+    if origin in (list, List):
+        (item_type,) = typing.get_args(expected) or (Any,)
+        return isinstance(value, list) and all(_matches(item, item_type) for item in value)
+    if expected is type(None):
+        return value is None
+    if expected is float:
+        return isinstance(value, (int, float)) and not isinstance(value, bool)
+    return isinstance(value, expected)
+
+
+def _child_nodes(value: Any) -> List[nodes.Node]:
+    if isinstance(value, nodes.Node):
+        return [value]
+    if isinstance(value, list):
+        return [item for item in value if isinstance(item, nodes.Node)]
+    return []
+
+
+def _contains(outer: Span, inner: Span) -> bool:
+    return outer.start <= inner.start and inner.end <= outer.end
+
+
+def _describe(expected: Any) -> str:
+    origin = typing.get_origin(expected)
+    if origin in (typing.Union, types.UnionType):
+        return " or ".join(_describe(option) for option in typing.get_args(expected))
+    if origin in (list, List):
+        (item_type,) = typing.get_args(expected) or (Any,)
+        return f"a list of {_describe(item_type)}"
+    if expected is type(None):
+        return "missing"
+    return getattr(expected, "__name__", str(expected))
+
+
+def _describe_value(value: Any) -> str:
+    return "nothing" if value is None else type(value).__name__
@@ -4,7 +4,8 @@ Visitor utilities for traversing Scriptum AST nodes.
 
 from __future__ import annotations
 
-from typing import Protocol
+from dataclasses import fields
+from typing import Dict, Iterator, List, Mapping, Optional, Protocol, Sequence, Set, Union
 
 from . import nodes
 
@@ -17,11 +18,233 @@ class Visitor(Protocol):
 
 
 def walk(visitor: Visitor, node: nodes.Node) -> None:
+    """Call `visitor.visit` on *node* and then on each descendant, depth first."""
+
+    visitor.visit(node)
+    for child in iter_children(node):
+        walk(visitor, child)
+
+
+def iter_children(node: nodes.Node) -> Iterator[nodes.Node]:
+    """Yield the direct child nodes of *node* in field order."""
+
+    for item in fields(node):
+        value = getattr(node, item.name)
+        if isinstance(value, nodes.Node):
+            yield value
+        elif isinstance(value, list):
+            yield from (entry for entry in value if isinstance(entry, nodes.Node))
+
+
+def count_nodes(node: nodes.Node) -> int:
+    """Number of nodes in the tree rooted at *node*, itself included: declarations, statements, expressions, types."""
+
+    # An explicit stack, so a deeply nested tree cannot exhaust the recursion limit.
+    count, pending = 0, [node]
+    while pending:
+        count += 1
+        pending.extend(iter_children(pending.pop()))
+    return count
+
+
+def nodes_at(node: nodes.Node, offset: int) -> List[nodes.Node]:
+    """The nodes whose span contains the source *offset*, from *node* down to the innermost one."""
+
+    path: List[nodes.Node] = []
+    current: Optional[nodes.Node] = node if _contains(node, offset) else None
+    while current is not None:
+        path.append(current)
+        current = next((child for child in iter_children(current) if _contains(child, offset)), None)
+    return path
+
+
+def node_at(node: nodes.Node, offset: int) -> Optional[nodes.Node]:
+    """The innermost node under the source *offset*, or None when *offset* lies outside *node*."""
+
+    path = nodes_at(node, offset)
+    return path[-1] if path else None
+
+
+def _contains(node: nodes.Node, offset: int) -> bool:
+    return node.span.start <= offset < node.span.end
+
+
+class FreeIdentifierCollector:
     """
-    Dispatch to `visitor.visit`.
+    Scoped visitor that finds the names a function or lambda uses without binding them.
 
-    Real traversal logic will be added once the AST node hierarchy is fleshed
-    out.
+    Parameters, local declarations and `pro` targets are bound; every other
+    identifier (captured locals of an enclosing function as well as globals)
+    is reported. Nested lambdas contribute the names they capture themselves.
     """
 
-    visitor.visit(node)
+    def __init__(self) -> None:
+        self.free: Set[str] = set()
+        self._scopes: List[Set[str]] = []
+
+    def collect(self, function: Union[nodes.FunctionDeclaration, nodes.LambdaExpression]) -> Set[str]:
+        self._visit_function(function.parameters, function.body)
+        return self.free
+
+    def visit(self, node: nodes.Node) -> None:
+        if isinstance(node, nodes.Identifier):
+            if not any(node.name in scope for scope in self._scopes):
+                self.free.add(node.name)
+        elif isinstance(node, (nodes.FunctionDeclaration, nodes.LambdaExpression)):
+            self._visit_function(node.parameters, node.body)
+        elif isinstance(node, nodes.BlockStatement):
+            self._scopes.append(set())
+            for statement in node.statements:
+                self.visit(statement)
+            self._scopes.pop()
+        elif isinstance(node, nodes.VariableDeclaration):
+            if node.initializer is not None:
+                self.visit(node.initializer)
+            self._scopes[-1].add(node.name)
+        elif isinstance(node, nodes.DestructuringDeclaration):
+            self.visit(node.initializer)
+            self._scopes[-1].update(node.names)
+        elif isinstance(node, nodes.ForStatement):
+            self.visit(node.iterable)
+            self._scopes.append({node.target.name})
+            self.visit(node.body)
+            self._scopes.pop()
+        elif isinstance(node, nodes.CStyleForStatement):
+            self._scopes.append(set())
+            for child in iter_children(node):
+                self.visit(child)
+            self._scopes.pop()
+        else:
+            for child in iter_children(node):
+                self.visit(child)
+
+    def _visit_function(self, parameters: List[nodes.Parameter], body: nodes.Node) -> None:
+        self._scopes.append(set())
+        for param in parameters:
+            if param.default_value is not None:
+                self.visit(param.default_value)
+            self._scopes[-1].add(param.name)
+        self.visit(body)
+        self._scopes.pop()
+
+
+def free_identifiers(function: Union[nodes.FunctionDeclaration, nodes.LambdaExpression]) -> Set[str]:
+    """Names referenced by *function* that it does not declare itself (its captures)."""
+
+    return FreeIdentifierCollector().collect(function)
+
+
+class CallCollector(FreeIdentifierCollector):
+    """Scoped visitor that lists, in first-call order, the unbound names a function calls directly."""
+
+    def __init__(self) -> None:
+        super().__init__()
+        self.calls: List[str] = []
+
+    def visit(self, node: nodes.Node) -> None:
+        if isinstance(node, nodes.CallExpression) and isinstance(node.callee, nodes.Identifier):
+            name = node.callee.name
+            if name not in self.calls and not any(name in scope for scope in self._scopes):
+                self.calls.append(name)
+        super().visit(node)
+
+
+def call_graph(module: nodes.Module) -> Dict[str, List[str]]:
+    """
+    Map each top-level function of *module* to the top-level functions it calls.
+
+    Calls made inside nested lambdas count for the enclosing function; names
+    shadowed by a parameter or local are not calls to the function.
+    """
+
+    functions = [decl for decl in module.declarations if isinstance(decl, nodes.FunctionDeclaration)]
+    known = {function.name for function in functions}
+    graph: Dict[str, List[str]] = {}
+    for function in functions:
+        collector = CallCollector()
+        collector.collect(function)
+        graph[function.name] = [name for name in collector.calls if name in known]
+    return graph
+
+
+def strongly_connected_components(graph: Mapping[str, Sequence[str]]) -> List[List[str]]:
+    """
+    Tarjan's algorithm over *graph*: the strongly connected components, callees before callers.
+
+    Each component keeps the order in which its members appear in *graph*;
+    edges to names that are not keys of *graph* are ignored.
+    """
+
+    order = {name: position for position, name in enumerate(graph)}
+    index: Dict[str, int] = {}
+    lowlink: Dict[str, int] = {}
+    stack: List[str] = []
+    on_stack: Set[str] = set()
+    components: List[List[str]] = []
+
+    def visit(name: str) -> None:
+        index[name] = lowlink[name] = len(index)
+        stack.append(name)
+        on_stack.add(name)
+
+    # An explicit work stack of (function, callees still to look at), so a long call chain
+    # cannot exhaust Python's recursion limit.
+    for root in graph:
+        if root in index:
+            continue
+        visit(root)
+        work = [(root, iter(graph[root]))]
+        while work:
+            name, callees = work[-1]
+            for callee in callees:
+                if callee not in graph:
+                    continue
+                if callee not in index:
+                    visit(callee)
+                    work.append((callee, iter(graph[callee])))
+                    break
+                if callee in on_stack:
+                    lowlink[name] = min(lowlink[name], index[callee])
+            else:
+                work.pop()
+                if work:
+                    caller = work[-1][0]
+                    lowlink[caller] = min(lowlink[caller], lowlink[name])
+                if lowlink[name] == index[name]:
+                    component: List[str] = []
+                    while True:
+                        member = stack.pop()
+                        on_stack.discard(member)
+                        component.append(member)
+                        if member == name:
+                            break
+                    components.append(sorted(component, key=order.__getitem__))
+    return components
+
+
+def recursive_functions(graph: Mapping[str, Sequence[str]]) -> List[List[str]]:
+    """
+    The groups of functions in *graph* that can call themselves, directly or through each other.
+
+    A function in one of these groups must never be inlined: expanding its
+    body would reach another call to the same group.
+    """
+
+    return [
+        component
+        for component in strongly_connected_components(graph)
+        if len(component) > 1 or component[0] in graph[component[0]]
+    ]
+
+
+def is_optional_chain(expr: nodes.Expression) -> bool:
+    """Whether *expr* is a member, call or index chain with a `?.` or `?[` link anywhere below it."""
+
+    while isinstance(expr, (nodes.MemberExpression, nodes.CallExpression, nodes.IndexExpression)):
+        if isinstance(expr, nodes.CallExpression):
+            expr = expr.callee
+            continue
+        if expr.optional:
+            return True
+        expr = expr.object if isinstance(expr, nodes.MemberExpression) else expr.collection
+    return False
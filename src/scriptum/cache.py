@@ -0,0 +1,73 @@
+"""On-disk cache of lowered IR, so rebuilding an unchanged file only runs codegen."""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import pathlib
+from typing import Dict, Iterable, Optional
+
+from . import errors
+from .ir import ModuleIr, from_bytes, to_bytes
+from .ir.binary import FORMAT_VERSION
+from .sema.analyzer import AnalyzerOptions
+
+
+def content_hash(source_text: str) -> str:
+    return hashlib.sha256(source_text.encode("utf8")).hexdigest()
+
+
+class BuildCache:
+    """
+    Stores the IR of checked modules in *directory*, one entry per source hash.
+
+    The key also covers the resolved source path, the compiler version, the IR
+    format version and the analyzer options, so entries written for another
+    file or under other settings are never reused: two files with the same
+    text may import different modules next to them.
+    Each entry records the hashes of the modules it imports; an entry whose
+    imports changed since it was written counts as a miss.
+    """
+
+    def __init__(self, directory: pathlib.Path, compiler_version: str) -> None:
+        self.directory = directory
+        self.compiler_version = compiler_version
+
+    def key(self, source_text: str, options: AnalyzerOptions, source_path: str = "<stdin>") -> str:
+        salt = f"{self.compiler_version}\0{FORMAT_VERSION}\0{options!r}\0{source_path}\0"
+        return content_hash(salt + source_text)
+
+    def load(self, key: str) -> Optional[ModuleIr]:
+        """Return the cached IR for *key*, or None when there is no usable entry."""
+
+        try:
+            dependencies: Dict[str, str] = json.loads(self._path(key, ".deps.json").read_text(encoding="utf8"))
+            data = self._path(key, ".scir").read_bytes()
+        except (OSError, ValueError):
+            return None
+        for path, expected in dependencies.items():
+            try:
+                current = content_hash(pathlib.Path(path).read_text(encoding="utf8"))
+            except OSError:
+                return None
+            if current != expected:
+                return None
+        try:
+            return from_bytes(data)
+        except errors.IrFormatError:
+            # Written by another compiler build; the caller recompiles and overwrites it.
+            return None
+
+    def store(self, key: str, module: ModuleIr, dependencies: Iterable[tuple[pathlib.Path, str]] = ()) -> None:
+        """Save *module* under *key*; *dependencies* pairs each imported file with its text."""
+
+        self.directory.mkdir(parents=True, exist_ok=True)
+        hashes = {str(path): content_hash(source_text) for path, source_text in dependencies}
+        self._path(key, ".scir").write_bytes(to_bytes(module))
+        self._path(key, ".deps.json").write_text(json.dumps(hashes, indent=2), encoding="utf8")
+
+    def _path(self, key: str, suffix: str) -> pathlib.Path:
+        return self.directory / f"{key}{suffix}"
+
+
+__all__ = ["BuildCache", "content_hash"]
@@ -12,7 +12,7 @@ import tempfile
 import textwrap
 from dataclasses import fields, is_dataclass
 from enum import Enum
-from typing import Any, Optional
+from typing import Any, Optional, Sequence
 
 import click
 
@@ -22,12 +22,18 @@ except Exception:  # pragma: no cover - fallback when dependency is missing
     Figlet = None
 
 from . import __version__, errors, tokens
-from .codegen import generate
-from .driver import CompilerDriver, Stage
-from .ir import format_module_ir
+from .ast.visitors import call_graph, recursive_functions
+from .codegen import generate, generate_js
+from .config import ProjectConfig, find_config, indent_unit, load_config
+from .diagnostics import collect_diagnostics
+from .driver import CompilerDriver, DriverConfig, Stage
+from .ir import format_ir, format_module_ir
+from .ir.interpreter import display_value
 from .lexer.lexer import ScriptumLexer
-from .parser.parser import ScriptumParser
-from .text import SourceFile, highlight_span, line_col
+from .lsp import LanguageServer
+from .parser.parser import ParseError, ScriptumParser
+from .sema.analyzer import AnalyzerOptions
+from .text import SourceFile, Span, highlight_span, line_col
 
 HELP_OPTIONS = ["-h", "--help"]
 
@@ -108,7 +114,7 @@ class ScriptumGroup(click.Group):
 @click.option(
     "--config",
     type=click.Path(dir_okay=False, path_type=pathlib.Path),
-    help="Configuration file path (reserved for future use).",
+    help="Project settings file (default: the nearest scriptum.toml in the current directory or its ancestors).",
 )
 @click.option(
     "-c",
@@ -144,6 +150,38 @@ def cli(
         _dispatch_default(ctx, inline_code=inline_code, module_name=module_name)
 
 
+def _load_project_config(explicit: Optional[pathlib.Path]) -> ProjectConfig:
+    path = explicit or find_config(pathlib.Path.cwd())
+    if path is None:
+        return ProjectConfig()
+    try:
+        return load_config(path)
+    except errors.ConfigError as exc:
+        raise click.ClickException(str(exc)) from exc
+
+
+def _project_config() -> ProjectConfig:
+    """
+    Settings from `scriptum.toml` for the running command; defaults outside a project.
+
+    The file is only read by commands that use it, so a broken one does not stop `tokens` or `--help`.
+    """
+
+    obj = click.get_current_context().find_root().obj
+    if obj is None:
+        return ProjectConfig()
+    if "project" not in obj:
+        obj["project"] = _load_project_config(obj.get("config"))
+    return obj["project"]
+
+
+def _driver_config(**overrides: Any) -> DriverConfig:
+    project = _project_config()
+    settings: dict[str, Any] = {"analyzer": project.analyzer_options(), "codegen": project.codegen_options()}
+    settings.update(overrides)
+    return DriverConfig(**settings)
+
+
 def _dispatch_default(ctx: click.Context, inline_code: Optional[str], module_name: Optional[str]) -> None:
     """Handle `scriptum <file>` / `scriptum -c` / `scriptum -m` when no subcommand is supplied."""
 
@@ -228,18 +266,31 @@ def _handle_semantic_error(exc: errors.SemanticError, source: pathlib.Path) -> N
     raise click.ClickException("Semantic analysis failed.") from exc
 
 
-def _handle_compiler_error(exc: errors.CompilerError) -> None:
-    raise click.ClickException(str(exc)) from exc
+def _compiler_error_message(exc: errors.CompilerError, source_text: Optional[str] = None) -> str:
+    message = str(exc)
+    if isinstance(exc, errors.LexerError) and source_text is not None:
+        message = f"{message}\n{highlight_span(source_text, exc.anchor)}"
+    if isinstance(exc, ParseError) and source_text is not None:
+        message += _notes_text(exc.notes, source_text)
+    return message
 
 
-def _run_driver(source: pathlib.Path, stage: Stage) -> CompilerDriver.Result:
-    driver = CompilerDriver()
+def _notes_text(notes: Sequence[tuple[str, Span]], source_text: str) -> str:
+    return "".join(f"\nnote: {label}\n{highlight_span(source_text, span)}" for label, span in notes)
+
+
+def _handle_compiler_error(exc: errors.CompilerError, source_text: Optional[str] = None) -> None:
+    raise click.ClickException(_compiler_error_message(exc, source_text)) from exc
+
+
+def _run_driver(source: pathlib.Path, stage: Stage, config: Optional[DriverConfig] = None) -> CompilerDriver.Result:
+    driver = CompilerDriver(config or _driver_config())
     try:
         return driver.run(source=source, until=stage)
     except errors.SemanticError as exc:
         _handle_semantic_error(exc, source)
     except errors.CompilerError as exc:
-        _handle_compiler_error(exc)
+        _handle_compiler_error(exc, source.read_text(encoding="utf8"))
     raise AssertionError("unreachable")  # pragma: no cover
 
 
@@ -275,7 +326,8 @@ def run_cmd(source: Optional[pathlib.Path], inline_code: Optional[str], module_n
 
     execution = result.execution
     value = execution.value if execution else None
-    click.echo(json.dumps(value, ensure_ascii=False))
+    # Ranges and functions have no JSON form; they are written as `imprime` shows them.
+    click.echo(json.dumps(value, ensure_ascii=False, default=display_value))
 
 
 @cli.command("repl", help="Start an experimental Scriptum REPL.")
@@ -314,8 +366,19 @@ def repl_cmd() -> None:
     help="Select the artifact to emit.",
 )
 @click.option("--out", "output_path", type=click.Path(dir_okay=False, path_type=pathlib.Path))
-def build_cmd(source: pathlib.Path, emit: str, output_path: Optional[pathlib.Path]) -> None:
-    result = _run_driver(source, Stage.CODEGEN)
+@click.option(
+    "--cache-dir",
+    type=click.Path(file_okay=False, path_type=pathlib.Path),
+    default=None,
+    help="Reuse the IR of unchanged sources stored in this directory.",
+)
+def build_cmd(
+    source: pathlib.Path,
+    emit: str,
+    output_path: Optional[pathlib.Path],
+    cache_dir: Optional[pathlib.Path],
+) -> None:
+    result = _run_driver(source, Stage.CODEGEN, _driver_config(cache_dir=cache_dir))
     if emit == "ir":
         payload = format_module_ir(result.ir) if result.ir else "{}"
     else:
@@ -323,6 +386,61 @@ def build_cmd(source: pathlib.Path, emit: str, output_path: Optional[pathlib.Pat
     _write_payload(payload, output_path)
 
 
+# `--emit` value -> the pipeline stage that produces it.
+_EMIT_STAGES = {
+    "tokens": Stage.LEXER,
+    "ast": Stage.PARSER,
+    "ir": Stage.IR,
+    "ir-json": Stage.IR,
+    "code": Stage.CODEGEN,
+    # Codegen also links and validates the IR before it is translated.
+    "js": Stage.CODEGEN,
+}
+
+
+@cli.command("compile", help="Run the pipeline up to one representation and print it.")
+@click.argument("source", type=SCRIPTUM_FILE, required=True)
+@click.option(
+    "--emit",
+    type=click.Choice(list(_EMIT_STAGES)),
+    default=None,
+    help="tokens and ast as JSON, the IR as an indented tree (ir) or JSON (ir-json), formatted Scriptum (code) "
+    "or JavaScript (js). "
+    "Defaults to build.target in scriptum.toml, else code.",
+)
+@click.option(
+    "--stage",
+    type=click.Choice([stage.value for stage in Stage]),
+    default=None,
+    hidden=True,
+    help="Legacy: run until this stage and print nothing.",
+)
+def compile_cmd(source: pathlib.Path, emit: Optional[str], stage: Optional[str]) -> None:
+    if stage is not None:
+        _warn_legacy("scriptum compile --stage", "scriptum compile --emit")
+        _run_driver(source, Stage(stage))
+        return
+    if emit is None:
+        emit = "js" if _project_config().target == "js" else "code"
+    result = _run_driver(source, _EMIT_STAGES[emit])
+    if emit == "tokens":
+        payload = json.dumps(_tokens_to_json(result), ensure_ascii=False, indent=2)
+    elif emit == "ast":
+        payload = json.dumps(_ast_to_dict(result.ast), indent=2, ensure_ascii=False)
+    elif emit == "ir":
+        payload = format_ir(result.ir)
+    elif emit == "ir-json":
+        payload = format_module_ir(result.ir)
+    elif emit == "js":
+        try:
+            payload = generate_js(result.ir, result.source)
+        except errors.CompilerError as exc:
+            _handle_compiler_error(exc)
+    else:
+        payload = result.formatted or ""
+    click.echo(payload)
+
+
 def _write_payload(payload: str, destination: Optional[pathlib.Path]) -> None:
     if destination:
         destination.write_text(payload, encoding="utf8")
@@ -462,45 +580,198 @@ def _format_path_list(paths: list[pathlib.Path]) -> str:
     return f"[{literal}]"
 
 
-def _perform_semantic_check(source: pathlib.Path, json_output: bool, quiet_success: bool = False) -> bool:
-    driver = CompilerDriver()
+def _analyzer_options(strict_any: Optional[bool], strict_params: Optional[bool]) -> AnalyzerOptions:
+    # Flags left unset fall back to scriptum.toml.
+    project = _project_config()
+    return AnalyzerOptions(
+        strict_any=project.strict_any if strict_any is None else strict_any,
+        strict_params=project.strict_params if strict_params is None else strict_params,
+    )
+
+
+def _diagnostic_label(diagnostic: dict[str, Any]) -> str:
+    return diagnostic["code"] if diagnostic["severity"] == "error" else f"{diagnostic['code']} (warning)"
+
+
+def _plural(count: int, noun: str) -> str:
+    return f"{count} {noun}" if count == 1 else f"{count} {noun}s"
+
+
+def _perform_semantic_check(
+    source: pathlib.Path,
+    json_output: bool,
+    quiet_success: bool = False,
+    strict_any: Optional[bool] = None,
+    strict_params: Optional[bool] = None,
+    show_inferred: bool = False,
+    deny_warnings: bool = False,
+) -> bool:
+    driver = CompilerDriver(_driver_config(analyzer=_analyzer_options(strict_any, strict_params)))
+    text_data = source.read_text(encoding="utf8")
     try:
         result = driver.run(source, until=Stage.SEMANTIC)
     except errors.SemanticError as exc:
         _handle_semantic_error(exc, source)
         return
     except errors.CompilerError as exc:
-        _handle_compiler_error(exc)
-        return
+        if not json_output:
+            _handle_compiler_error(exc, text_data)
+        payload = [_diagnostic_to_json(diag, text_data) for diag in collect_diagnostics(exc)]
+        click.echo(json.dumps(payload, indent=2, ensure_ascii=False))
+        raise click.ClickException("Compilation stopped before semantic analysis.") from exc
 
-    diagnostics = result.diagnostics or []
+    diagnostics = collect_diagnostics(semantic=result.diagnostics or [])
     if diagnostics:
-        payload = [_diagnostic_to_json(diag, source.read_text(encoding="utf8")) for diag in diagnostics]
+        payload = [_diagnostic_to_json(diag, text_data) for diag in diagnostics]
         if json_output:
             click.echo(json.dumps(payload, indent=2, ensure_ascii=False))
         else:
-            for diagnostic in payload:
-                click.echo(f"{diagnostic['code']}: {diagnostic['message']}")
-        raise click.ClickException("Semantic analysis reported issues.")
-
-    if json_output and not quiet_success:
+            for diagnostic, entry in zip(diagnostics, payload):
+                click.echo(f"{_diagnostic_label(entry)}: {entry['message']}{_notes_text(diagnostic.notes, text_data)}")
+        warnings = sum(1 for diag in diagnostics if not diag.is_error)
+        denied = warnings if deny_warnings else 0
+        if denied or any(diag.is_error for diag in diagnostics):
+            message = "Semantic analysis reported issues."
+            if denied:
+                promoted = "1 warning treated as an error" if denied == 1 else f"{denied} warnings treated as errors"
+                message += f" {promoted} (--deny-warnings)."
+            raise click.ClickException(message)
+    elif json_output and not quiet_success:
         click.echo("[]")
+
+    if show_inferred:
+        for entry in result.symbols or []:
+            if entry.inferred:
+                click.echo(f"functio {entry.name}{str(entry.type)[len('functio'):]}")
     return True
 
 
+def _check_files(
+    sources: tuple[pathlib.Path, ...],
+    json_output: bool,
+    strict_any: Optional[bool],
+    strict_params: Optional[bool],
+    show_inferred: bool,
+    deny_warnings: bool,
+) -> None:
+    """Check every file in *sources*, grouping diagnostics per file, and fail if any of them has errors."""
+
+    driver = CompilerDriver(_driver_config(analyzer=_analyzer_options(strict_any, strict_params)))
+    report: list[dict[str, Any]] = []
+    error_count = warning_count = 0
+    for source in sources:
+        text_data = source.read_text(encoding="utf8")
+        stopped: Optional[errors.CompilerError] = None
+        result = None
+        try:
+            result = driver.run(source, until=Stage.SEMANTIC)
+            diagnostics = collect_diagnostics(semantic=result.diagnostics or [])
+        except errors.CompilerError as exc:
+            # A file that does not parse counts as one error and does not stop the others.
+            stopped = exc
+            diagnostics = collect_diagnostics(exc)
+        payload = [_diagnostic_to_json(diag, text_data) for diag in diagnostics]
+        report.append({"file": str(source), "diagnostics": payload})
+        error_count += sum(1 for diag in diagnostics if diag.is_error)
+        warning_count += sum(1 for diag in diagnostics if not diag.is_error)
+        if json_output:
+            continue
+        inferred = [entry for entry in (result.symbols or []) if entry.inferred] if show_inferred and result else []
+        if not payload and not inferred:
+            continue
+        click.echo(f"{source}:")
+        if stopped is not None:
+            click.echo(textwrap.indent(_compiler_error_message(stopped, text_data), "  "))
+        else:
+            for diagnostic, entry in zip(diagnostics, payload):
+                line = f"{_diagnostic_label(entry)}: {entry['message']}{_notes_text(diagnostic.notes, text_data)}"
+                click.echo(textwrap.indent(line, "  "))
+        for entry in inferred:
+            click.echo(f"  functio {entry.name}{str(entry.type)[len('functio'):]}")
+
+    if json_output:
+        click.echo(json.dumps(report, indent=2, ensure_ascii=False))
+    else:
+        click.echo(
+            f"Checked {_plural(len(sources), 'file')}: "
+            f"{_plural(error_count, 'error')}, {_plural(warning_count, 'warning')}."
+        )
+    denied = warning_count if deny_warnings else 0
+    if error_count or denied:
+        failing = sum(
+            1
+            for entry in report
+            if any(deny_warnings or diag["severity"] == "error" for diag in entry["diagnostics"])
+        )
+        message = f"Semantic analysis reported issues in {failing} of {_plural(len(sources), 'file')}."
+        if denied:
+            promoted = "1 warning treated as an error" if denied == 1 else f"{denied} warnings treated as errors"
+            message += f" {promoted} (--deny-warnings)."
+        raise click.ClickException(message)
+    if not json_output:
+        click.echo("Semantic analysis completed successfully.")
+
+
 @cli.command("check", help="Run semantic analysis and report diagnostics.")
-@click.argument("source", type=SCRIPTUM_FILE, required=True)
+@click.argument("sources", type=SCRIPTUM_FILE, nargs=-1, required=True)
 @click.option("--json", "json_output", is_flag=True, help="Return diagnostics as JSON.")
-def check_cmd(source: pathlib.Path, json_output: bool) -> None:
-    _perform_semantic_check(source, json_output, quiet_success=json_output)
+@click.option(
+    "--strict-any/--no-strict-any",
+    default=None,
+    help="Require 'ut' casts for conversions to or from quodlibet (default: check.strict_any in scriptum.toml).",
+)
+@click.option(
+    "--strict-params/--no-strict-params",
+    default=None,
+    help="Require type annotations on top-level function parameters (default: check.strict_params).",
+)
+@click.option("--show-inferred", is_flag=True, help="Print the inferred signature of functions without a return type.")
+@click.option("--deny-warnings", is_flag=True, help="Fail when there are warnings, as if they were errors.")
+def check_cmd(
+    sources: tuple[pathlib.Path, ...],
+    json_output: bool,
+    strict_any: Optional[bool],
+    strict_params: Optional[bool],
+    show_inferred: bool,
+    deny_warnings: bool,
+) -> None:
+    if show_inferred and json_output:
+        raise click.UsageError("--show-inferred cannot be combined with --json.")
+    if len(sources) > 1:
+        _check_files(sources, json_output, strict_any, strict_params, show_inferred, deny_warnings)
+        return
+    _perform_semantic_check(
+        sources[0],
+        json_output,
+        quiet_success=json_output,
+        strict_any=strict_any,
+        strict_params=strict_params,
+        show_inferred=show_inferred,
+        deny_warnings=deny_warnings,
+    )
     if not json_output:
         click.echo("Semantic analysis completed successfully.")
 
 
 @cli.command("fmt", help="Format Scriptum files or stdin.")
 @click.argument("source", type=SCRIPTUM_FILE, required=False)
-def fmt_cmd(source: Optional[pathlib.Path]) -> None:
+@click.option("--indent", default=None, help="Spaces per indentation level (1 to 8) or 'tab' (default: format.indent).")
+@click.option(
+    "--max-width",
+    type=click.IntRange(min=1),
+    default=None,
+    help="Break the outermost list of longer statements (default: format.max_width, else never).",
+)
+def fmt_cmd(source: Optional[pathlib.Path], indent: Optional[str], max_width: Optional[int]) -> None:
     parser = ScriptumParser()
+    options = _project_config().codegen_options()
+    if indent is not None:
+        try:
+            options.indent = indent_unit(indent)
+        except errors.ConfigError as exc:
+            raise click.BadParameter(str(exc), param_hint="--indent") from exc
+    if max_width is not None:
+        options.max_width = max_width
 
     if source is None:
         text_data = sys.stdin.read()
@@ -509,8 +780,8 @@ def fmt_cmd(source: Optional[pathlib.Path]) -> None:
         try:
             module = parser.parse(SourceFile("<stdin>", text_data))
         except errors.CompilerError as exc:
-            _handle_compiler_error(exc)
-        formatted = generate(module).formatted
+            _handle_compiler_error(exc, text_data)
+        formatted = generate(module, options).formatted
         click.echo(formatted, nl=False)
         return
 
@@ -518,9 +789,9 @@ def fmt_cmd(source: Optional[pathlib.Path]) -> None:
     try:
         module = parser.parse(SourceFile(str(source), original_text))
     except errors.CompilerError as exc:
-        _handle_compiler_error(exc)
+        _handle_compiler_error(exc, original_text)
 
-    formatted = generate(module).formatted
+    formatted = generate(module, options).formatted
     if original_text != formatted:
         source.write_text(formatted, encoding="utf8")
         click.echo(f"Formatted {source}")
@@ -528,6 +799,38 @@ def fmt_cmd(source: Optional[pathlib.Path]) -> None:
         click.echo(f"{source} already formatted")
 
 
+def _call_graph_dot(graph: dict[str, list[str]]) -> str:
+    lines = ["digraph calls {"]
+    lines.extend(f"    {json.dumps(name)};" for name in graph)
+    for caller, callees in graph.items():
+        lines.extend(f"    {json.dumps(caller)} -> {json.dumps(callee)};" for callee in callees)
+    lines.append("}")
+    return "\n".join(lines)
+
+
+@cli.command("graph", help="Print the call graph of a program in DOT format, or its recursive functions.")
+@click.argument("source", type=SCRIPTUM_FILE, required=True)
+@click.option("--calls", is_flag=True, help="Call graph: an edge 'f -> g' means function f calls g.")
+@click.option("--recursive", is_flag=True, help="List recursive functions, one mutually recursive group per line.")
+def graph_cmd(source: pathlib.Path, calls: bool, recursive: bool) -> None:
+    if not calls and not recursive:
+        raise click.UsageError("Choose what to print: --calls, --recursive, or both.")
+    result = _run_driver(source, Stage.PARSER)
+    graph = call_graph(result.ast) if result.ast else {}
+    if calls:
+        click.echo(_call_graph_dot(graph))
+    if recursive:
+        for group in recursive_functions(graph):
+            click.echo(", ".join(group))
+
+
+@cli.command("lsp", help="Serve the Language Server Protocol over stdio: diagnostics and hover.")
+@click.pass_context
+def lsp_cmd(ctx: click.Context) -> None:
+    server = LanguageServer(sys.stdin.buffer, sys.stdout.buffer, _project_config().analyzer_options())
+    ctx.exit(server.serve())
+
+
 @cli.command("test", help="Run the automated test suite.")
 @click.option("--unit/--no-unit", default=True, show_default=True, help="Run pytest.")
 @click.option("--smoke/--no-smoke", default=False, show_default=True, help="Run smoke-test scripts.")
@@ -588,14 +891,22 @@ def dev_group() -> None:
     """Namespace for development utilities."""
 
 
-def _lex_impl(source: pathlib.Path) -> None:
+def _lex_impl(source: pathlib.Path, output_format: str = "json") -> None:
     result = _run_driver(source, Stage.LEXER)
-    payload = [
-        _token_to_json(token)
-        for token in (result.tokens or [])
-        if token.kind is not tokens.TokenKind.EOF
-    ]
-    click.echo(json.dumps(payload, ensure_ascii=False, indent=2))
+    if output_format == "text":
+        for token in _significant_tokens(result):
+            start, end = _token_positions(token, result.source.text)
+            click.echo(f"{start[0]}:{start[1]}-{end[0]}:{end[1]} {token.kind.name} {json.dumps(token.lexeme)}")
+        return
+    click.echo(json.dumps(_tokens_to_json(result), ensure_ascii=False, indent=2))
+
+
+def _significant_tokens(result: CompilerDriver.Result) -> list[tokens.Token]:
+    return [token for token in (result.tokens or []) if token.kind is not tokens.TokenKind.EOF]
+
+
+def _tokens_to_json(result: CompilerDriver.Result) -> list[dict[str, Any]]:
+    return [_token_to_json(token, result.source.text) for token in _significant_tokens(result)]
 
 
 def _ast_impl(source: pathlib.Path) -> None:
@@ -623,10 +934,18 @@ def _build_lexer_tables() -> None:
     click.echo("AFD gerado com sucesso.")
 
 
-@dev_group.command("lex", help="Tokenize a file and emit JSON.")
+@dev_group.command("lex", help="Tokenize a file and list the tokens with their positions.")
 @click.argument("source", type=SCRIPTUM_FILE, required=True)
-def dev_lex_cmd(source: pathlib.Path) -> None:
-    _lex_impl(source)
+@click.option(
+    "--format",
+    "output_format",
+    type=click.Choice(["json", "text"]),
+    default="json",
+    show_default=True,
+    help="JSON objects, or one 'line:col-line:col KIND \"lexeme\"' line per token.",
+)
+def dev_lex_cmd(source: pathlib.Path, output_format: str) -> None:
+    _lex_impl(source, output_format)
 
 
 @dev_group.command("ast", help="Emit the AST as JSON.")
@@ -702,18 +1021,6 @@ def legacy_ir_cmd(source: pathlib.Path) -> None:
     _ir_impl(source)
 
 
-@cli.command("compile", hidden=True)
-@click.argument("source", type=SCRIPTUM_FILE, required=True)
-@click.option(
-    "--stage",
-    type=click.Choice([stage.value for stage in Stage]),
-    default=Stage.CODEGEN.value,
-    show_default=True,
-    help="Pipeline stage to stop after.",
-)
-def legacy_compile_cmd(source: pathlib.Path, stage: str) -> None:
-    _warn_legacy("scriptum compile", "scriptum build")
-    _run_driver(source, Stage(stage))
 
 
 @cli.command("build-lexer", hidden=True)
@@ -727,12 +1034,22 @@ def version_cmd() -> None:
     click.echo(f"Scriptum CLI version {__version__}")
 
 
-def _token_to_json(token: tokens.Token) -> dict[str, Any]:
+def _token_positions(token: tokens.Token, source_text: str) -> tuple[tuple[int, int], tuple[int, int]]:
+    """1-based (line, column) of the token's first character and of the position just after it."""
+
+    end = Span(token.span.end, token.span.end)
+    return line_col(source_text, token.span), line_col(source_text, end)
+
+
+def _token_to_json(token: tokens.Token, source_text: str) -> dict[str, Any]:
+    start, end = _token_positions(token, source_text)
     return {
         "kind": token.kind.name,
         "lexeme": token.lexeme,
         "value": token.value,
         "span": [token.span.start, token.span.end],
+        "start": {"line": start[0], "column": start[1]},
+        "end": {"line": end[0], "column": end[1]},
     }
 
 
@@ -740,6 +1057,7 @@ def _diagnostic_to_json(diagnostic, source_text: Optional[str]) -> dict[str, Any
     span = diagnostic.span if diagnostic.span else None
     payload = {
         "code": getattr(diagnostic, "code", ""),
+        "severity": diagnostic.severity.value if hasattr(diagnostic, "severity") else "error",
         "message": diagnostic.message if hasattr(diagnostic, "message") else str(diagnostic),
         "span": [span.start, span.end] if span else None,
     }
@@ -748,6 +1066,9 @@ def _diagnostic_to_json(diagnostic, source_text: Optional[str]) -> dict[str, Any
         line, column = line_col(source_text, span)
         payload["position"] = {"line": line, "column": column}
         payload["highlight"] = highlight_span(source_text, span)
+    notes = getattr(diagnostic, "notes", ())
+    if notes:
+        payload["notes"] = [{"message": label, "span": [note.start, note.end]} for label, note in notes]
     return payload
 
 
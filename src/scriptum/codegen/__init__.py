@@ -1,6 +1,16 @@
 from __future__ import annotations
 
 from .emitter import CodeEmitter
-from .generate import CodegenOutput, generate
+from .generate import CodegenOptions, CodegenOutput, generate
+from .javascript import generate_js, generate_js_with_map
+from .sourcemap import SourceMap
 
-__all__ = ["CodeEmitter", "CodegenOutput", "generate"]
+__all__ = [
+    "CodeEmitter",
+    "CodegenOptions",
+    "CodegenOutput",
+    "SourceMap",
+    "generate",
+    "generate_js",
+    "generate_js_with_map",
+]
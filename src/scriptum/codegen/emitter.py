@@ -4,16 +4,23 @@ Code generation scaffolding for Scriptum.
 
 from __future__ import annotations
 
-from typing import List, Optional, Tuple
+import json
+import re
+import unicodedata
+from functools import lru_cache
+from typing import Callable, List, Optional, Tuple
 
+from .. import errors
 from ..ir import (
     IrArrayLiteral,
     IrAssignment,
     IrBinary,
     IrBreak,
     IrCall,
+    IrCast,
     IrConditional,
     IrContinue,
+    IrDestructuring,
     IrExpr,
     IrExpressionStatement,
     IrForIn,
@@ -21,6 +28,7 @@ from ..ir import (
     IrFunction,
     IrIdentifier,
     IrIf,
+    IrImport,
     IrIndex,
     IrLambda,
     IrLiteral,
@@ -29,32 +37,51 @@ from ..ir import (
     IrObjectLiteral,
     IrObjectProperty,
     IrParameter,
+    IrRange,
     IrReturn,
     IrStatement,
+    IrTupleLiteral,
     IrUnary,
+    IrUpdate,
     IrVariable,
     IrVariableDeclaration,
     IrWhile,
     ModuleIr,
 )
+from ..lexer.lexer import ScriptumLexer
+from ..text import SourceFile
+from ..tokens import TokenKind, property_key
 
 
 class CodeEmitter:
     """Produces Scriptum source code from the lowered IR."""
 
-    _INDENT = "    "
+    def __init__(self, minify: bool = False, indent: str = "    ", max_width: Optional[int] = None) -> None:
+        self.minify = minify
+        self.indent = indent
+        self.max_width = max_width
+        # Set while re-emitting a statement that is too long; the first list emitted takes it.
+        self._break_list = False
 
     def emit(self, module: ModuleIr) -> str:
-        lines: List[str] = []
+        formatted = self._emit_formatted(module)
+        return _minify(formatted) if self.minify else formatted
+
+    def _emit_formatted(self, module: ModuleIr) -> str:
+        lines: List[str] = [self._emit_import(imp) for imp in module.imports]
+        if module.imports and (module.globals or module.functions):
+            lines.append("")
         for index, var in enumerate(module.globals):
-            lines.append(self._emit_variable(var))
+            lines.extend(self._emit_doc(var.doc))
+            lines.extend(self._fitted(lambda: [self._emit_variable(var)]))
         if module.globals and module.functions:
             lines.append("")
         for index, func in enumerate(module.functions):
+            lines.extend(self._emit_doc(func.doc))
             lines.extend(self._emit_function(func))
             if index != len(module.functions) - 1:
                 lines.append("")
-        if not module.globals and not module.functions:
+        if not lines:
             formatted = ""
         else:
             formatted = "\n".join(lines) + "\n"
@@ -62,11 +89,24 @@ class CodeEmitter:
 
     # Top-level declarations -------------------------------------------------
 
+    @staticmethod
+    def _emit_doc(doc: List[str]) -> List[str]:
+        return [f"/// {line}" if line else "///" for line in doc]
+
+    def _emit_import(self, imp: IrImport) -> str:
+        # String literal escapes are JSON's, so a path with quotes or backslashes reads back unchanged.
+        path = json.dumps(imp.path, ensure_ascii=False)
+        if imp.names:
+            names = ", ".join(f"{item.name} ut {item.alias}" if item.alias else item.name for item in imp.names)
+            return f"importa {{ {names} }} de {path};"
+        if imp.alias:
+            return f"importa {path} ut {imp.alias};"
+        return f"importa {path};"
+
     def _emit_variable(self, var: IrVariable) -> str:
-        parts = ["mutabilis" if var.mutable else "constans"]
-        if var.type_annotation:
-            parts.append(var.type_annotation)
-        parts.append(var.name)
+        parts = [var.visibility] if var.visibility else []
+        parts.append("mutabilis" if var.mutable else "constans")
+        parts.append(_binding(var.name, var.type_annotation))
         line = " ".join(parts)
         if var.initializer:
             line += f" = {self._emit_expression(var.initializer)}"
@@ -74,7 +114,10 @@ class CodeEmitter:
 
     def _emit_function(self, func: IrFunction) -> List[str]:
         params = ", ".join(self._format_parameter(param) for param in func.parameters)
-        header = f"functio {func.name}({params})"
+        generics = f"<{', '.join(func.type_parameters)}>" if func.type_parameters else ""
+        header = f"functio {func.name}{generics}({params})"
+        if func.visibility:
+            header = f"{func.visibility} {header}"
         if func.return_annotation:
             header += f" -> {func.return_annotation}"
         header += " {"
@@ -84,11 +127,7 @@ class CodeEmitter:
         return lines
 
     def _format_parameter(self, param: IrParameter) -> str:
-        pieces: List[str] = []
-        if param.type_annotation:
-            pieces.append(param.type_annotation)
-        pieces.append(param.name)
-        result = " ".join(pieces)
+        result = _binding(param.name, param.type_annotation)
         if param.default_value:
             result += f" = {self._emit_expression(param.default_value)}"
         return result
@@ -98,29 +137,43 @@ class CodeEmitter:
     def _emit_statements(self, statements: List[IrStatement], indent_level: int) -> List[str]:
         lines: List[str] = []
         for stmt in statements:
-            lines.extend(self._emit_statement(stmt, indent_level))
+            lines.extend(self._fitted(lambda: self._emit_statement(stmt, indent_level)))
         return lines
 
+    def _fitted(self, emit: Callable[[], List[str]]) -> List[str]:
+        """Run *emit*; a single line past `max_width` is emitted again with its outermost list broken."""
+
+        lines = emit()
+        if self.max_width is None or len(lines) != 1 or len(lines[0]) <= self.max_width:
+            return lines
+        self._break_list = True
+        try:
+            return emit()
+        finally:
+            self._break_list = False
+
     def _emit_statement(self, stmt: IrStatement, indent_level: int) -> List[str]:
-        indent = self._INDENT * indent_level
+        indent = self.indent * indent_level
 
         if isinstance(stmt, IrVariableDeclaration):
-            parts = ["mutabilis" if stmt.mutable else "constans"]
-            if stmt.type_annotation:
-                parts.append(stmt.type_annotation)
-            parts.append(stmt.name)
-            line = " ".join(parts)
+            keyword = "mutabilis" if stmt.mutable else "constans"
+            line = f"{keyword} {_binding(stmt.name, stmt.type_annotation)}"
             if stmt.initializer:
-                line += f" = {self._emit_expression(stmt.initializer)}"
+                line += f" = {self._emit_expression(stmt.initializer, indent_level=indent_level)}"
             return [f"{indent}{line};"]
 
+        if isinstance(stmt, IrDestructuring):
+            keyword = "mutabilis" if stmt.mutable else "constans"
+            value = self._emit_expression(stmt.initializer, indent_level=indent_level)
+            return [f"{indent}{keyword} ({', '.join(stmt.names)}) = {value};"]
+
         if isinstance(stmt, IrExpressionStatement):
-            expr = self._emit_expression(stmt.expression)
+            expr = self._emit_expression(stmt.expression, indent_level=indent_level)
             return [f"{indent}{expr};"]
 
         if isinstance(stmt, IrReturn):
             if stmt.value:
-                return [f"{indent}redde {self._emit_expression(stmt.value)};"]
+                return [f"{indent}redde {self._emit_expression(stmt.value, indent_level=indent_level)};"]
             return [f"{indent}redde;"]
 
         if isinstance(stmt, IrIf):
@@ -128,17 +181,26 @@ class CodeEmitter:
             condition = self._emit_expression(stmt.condition)
             lines.append(f"{indent}si ({condition}) {{")
             lines.extend(self._emit_statements(stmt.then_branch, indent_level + 1))
-            if stmt.else_branch:
+            else_branch = stmt.else_branch
+            # An else branch holding only another `si` continues the chain at the same depth.
+            while len(else_branch) == 1 and isinstance(else_branch[0], IrIf):
+                nested = else_branch[0]
+                lines.append(f"{indent}}} aliter si ({self._emit_expression(nested.condition)}) {{")
+                lines.extend(self._emit_statements(nested.then_branch, indent_level + 1))
+                else_branch = nested.else_branch
+            if else_branch:
                 lines.append(f"{indent}}} aliter {{")
-                lines.extend(self._emit_statements(stmt.else_branch, indent_level + 1))
-                lines.append(f"{indent}}}")
-            else:
-                lines.append(f"{indent}}}")
+                lines.extend(self._emit_statements(else_branch, indent_level + 1))
+            lines.append(f"{indent}}}")
             return lines
 
         if isinstance(stmt, IrWhile):
-            condition = self._emit_expression(stmt.condition)
-            lines = [f"{indent}dum ({condition}) {{"]  # while body
+            label = f"{stmt.label}: " if stmt.label else ""
+            if stmt.init is not None or stmt.update is not None or stmt.condition is None:
+                lines = [f"{indent}{label}pro ({self._format_c_style_header(stmt, indent_level)}) {{"]
+            else:
+                condition = self._emit_expression(stmt.condition)
+                lines = [f"{indent}{label}dum ({condition}) {{"]  # while body
             lines.extend(self._emit_statements(stmt.body, indent_level + 1))
             lines.append(f"{indent}}}")
             return lines
@@ -146,27 +208,35 @@ class CodeEmitter:
         if isinstance(stmt, IrForIn):
             target = self._format_for_target(stmt.target)
             iterable = self._emit_expression(stmt.iterable)
-            lines = [f"{indent}pro {target} in {iterable} {{"]
+            label = f"{stmt.label}: " if stmt.label else ""
+            lines = [f"{indent}{label}pro {target} in {iterable} {{"]
             lines.extend(self._emit_statements(stmt.body, indent_level + 1))
             lines.append(f"{indent}}}")
             return lines
 
         if isinstance(stmt, IrBreak):
-            return [f"{indent}frange;"]
+            return [f"{indent}frange {stmt.label};" if stmt.label else f"{indent}frange;"]
 
         if isinstance(stmt, IrContinue):
-            return [f"{indent}perge;"]
+            return [f"{indent}perge {stmt.label};" if stmt.label else f"{indent}perge;"]
 
         raise TypeError(f"Unsupported statement type: {type(stmt)!r}")
 
+    def _format_c_style_header(self, stmt: IrWhile, indent_level: int) -> str:
+        header = ""
+        if stmt.init is not None:
+            header = "\n".join(self._emit_statement(stmt.init, indent_level)).strip()[:-1]
+        header += ";"
+        if stmt.condition is not None:
+            header += f" {self._emit_expression(stmt.condition, indent_level=indent_level)}"
+        header += ";"
+        if stmt.update is not None:
+            header += f" {self._emit_expression(stmt.update, indent_level=indent_level)}"
+        return header
+
     def _format_for_target(self, target: IrForTarget) -> str:
-        parts: List[str] = []
-        if target.mutable:
-            parts.append("mutabilis")
-        if target.type_annotation:
-            parts.append(target.type_annotation)
-        parts.append(target.name)
-        return " ".join(parts)
+        binding = _binding(target.name, target.type_annotation)
+        return f"mutabilis {binding}" if target.mutable else binding
 
     # Expressions ------------------------------------------------------------
 
@@ -181,17 +251,44 @@ class CodeEmitter:
             return expr.name
 
         if isinstance(expr, IrLiteral):
+            if isinstance(expr.value, str) and expr.raw[:1] in {'"', "'"}:
+                return _string_literal(expr.raw)
             return expr.raw
 
         if isinstance(expr, IrUnary):
             operator = self._unary_symbol(expr.operator)
             operand = self._emit_expression(expr.operand, self._precedence_unary(), "right", indent_level)
+            if operator in {"-", "+"} and operand[:1] in {"-", "+"}:
+                # `- -x` must not collapse into the `--` token.
+                operand = f"({operand})"
             text = f"{operator}{operand}"
             return self._maybe_parenthesize(text, self._precedence_unary(), parent_prec, "right", position)
 
+        if isinstance(expr, IrUpdate):
+            symbol = "++" if expr.operator == "INCREMENT" else "--"
+            if expr.prefix:
+                text = f"{symbol}{expr.target.name}"
+                return self._maybe_parenthesize(text, self._precedence_unary(), parent_prec, "right", position)
+            return f"{expr.target.name}{symbol}"
+
+        if isinstance(expr, IrCast):
+            prec, assoc = 7, "left"
+            value = self._emit_expression(expr.value, prec, "left", indent_level)
+            return self._maybe_parenthesize(f"{value} ut {expr.type_name}", prec, parent_prec, assoc, position)
+
+        if isinstance(expr, IrRange):
+            prec, assoc = 7, "left"
+            start = self._emit_expression(expr.start, prec, "left", indent_level)
+            end = self._emit_expression(expr.end, prec, "right", indent_level)
+            operator = "..=" if expr.inclusive else ".."
+            return self._maybe_parenthesize(f"{start}{operator}{end}", prec, parent_prec, assoc, position)
+
         if isinstance(expr, IrBinary):
             symbol, prec, assoc = self._binary_metadata(expr.operator)
             left = self._emit_expression(expr.left, prec, "left", indent_level)
+            if symbol == "**" and _is_prefix_operation(expr.left):
+                # A prefix operator takes the whole power as its operand: `-x ** 2` reads as `-(x ** 2)`.
+                left = f"({left})"
             right = self._emit_expression(expr.right, prec, "right", indent_level)
             text = f"{left} {symbol} {right}"
             return self._maybe_parenthesize(text, prec, parent_prec, assoc, position)
@@ -213,67 +310,105 @@ class CodeEmitter:
 
         if isinstance(expr, IrCall):
             prec, assoc = 14, "left"
+            leading = expr.arguments[:-1] if expr.trailing_lambda else expr.arguments
+            # Arguments first: when a long line is broken, they are the outermost list.
+            arguments = self._emit_list(leading, indent_level)
             callee = self._emit_expression(expr.callee, prec, "left", indent_level)
-            arguments = ", ".join(self._emit_expression(arg, 0, "any", indent_level) for arg in expr.arguments)
-            text = f"{callee}({arguments})"
+            generics = f"<{', '.join(expr.type_arguments)}>" if expr.type_arguments else ""
+            text = f"{callee}{generics}({arguments})"
+            if expr.trailing_lambda:
+                lambda_text = self._emit_expression(expr.arguments[-1], 0, "any", indent_level)
+                text = f"{callee} {lambda_text}" if not leading and not generics else f"{text} {lambda_text}"
             return self._maybe_parenthesize(text, prec, parent_prec, assoc, position)
 
         if isinstance(expr, IrMemberAccess):
             prec, assoc = 15, "left"
             obj = self._emit_expression(expr.object, prec, "left", indent_level)
-            text = f"{obj}.{expr.property}"
+            text = f"{obj}{'?.' if expr.optional else '.'}{expr.property}"
             return self._maybe_parenthesize(text, prec, parent_prec, assoc, position)
 
         if isinstance(expr, IrIndex):
             prec, assoc = 15, "left"
             collection = self._emit_expression(expr.collection, prec, "left", indent_level)
             index = self._emit_expression(expr.index, 0, "any", indent_level)
-            text = f"{collection}[{index}]"
+            text = f"{collection}{'?[' if expr.optional else '['}{index}]"
             return self._maybe_parenthesize(text, prec, parent_prec, assoc, position)
 
         if isinstance(expr, IrArrayLiteral):
-            elements = ", ".join(self._emit_expression(elem, 0, "any", indent_level) for elem in expr.elements)
-            return f"[{elements}]"
+            return f"[{self._emit_list(expr.elements, indent_level)}]"
+
+        if isinstance(expr, IrTupleLiteral):
+            return f"({self._emit_list(expr.elements, indent_level)})"
 
         if isinstance(expr, IrObjectLiteral):
-            props = ", ".join(self._format_object_property(prop, indent_level) for prop in expr.properties)
-            return f"structura {{ {props} }}"
+            return self._emit_object(expr, indent_level)
 
         if isinstance(expr, IrLambda):
             params = ", ".join(self._format_parameter(param) for param in expr.parameters)
+            header = f"functio ({params})"
+            if expr.return_annotation:
+                header += f" -> {expr.return_annotation}"
             if expr.body_expression is not None:
                 body = self._emit_expression(expr.body_expression, 0, "any", indent_level)
-                text = f"functio ({params}) => {body}"
+                text = f"{header} => {body}"
                 return self._maybe_parenthesize(text, 3, parent_prec, "right", position)
 
             statements = self._emit_statements(expr.body_statements, indent_level + 1)
-            if statements:
-                body = "\n".join(statements)
-                closing = self._INDENT * indent_level + "}"
-                opening = f"functio ({params}) {{"
-                text = "\n".join([self._INDENT * indent_level + opening] + statements + [closing])
-            else:
-                opening = f"functio ({params}) {{"
-                closing = self._INDENT * indent_level + "}"
-                text = "\n".join([self._INDENT * indent_level + opening, closing])
-            return text
+            opening = f"{header} {{"
+            closing = self.indent * indent_level + "}"
+            return "\n".join([opening] + statements + [closing])
 
         raise TypeError(f"Unsupported expression type: {type(expr)!r}")
 
+    def _emit_list(self, items: List[IrExpr], indent_level: int) -> str:
+        """Join *items* with commas, or put one per line if this is the list a long statement breaks."""
+
+        if not (self._break_list and items):
+            return ", ".join(self._emit_expression(item, 0, "any", indent_level) for item in items)
+        self._break_list = False
+        inner = self.indent * (indent_level + 1)
+        rendered = [inner + self._emit_expression(item, 0, "any", indent_level + 1) for item in items]
+        return "\n" + ",\n".join(rendered) + "\n" + self.indent * indent_level
+
+    def _emit_object(self, expr: IrObjectLiteral, indent_level: int) -> str:
+        """
+        `structura { a: 1, b: 2 }`, or one property per line when a long statement breaks it.
+
+        A property with a comment always breaks its object, and the objects it is nested in,
+        so the comment can close the property's line.
+        """
+
+        if not expr.properties or not (_has_property_comments(expr) or self._break_list):
+            props = ", ".join(self._format_object_property(prop, indent_level) for prop in expr.properties)
+            return f"structura {{ {props} }}"
+        self._break_list = False
+        inner = self.indent * (indent_level + 1)
+        lines: List[str] = []
+        for index, prop in enumerate(expr.properties):
+            line = inner + self._format_object_property(prop, indent_level + 1)
+            if index != len(expr.properties) - 1:
+                line += ","
+            if prop.comment is not None:
+                line += f" // {prop.comment}" if prop.comment else " //"
+            lines.append(line)
+        return "structura {\n" + "\n".join(lines) + "\n" + self.indent * indent_level + "}"
+
     def _format_object_property(self, prop: IrObjectProperty, indent_level: int) -> str:
         value = self._emit_expression(prop.value, 0, "any", indent_level)
-        return f"{prop.key}: {value}"
+        return f"{property_key(prop.key)}: {value}"
 
     # Operator metadata ------------------------------------------------------
 
     def _binary_metadata(self, operator: str) -> Tuple[str, int, str]:
         mapping = {
-            "ADD": ("+", 8, "left"),
-            "SUB": ("-", 8, "left"),
-            "MUL": ("*", 9, "left"),
-            "DIV": ("/", 9, "left"),
-            "MOD": ("%", 9, "left"),
-            "POW": ("**", 10, "right"),
+            "SHL": ("<<", 8, "left"),
+            "SHR": (">>", 8, "left"),
+            "ADD": ("+", 9, "left"),
+            "SUB": ("-", 9, "left"),
+            "MUL": ("*", 10, "left"),
+            "DIV": ("/", 10, "left"),
+            "MOD": ("%", 10, "left"),
+            "POW": ("**", 11, "right"),
             "OR": ("||", 4, "left"),
             "AND": ("&&", 5, "left"),
             "NULLISH": ("??", 3, "left"),
@@ -302,13 +437,15 @@ class CodeEmitter:
             ">=": 7,
             "<": 7,
             "<=": 7,
-            "+": 8,
-            "-": 8,
-            "*": 9,
-            "/": 9,
-            "%": 9,
-            "**": 10,
-        }.get(symbol, 8)
+            "<<": 8,
+            ">>": 8,
+            "+": 9,
+            "-": 9,
+            "*": 10,
+            "/": 10,
+            "%": 10,
+            "**": 11,
+        }.get(symbol, 9)
         assoc = "right" if symbol == "**" else "left"
         return symbol, precedence, assoc
 
@@ -317,11 +454,12 @@ class CodeEmitter:
             "NEGATE": "-",
             "POSITIVE": "+",
             "NOT": "!",
+            "BIT_NOT": "~",
         }
         return mapping.get(operator, operator)
 
     def _precedence_unary(self) -> int:
-        return 11
+        return 12
 
     def _maybe_parenthesize(self, text: str, prec: int, parent_prec: int, assoc: str, position: str) -> str:
         need = False
@@ -333,3 +471,88 @@ class CodeEmitter:
             elif assoc == "right" and position == "left":
                 need = True
         return f"({text})" if need else text
+
+
+def _is_prefix_operation(expr: IrExpr) -> bool:
+    return isinstance(expr, IrUnary) or (isinstance(expr, IrUpdate) and expr.prefix)
+
+
+def _has_property_comments(expr: IrObjectLiteral) -> bool:
+    return any(
+        prop.comment is not None or (isinstance(prop.value, IrObjectLiteral) and _has_property_comments(prop.value))
+        for prop in expr.properties
+    )
+
+
+_STRING_PIECE = re.compile(r"\\u[0-9a-fA-F]{4}|\\.|.", re.S)
+
+
+def _string_literal(raw: str) -> str:
+    """
+    Re-quote the string literal *raw* with the delimiter that needs fewer escapes.
+
+    `"` wins ties, so `'abc'` becomes `"abc"` while `'diz "oi"'` keeps its single quotes.
+    Only quote escapes change; `\\n`, `\\u00e9` and the rest are written as in the source.
+    """
+
+    pieces = _STRING_PIECE.findall(raw[1:-1])
+    doubles = sum(piece in {'"', '\\"'} for piece in pieces)
+    singles = sum(piece in {"'", "\\'"} for piece in pieces)
+    quote = "'" if singles < doubles else '"'
+    body = []
+    for piece in pieces:
+        if piece in {'"', '\\"', "'", "\\'"}:
+            char = piece[-1]
+            piece = "\\" + char if char == quote else char
+        body.append(piece)
+    return quote + "".join(body) + quote
+
+
+def _binding(name: str, annotation: Optional[str]) -> str:
+    """
+    `numerus x` for a one-word annotation, `x: numerus[]` otherwise.
+
+    The parser only reads a single token as a type prefix, so compound types
+    such as `numerus[]` or `(numerus)?` must use the `:` form to round-trip.
+    """
+
+    if not annotation:
+        return name
+    if annotation.isidentifier():
+        return f"{annotation} {name}"
+    return f"{name}: {annotation}"
+
+
+def _minify(formatted: str) -> str:
+    """
+    Re-lex *formatted* and join its tokens on a single line.
+
+    A space is kept only where two neighbouring tokens would otherwise lex as
+    something else (`functio main`, `- -x`), so the result still parses to the
+    same module. Comments, including `///` docs, are dropped.
+    """
+
+    text = unicodedata.normalize("NFKC", formatted)
+    pieces: List[str] = []
+    previous: Optional[str] = None
+    for token in _MINIFY_LEXER.tokenize(SourceFile("<minify>", text)):
+        if token.kind is TokenKind.EOF:
+            break
+        lexeme = text[token.span.start : token.span.end]
+        if previous is not None and _needs_separator(previous, lexeme):
+            pieces.append(" ")
+        pieces.append(lexeme)
+        previous = lexeme
+    return "".join(pieces)
+
+
+_MINIFY_LEXER = ScriptumLexer()
+
+
+@lru_cache(maxsize=None)
+def _needs_separator(left: str, right: str) -> bool:
+    try:
+        glued = _MINIFY_LEXER.tokenize(SourceFile("<minify>", left + right))
+    except errors.LexerError:
+        return True
+    return len(glued) != 3 or glued[0].span.end != len(left)
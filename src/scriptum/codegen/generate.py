@@ -1,23 +1,35 @@
 from __future__ import annotations
 
 from dataclasses import dataclass
-from typing import Union
+from typing import Optional, Union
 
 from ..ast import nodes
 from ..ir import ModuleIr, lower_module
 from .emitter import CodeEmitter
 
 
+@dataclass(slots=True)
+class CodegenOptions:
+    """Knobs for `generate`; the defaults produce the canonical formatting."""
+
+    minify: bool = False
+    # One level of indentation: spaces or a tab.
+    indent: str = "    "
+    # Statements longer than this break their outermost argument or element list; None never breaks.
+    max_width: Optional[int] = None
+
+
 @dataclass(slots=True)
 class CodegenOutput:
     ir: ModuleIr
     formatted: str
 
 
-def generate(module: Union[nodes.Module, ModuleIr]) -> CodegenOutput:
-    """Lower *module* to IR if needed and pretty-print it."""
+def generate(module: Union[nodes.Module, ModuleIr], options: Optional[CodegenOptions] = None) -> CodegenOutput:
+    """Lower *module* to IR if needed and pretty-print it (or minify it, see `CodegenOptions`)."""
 
+    options = options or CodegenOptions()
     ir_module = module if isinstance(module, ModuleIr) else lower_module(module)
-    emitter = CodeEmitter()
+    emitter = CodeEmitter(minify=options.minify, indent=options.indent, max_width=options.max_width)
     formatted = emitter.emit(ir_module)
     return CodegenOutput(ir=ir_module, formatted=formatted)
@@ -0,0 +1,565 @@
+"""
+JavaScript backend for Scriptum.
+
+Translates the structural IR into ES2020 source. Every top-level item,
+statement and expression that starts a piece of output is recorded in a
+`SourceMap`, so generated code can be traced back to the original `.stm` file.
+Modules that use `importa` are rejected with `CompilerNotImplemented`.
+"""
+
+from __future__ import annotations
+
+import pathlib
+from typing import List, Optional, Tuple
+
+from .. import errors
+from ..ir import (
+    IrArrayLiteral,
+    IrAssignment,
+    IrBinary,
+    IrBreak,
+    IrCall,
+    IrCast,
+    IrConditional,
+    IrContinue,
+    IrDestructuring,
+    IrExpr,
+    IrExpressionStatement,
+    IrForIn,
+    IrFunction,
+    IrIdentifier,
+    IrIf,
+    IrIndex,
+    IrLambda,
+    IrLiteral,
+    IrMemberAccess,
+    IrObjectLiteral,
+    IrParameter,
+    IrRange,
+    IrReturn,
+    IrStatement,
+    IrTupleLiteral,
+    IrUnary,
+    IrUpdate,
+    IrVariable,
+    IrVariableDeclaration,
+    IrWhile,
+    ModuleIr,
+)
+from ..ir.passes import mentions_name
+from ..text import SourceFile, Span
+from ..tokens import NUMBER_SUFFIXES, property_key
+from .sourcemap import SourceMap
+
+_KEYWORD_LITERALS = {
+    "verum": "true",
+    "falsum": "false",
+    "nullum": "null",
+    "indefinitum": "undefined",
+    "infinitum": "Infinity",
+    "nan": "NaN",
+}
+
+# (symbol, precedence, associativity); precedences mirror CodeEmitter.
+_BINARY_OPERATORS = {
+    "SHL": ("<<", 8, "left"),
+    "SHR": (">>", 8, "left"),
+    "ADD": ("+", 9, "left"),
+    "SUB": ("-", 9, "left"),
+    "MUL": ("*", 10, "left"),
+    "DIV": ("/", 10, "left"),
+    "MOD": ("%", 10, "left"),
+    "POW": ("**", 11, "right"),
+    "OR": ("||", 4, "left"),
+    "AND": ("&&", 5, "left"),
+    "NULLISH": ("??", 3, "left"),
+    "EQ": ("===", 6, "left"),
+    "NE": ("!==", 6, "left"),
+    "STRICT_EQ": ("===", 6, "left"),
+    "STRICT_NE": ("!==", 6, "left"),
+    "GT": (">", 7, "left"),
+    "GE": (">=", 7, "left"),
+    "LT": ("<", 7, "left"),
+    "LE": ("<=", 7, "left"),
+}
+
+_UNARY_OPERATORS = {"NEGATE": "-", "POSITIVE": "+", "NOT": "!", "BIT_NOT": "~"}
+
+# Lazily yields start, start + 1, ... below end, mirroring the interpreter's ranges.
+_RANGE_HELPER = "(function* (s, e) { for (let i = s; i < e; i++) yield i; })"
+_INCLUSIVE_RANGE_HELPER = "(function* (s, e) { for (let i = s; i <= e; i++) yield i; })"
+
+# Builtins from `sema.prelude`, defined at the top of modules that call them without declaring the name.
+_NATIVE_HELPERS = {
+    "imprime": "function imprime(...valores) {\n  console.log(...valores);\n}\n",
+}
+
+_UNARY_PRECEDENCE = 12
+_CALL_PRECEDENCE = 15
+_MEMBER_PRECEDENCE = 15
+
+
+class JsEmitter:
+    """Writes JavaScript while tracking the output position of every node."""
+
+    _INDENT = "  "
+
+    def __init__(self, source: SourceFile) -> None:
+        self._source = source
+        self._chunks: List[str] = []
+        self._line = 0
+        self._column = 0
+        self.source_map = SourceMap(
+            source=pathlib.Path(source.path).name if source.path else "<stdin>",
+            source_content=source.text,
+        )
+        # True while emitting the object, callee or collection of a member access, call or index.
+        self._in_chain = False
+
+    def emit(self, module: ModuleIr) -> str:
+        if module.imports:
+            raise errors.CompilerNotImplemented(
+                f"The JavaScript backend does not support 'importa' yet (imports '{module.imports[0].path}')"
+            )
+        declared = {var.name for var in module.globals} | {func.name for func in module.functions}
+        for name, helper in _NATIVE_HELPERS.items():
+            if name not in declared and mentions_name(module, name):
+                self._write(helper)
+                self._newline()
+        for var in module.globals:
+            self._emit_variable(var, indent_level=0)
+        if module.globals and module.functions:
+            self._newline()
+        for index, func in enumerate(module.functions):
+            if index:
+                self._newline()
+            self._emit_function(func)
+        return "".join(self._chunks)
+
+    # Output tracking ----------------------------------------------------------
+
+    def _write(self, text: str, span: Optional[Span] = None) -> None:
+        if span is not None:
+            line, column = self._source.line_col(span)
+            self.source_map.add(self._line, self._column, line - 1, column - 1)
+        self._chunks.append(text)
+        newlines = text.count("\n")
+        if newlines:
+            self._line += newlines
+            self._column = len(text) - text.rfind("\n") - 1
+        else:
+            self._column += len(text)
+
+    def _newline(self) -> None:
+        self._write("\n")
+
+    def _indent(self, level: int) -> None:
+        self._write(self._INDENT * level)
+
+    # Declarations -------------------------------------------------------------
+
+    def _emit_doc(self, doc: List[str]) -> None:
+        if not doc:
+            return
+        self._write("/**")
+        self._newline()
+        for line in doc:
+            # `*/` in the text would end the comment early.
+            self._write(f" * {line}".rstrip().replace("*/", "*\\/"))
+            self._newline()
+        self._write(" */")
+        self._newline()
+
+    def _emit_variable(self, var: IrVariable | IrVariableDeclaration, indent_level: int) -> None:
+        if isinstance(var, IrVariable):
+            self._emit_doc(var.doc)
+        self._indent(indent_level)
+        self._write("let " if var.mutable else "const ", var.span)
+        self._write(var.name)
+        if var.initializer is not None:
+            self._write(" = ")
+            self._emit_expression(var.initializer, indent_level=indent_level)
+        self._write(";")
+        self._newline()
+
+    def _emit_for_header(self, loop: IrWhile, indent_level: int) -> None:
+        init = loop.init
+        if isinstance(init, IrVariableDeclaration):
+            self._write("let " if init.mutable else "const ", init.span)
+            self._write(init.name)
+            if init.initializer is not None:
+                self._write(" = ")
+                self._emit_expression(init.initializer, indent_level=indent_level)
+        elif isinstance(init, IrExpressionStatement):
+            self._emit_expression(init.expression, indent_level=indent_level)
+        self._write(";")
+        if loop.condition is not None:
+            self._write(" ")
+            self._emit_expression(loop.condition, indent_level=indent_level)
+        self._write(";")
+        if loop.update is not None:
+            self._write(" ")
+            self._emit_expression(loop.update, indent_level=indent_level)
+
+    def _emit_function(self, func: IrFunction) -> None:
+        self._emit_doc(func.doc)
+        self._write("function ", func.span)
+        self._write(func.name)
+        self._emit_parameters(func.parameters)
+        self._write(" {")
+        self._newline()
+        self._emit_statements(func.body, indent_level=1)
+        self._write("}")
+        self._newline()
+
+    def _emit_parameters(self, parameters: List[IrParameter]) -> None:
+        self._write("(")
+        for index, param in enumerate(parameters):
+            if index:
+                self._write(", ")
+            self._write(param.name, param.span)
+            if param.default_value is not None:
+                self._write(" = ")
+                self._emit_expression(param.default_value)
+        self._write(")")
+
+    # Statements ---------------------------------------------------------------
+
+    def _emit_statements(self, statements: List[IrStatement], indent_level: int) -> None:
+        for stmt in statements:
+            self._emit_statement(stmt, indent_level)
+
+    def _emit_block(self, statements: List[IrStatement], indent_level: int) -> None:
+        self._write("{")
+        self._newline()
+        self._emit_statements(statements, indent_level + 1)
+        self._indent(indent_level)
+        self._write("}")
+
+    def _emit_statement(self, stmt: IrStatement, indent_level: int) -> None:
+        if isinstance(stmt, IrVariableDeclaration):
+            self._emit_variable(stmt, indent_level)
+            return
+
+        self._indent(indent_level)
+        if isinstance(stmt, IrDestructuring):
+            # Tuples are plain arrays in JavaScript.
+            self._write("let [" if stmt.mutable else "const [", stmt.span)
+            self._write(", ".join(stmt.names))
+            self._write("] = ")
+            self._emit_expression(stmt.initializer, indent_level=indent_level)
+            self._write(";")
+            self._newline()
+            return
+        if isinstance(stmt, IrExpressionStatement):
+            # A leading '{' or 'function' would be read as a block or declaration.
+            wrap = isinstance(stmt.expression, (IrObjectLiteral, IrLambda))
+            self._write("(" if wrap else "", stmt.span)
+            self._emit_expression(stmt.expression, indent_level=indent_level)
+            self._write(");" if wrap else ";")
+        elif isinstance(stmt, IrReturn):
+            self._write("return", stmt.span)
+            if stmt.value is not None:
+                self._write(" ")
+                self._emit_expression(stmt.value, indent_level=indent_level)
+            self._write(";")
+        elif isinstance(stmt, IrIf):
+            self._write("if (", stmt.span)
+            self._emit_expression(stmt.condition, indent_level=indent_level)
+            self._write(") ")
+            self._emit_block(stmt.then_branch, indent_level)
+            if stmt.else_branch:
+                self._write(" else ")
+                self._emit_block(stmt.else_branch, indent_level)
+        elif isinstance(stmt, IrWhile):
+            if stmt.label:
+                self._write(f"{stmt.label}: ", stmt.span)
+            if stmt.init is None and stmt.update is None and stmt.condition is not None:
+                self._write("while (", stmt.span)
+                self._emit_expression(stmt.condition, indent_level=indent_level)
+            else:
+                self._write("for (", stmt.span)
+                self._emit_for_header(stmt, indent_level)
+            self._write(") ")
+            self._emit_block(stmt.body, indent_level)
+        elif isinstance(stmt, IrForIn):
+            if stmt.label:
+                self._write(f"{stmt.label}: ", stmt.span)
+            self._write("for (", stmt.span)
+            self._write("let " if stmt.target.mutable else "const ")
+            self._write(stmt.target.name, stmt.target.span)
+            self._write(" of ")
+            self._emit_expression(stmt.iterable, indent_level=indent_level)
+            self._write(") ")
+            self._emit_block(stmt.body, indent_level)
+        elif isinstance(stmt, IrBreak):
+            self._write(f"break {stmt.label};" if stmt.label else "break;", stmt.span)
+        elif isinstance(stmt, IrContinue):
+            self._write(f"continue {stmt.label};" if stmt.label else "continue;", stmt.span)
+        else:
+            raise TypeError(f"Unsupported statement type: {type(stmt)!r}")
+        self._newline()
+
+    # Expressions --------------------------------------------------------------
+
+    def _emit_expression(
+        self,
+        expr: IrExpr,
+        parent_prec: int = 0,
+        position: str = "any",
+        indent_level: int = 0,
+    ) -> None:
+        if isinstance(expr, IrCast):
+            # Casts only inform the checker; JavaScript sees the bare value.
+            self._emit_expression(expr.value, parent_prec, position, indent_level)
+            return
+        chained, self._in_chain = self._in_chain, False
+        if not chained and _has_optional_link(expr):
+            # JavaScript short-circuits an optional chain to undefined; Scriptum makes it nullum.
+            self._write("(")
+            self._emit_bare_expression(expr, _MEMBER_PRECEDENCE, indent_level)
+            self._write(" ?? null)")
+            return
+        prec, assoc = self._precedence(expr)
+        wrap = _needs_parentheses(prec, parent_prec, assoc, position)
+        if wrap:
+            self._write("(")
+        self._emit_bare_expression(expr, prec, indent_level)
+        if wrap:
+            self._write(")")
+
+    def _emit_chain_link(self, expr: IrExpr, prec: int, indent_level: int) -> None:
+        self._in_chain = True
+        self._emit_expression(expr, prec, "left", indent_level)
+
+    def _emit_bare_expression(self, expr: IrExpr, prec: int, indent_level: int) -> None:
+        if isinstance(expr, IrIdentifier):
+            # `ipse` is a keyword, so it can only be the receiver of the enclosing method.
+            self._write("this" if expr.name == "ipse" else expr.name, expr.span)
+        elif isinstance(expr, IrLiteral):
+            raw = expr.raw
+            if isinstance(expr.value, (int, float)) and raw.endswith(NUMBER_SUFFIXES):
+                # JavaScript has no `5i`/`5f`; the suffix only records the intent.
+                raw = raw[:-1]
+            self._write(_KEYWORD_LITERALS.get(raw, raw), expr.span)
+        elif isinstance(expr, IrUnary):
+            self._write(_UNARY_OPERATORS.get(expr.operator, expr.operator), expr.span)
+            # Keep `- -x` from turning into the `--` token.
+            operand_prec = _CALL_PRECEDENCE if isinstance(_strip_casts(expr.operand), IrUnary) else prec
+            self._emit_expression(expr.operand, operand_prec, "right", indent_level)
+        elif isinstance(expr, IrUpdate):
+            symbol = "++" if expr.operator == "INCREMENT" else "--"
+            if expr.prefix:
+                self._write(symbol, expr.span)
+                self._write(expr.target.name, expr.target.span)
+            else:
+                self._write(expr.target.name, expr.span)
+                self._write(symbol)
+        elif isinstance(expr, IrRange):
+            self._write((_INCLUSIVE_RANGE_HELPER if expr.inclusive else _RANGE_HELPER) + "(", expr.span)
+            self._emit_list([expr.start, expr.end], indent_level)
+            self._write(")")
+        elif isinstance(expr, IrBinary):
+            self._emit_binary(expr, prec, indent_level)
+        elif isinstance(expr, IrAssignment):
+            self._emit_expression(expr.target, prec, "left", indent_level)
+            self._write(" = ", expr.span)
+            self._emit_expression(expr.value, prec, "right", indent_level)
+        elif isinstance(expr, IrConditional):
+            self._emit_expression(expr.condition, prec + 1, "left", indent_level)
+            self._write(" ? ", expr.span)
+            self._emit_expression(expr.consequent, prec, "right", indent_level)
+            self._write(" : ")
+            self._emit_expression(expr.alternate, prec, "right", indent_level)
+        elif isinstance(expr, IrCall):
+            self._emit_chain_link(expr.callee, prec, indent_level)
+            self._write("?.(" if _follows_guarded_index(expr.callee) else "(", expr.span)
+            self._emit_list(expr.arguments, indent_level)
+            self._write(")")
+        elif isinstance(expr, IrMemberAccess):
+            self._emit_chain_link(expr.object, prec, indent_level)
+            optional = expr.optional or _follows_guarded_index(expr.object)
+            self._write(f"{'?.' if optional else '.'}{expr.property}", expr.span)
+        elif isinstance(expr, IrIndex):
+            # A helper call ends the JavaScript chain, so it returns nullum itself when an optional link
+            # below it short-circuits, and the link right above it is written with `?.`.
+            guarded = _has_optional_link_below(expr)
+            if isinstance(expr.index, IrRange):
+                self._write(_slice_helper(expr.index.inclusive, guarded) + "(", expr.span)
+                arguments = [expr.index.start, expr.index.end]
+            else:
+                self._write(_index_helper(guarded) + "(", expr.span)
+                arguments = [expr.index]
+            self._emit_chain_link(expr.collection, 0, indent_level)
+            for argument in arguments:
+                # Guarded helpers take the index as a thunk: Scriptum skips it when the chain short-circuits.
+                self._write(", () => " if guarded else ", ")
+                self._emit_expression(argument, 2, "right", indent_level)
+            self._write(")")
+        elif isinstance(expr, (IrArrayLiteral, IrTupleLiteral)):
+            self._write("[", expr.span)
+            self._emit_list(expr.elements, indent_level)
+            self._write("]")
+        elif isinstance(expr, IrObjectLiteral):
+            self._write("{ " if expr.properties else "{", expr.span)
+            for index, prop in enumerate(expr.properties):
+                if index:
+                    self._write(", ")
+                self._write(f"{property_key(prop.key)}: ", prop.span)
+                self._emit_expression(prop.value, indent_level=indent_level)
+            self._write(" }" if expr.properties else "}")
+        elif isinstance(expr, IrLambda):
+            self._emit_lambda(expr, indent_level)
+        else:
+            raise TypeError(f"Unsupported expression type: {type(expr)!r}")
+
+    def _emit_binary(self, expr: IrBinary, prec: int, indent_level: int) -> None:
+        symbol, _, _ = _BINARY_OPERATORS.get(expr.operator, (expr.operator, prec, "left"))
+        left_prec = prec
+        right_prec = prec
+        if symbol == "??" or symbol in {"&&", "||"}:
+            # JavaScript rejects mixing `??` with `&&`/`||` without parentheses.
+            left_prec = right_prec = _mixing_guard(symbol, expr.left, expr.right, prec)
+        if symbol == "**" and isinstance(_strip_casts(expr.left), IrUnary):
+            left_prec = _CALL_PRECEDENCE
+        self._emit_expression(expr.left, left_prec, "left", indent_level)
+        self._write(f" {symbol} ", expr.span)
+        self._emit_expression(expr.right, right_prec, "right", indent_level)
+
+    def _emit_lambda(self, expr: IrLambda, indent_level: int) -> None:
+        if expr.method:
+            # Arrow functions would not bind `this` to the object the method is called on.
+            self._write("function ", expr.span)
+            self._emit_parameters(expr.parameters)
+            self._write(" ")
+            if expr.body_expression is None:
+                self._emit_block(expr.body_statements, indent_level)
+                return
+            body = IrReturn(span=expr.body_expression.span, value=expr.body_expression)
+            self._emit_block([body], indent_level)
+            return
+        self._emit_parameters(expr.parameters)
+        self._write(" => ", expr.span)
+        if expr.body_expression is not None:
+            wrap = isinstance(expr.body_expression, IrObjectLiteral)
+            self._write("(" if wrap else "")
+            self._emit_expression(expr.body_expression, 2, "right", indent_level)
+            self._write(")" if wrap else "")
+            return
+        self._emit_block(expr.body_statements, indent_level)
+
+    def _emit_list(self, items: List[IrExpr], indent_level: int) -> None:
+        for index, item in enumerate(items):
+            if index:
+                self._write(", ")
+            self._emit_expression(item, 2, "any", indent_level)
+
+    @staticmethod
+    def _precedence(expr: IrExpr) -> Tuple[int, str]:
+        if isinstance(expr, IrBinary):
+            _, prec, assoc = _BINARY_OPERATORS.get(expr.operator, (expr.operator, 9, "left"))
+            return prec, assoc
+        if isinstance(expr, IrUnary) or (isinstance(expr, IrUpdate) and expr.prefix):
+            return _UNARY_PRECEDENCE, "right"
+        if isinstance(expr, IrAssignment):
+            return 1, "right"
+        if isinstance(expr, (IrConditional, IrLambda)):
+            return 2, "right"
+        if isinstance(expr, IrCall):
+            return _CALL_PRECEDENCE, "left"
+        if isinstance(expr, (IrMemberAccess, IrIndex)):
+            return _MEMBER_PRECEDENCE, "left"
+        return 16, "left"
+
+
+def _has_optional_link(expr: IrExpr) -> bool:
+    """Whether *expr* ends a JavaScript chain of member accesses and calls in which some link is `?.`."""
+
+    while isinstance(expr, (IrMemberAccess, IrCall)):
+        inner = expr.callee if isinstance(expr, IrCall) else expr.object
+        if (isinstance(expr, IrMemberAccess) and expr.optional) or _follows_guarded_index(inner):
+            return True
+        expr = inner
+    return False
+
+
+def _has_optional_link_below(expr: IrExpr) -> bool:
+    """Whether some member access or index in the Scriptum chain *expr* ends, itself included, is `?.` or `?[`."""
+
+    while isinstance(expr, (IrMemberAccess, IrIndex, IrCall)):
+        if not isinstance(expr, IrCall) and expr.optional:
+            return True
+        if isinstance(expr, IrCall):
+            expr = expr.callee
+        else:
+            expr = expr.object if isinstance(expr, IrMemberAccess) else expr.collection
+    return False
+
+
+def _follows_guarded_index(expr: IrExpr) -> bool:
+    """Whether *expr* is an index helper that may return nullum for a short-circuited chain."""
+
+    expr = _strip_casts(expr)
+    return isinstance(expr, IrIndex) and _has_optional_link_below(expr)
+
+
+def _index_helper(optional: bool) -> str:
+    """`texto[i]`: one character counted in code points, like the interpreter; arrays and tuples index as usual."""
+
+    if optional:
+        body = 't == null ? null : typeof t === "string" ? Array.from(t)[i()] : t[i()]'
+    else:
+        body = 'typeof t === "string" ? Array.from(t)[i] : t[i]'
+    return f"(function (t, i) {{ return {body}; }})"
+
+
+def _slice_helper(inclusive: bool, optional: bool) -> str:
+    """`texto[a..b]`: a substring counted in code points, like the interpreter, rather than UTF-16 units."""
+
+    # `texto?[a..b]`, or a slice of a short-circuited chain, is nullum instead of failing in Array.from.
+    start, end = ("s()", "e()") if optional else ("s", "e")
+    end = f"{end} + 1" if inclusive else end
+    guard = "t == null ? null : " if optional else ""
+    return f'(function (t, s, e) {{ return {guard}Array.from(t).slice({start}, {end}).join(""); }})'
+
+
+def _needs_parentheses(prec: int, parent_prec: int, assoc: str, position: str) -> bool:
+    if prec < parent_prec:
+        return True
+    if prec == parent_prec and position != "any":
+        return (assoc == "left" and position == "right") or (assoc == "right" and position == "left")
+    return False
+
+
+def _mixing_guard(symbol: str, left: IrExpr, right: IrExpr, prec: int) -> int:
+    others = {"&&", "||"} if symbol == "??" else {"??"}
+    for operand in map(_strip_casts, (left, right)):
+        if isinstance(operand, IrBinary) and _BINARY_OPERATORS.get(operand.operator, ("",))[0] in others:
+            return _UNARY_PRECEDENCE
+    return prec
+
+
+def _strip_casts(expr: IrExpr) -> IrExpr:
+    while isinstance(expr, IrCast):
+        expr = expr.value
+    return expr
+
+
+def generate_js_with_map(module: ModuleIr, source: SourceFile, file: Optional[str] = None) -> Tuple[str, str]:
+    """
+    Translate *module* to JavaScript and return ``(code, source_map_json)``.
+
+    *source* must be the file the IR was lowered from; its text is used to turn
+    spans into line/column pairs. *file* names the generated script in the map.
+    """
+
+    emitter = JsEmitter(source)
+    code = emitter.emit(module)
+    emitter.source_map.file = file
+    return code, emitter.source_map.to_json()
+
+
+def generate_js(module: ModuleIr, source: SourceFile) -> str:
+    """Translate *module* to JavaScript without building a source map."""
+
+    return generate_js_with_map(module, source)[0]
@@ -0,0 +1,99 @@
+"""Source map (revision 3) builder used by the JavaScript backend."""
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from typing import List, Optional
+
+_BASE64 = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+
+
+@dataclass(slots=True, frozen=True)
+class Mapping:
+    """Links a generated position to an original one. All values are 0-based."""
+
+    generated_line: int
+    generated_column: int
+    source_line: int
+    source_column: int
+
+
+@dataclass(slots=True)
+class SourceMap:
+    source: str
+    file: Optional[str] = None
+    source_content: Optional[str] = None
+    mappings: List[Mapping] = field(default_factory=list)
+
+    def add(self, generated_line: int, generated_column: int, source_line: int, source_column: int) -> None:
+        mapping = Mapping(generated_line, generated_column, source_line, source_column)
+        if self.mappings and self.mappings[-1] == mapping:
+            return
+        self.mappings.append(mapping)
+
+    def encode_mappings(self) -> str:
+        lines: List[List[str]] = []
+        previous_source_line = 0
+        previous_source_column = 0
+        previous_column = 0
+        for mapping in sorted(self.mappings, key=lambda item: (item.generated_line, item.generated_column)):
+            while len(lines) <= mapping.generated_line:
+                lines.append([])
+                previous_column = 0
+            segment = (
+                mapping.generated_column - previous_column,
+                0,
+                mapping.source_line - previous_source_line,
+                mapping.source_column - previous_source_column,
+            )
+            lines[mapping.generated_line].append("".join(encode_vlq(value) for value in segment))
+            previous_column = mapping.generated_column
+            previous_source_line = mapping.source_line
+            previous_source_column = mapping.source_column
+        return ";".join(",".join(segments) for segments in lines)
+
+    def to_json(self) -> str:
+        payload = {
+            "version": 3,
+            "file": self.file or "",
+            "sources": [self.source],
+            "names": [],
+            "mappings": self.encode_mappings(),
+        }
+        if self.source_content is not None:
+            payload["sourcesContent"] = [self.source_content]
+        return json.dumps(payload, ensure_ascii=False)
+
+
+def encode_vlq(value: int) -> str:
+    """Encode *value* as a Base64 VLQ, as used by the `mappings` field."""
+
+    vlq = (-value << 1) | 1 if value < 0 else value << 1
+    encoded = ""
+    while True:
+        digit = vlq & 0b11111
+        vlq >>= 5
+        if vlq:
+            digit |= 0b100000
+        encoded += _BASE64[digit]
+        if not vlq:
+            return encoded
+
+
+def decode_vlq(text: str) -> List[int]:
+    """Decode a sequence of Base64 VLQ values (inverse of `encode_vlq`)."""
+
+    values: List[int] = []
+    shift = 0
+    accumulator = 0
+    for char in text:
+        digit = _BASE64.index(char)
+        accumulator += (digit & 0b11111) << shift
+        if digit & 0b100000:
+            shift += 5
+            continue
+        values.append(-(accumulator >> 1) if accumulator & 1 else accumulator >> 1)
+        shift = 0
+        accumulator = 0
+    return values
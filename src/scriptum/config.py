@@ -0,0 +1,112 @@
+"""Project settings read from `scriptum.toml`."""
+
+from __future__ import annotations
+
+import pathlib
+import tomllib
+from dataclasses import dataclass
+from typing import Any, Dict, Optional, Union
+
+from .codegen import CodegenOptions
+from .errors import ConfigError
+from .sema.analyzer import AnalyzerOptions
+
+CONFIG_FILENAME = "scriptum.toml"
+
+# What `scriptum compile` prints by default: formatted Scriptum or JavaScript.
+TARGETS = ("scriptum", "js")
+
+# Section -> keys accepted in `scriptum.toml`; anything else is reported, so typos do not pass silently.
+_KNOWN_KEYS = {
+    "format": ("indent", "max_width"),
+    "check": ("strict_any", "strict_params"),
+    "build": ("target",),
+}
+
+
+@dataclass(slots=True)
+class ProjectConfig:
+    """Defaults for the CLI; command-line flags take precedence over them."""
+
+    # The file the settings came from; None when no `scriptum.toml` was found.
+    path: Optional[pathlib.Path] = None
+    indent: str = "    "
+    # Statements longer than this break their outermost argument or element list; None never breaks.
+    max_width: Optional[int] = None
+    strict_any: bool = False
+    strict_params: bool = False
+    target: str = "scriptum"
+
+    def codegen_options(self) -> CodegenOptions:
+        return CodegenOptions(indent=self.indent, max_width=self.max_width)
+
+    def analyzer_options(self) -> AnalyzerOptions:
+        return AnalyzerOptions(strict_any=self.strict_any, strict_params=self.strict_params)
+
+
+def find_config(start: pathlib.Path) -> Optional[pathlib.Path]:
+    """The `scriptum.toml` in *start* or the nearest of its ancestors, if any."""
+
+    start = start.resolve()
+    for directory in (start, *start.parents):
+        candidate = directory / CONFIG_FILENAME
+        if candidate.is_file():
+            return candidate
+    return None
+
+
+def load_config(path: pathlib.Path) -> ProjectConfig:
+    """Read and validate *path*; raises `ConfigError` naming the file and the offending key."""
+
+    try:
+        data = tomllib.loads(path.read_text(encoding="utf8"))
+    except OSError as exc:
+        raise ConfigError(f"Cannot read {path}: {exc.strerror}.") from exc
+    except tomllib.TOMLDecodeError as exc:
+        raise ConfigError(f"{path} is not valid TOML: {exc}.") from exc
+
+    settings: Dict[str, Any] = {}
+    for section, table in data.items():
+        if section not in _KNOWN_KEYS or not isinstance(table, dict):
+            raise ConfigError(f"{path}: unknown section '{section}'; expected {', '.join(_KNOWN_KEYS)}.")
+        for key, value in table.items():
+            if key not in _KNOWN_KEYS[section]:
+                raise ConfigError(f"{path}: unknown setting '{section}.{key}'.")
+            settings[key] = value
+
+    config = ProjectConfig(path=path)
+    try:
+        if "indent" in settings:
+            config.indent = indent_unit(settings["indent"])
+        if "max_width" in settings:
+            config.max_width = _positive_int(settings["max_width"], "format.max_width")
+        for key in ("strict_any", "strict_params"):
+            if key in settings:
+                if not isinstance(settings[key], bool):
+                    raise ConfigError(f"'check.{key}' must be true or false.")
+                setattr(config, key, settings[key])
+        if "target" in settings:
+            if settings["target"] not in TARGETS:
+                raise ConfigError(f"'build.target' must be one of {', '.join(TARGETS)}.")
+            config.target = settings["target"]
+    except ConfigError as exc:
+        raise ConfigError(f"{path}: {exc}") from None
+    return config
+
+
+def indent_unit(value: Union[int, str]) -> str:
+    """One indentation level for *value*: a number of spaces (1 to 8) or `"tab"`."""
+
+    if value == "tab":
+        return "\t"
+    if isinstance(value, str) and value.isdigit():
+        value = int(value)
+    if isinstance(value, bool) or not isinstance(value, int) or not 1 <= value <= 8:
+        raise ConfigError(f"Indentation must be a number of spaces from 1 to 8 or 'tab', got {value!r}.")
+    return " " * value
+
+
+def _positive_int(value: Any, name: str) -> int:
+    if isinstance(value, bool) or not isinstance(value, int) or value <= 0:
+        raise ConfigError(f"'{name}' must be a positive integer, got {value!r}.")
+    return value
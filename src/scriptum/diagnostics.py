@@ -0,0 +1,69 @@
+"""
+One ordered list of diagnostics for a module, whichever stage found them.
+
+Lexer, parser and import errors stop compilation with an exception, while the
+semantic analyzer returns its findings as a list. `collect_diagnostics` merges
+both into `Diagnostic` values sorted by position, ready for editors and the
+LSP server.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import List, Optional, Sequence, Tuple
+
+from . import errors
+from .parser.parser import ParseError
+from .sema.analyzer import SemanticDiagnostic, Severity
+from .text import Span
+
+
+@dataclass(frozen=True, slots=True)
+class Diagnostic:
+    severity: Severity
+    message: str
+    span: Optional[Span]
+    # Only semantic diagnostics have a code (see `sema.codes`).
+    code: Optional[str] = None
+    # Secondary locations, such as where a delimiter that is still open was opened.
+    notes: Tuple[Tuple[str, Span], ...] = ()
+
+    @property
+    def is_error(self) -> bool:
+        return self.severity is Severity.ERROR
+
+
+def collect_diagnostics(
+    error: Optional[errors.CompilerError] = None,
+    semantic: Sequence[SemanticDiagnostic] = (),
+    error_span: Optional[Span] = None,
+) -> List[Diagnostic]:
+    """
+    Merge the *error* that stopped compilation, if any, with the *semantic* diagnostics.
+
+    *error_span* locates an error that carries no span of its own, such as a failed
+    import. The result is sorted by start offset, then end offset, errors before
+    warnings; diagnostics without a span come first.
+    """
+
+    collected = [_from_error(error, error_span)] if error is not None else []
+    collected += [
+        Diagnostic(severity=diag.severity, message=diag.message, span=diag.span, code=diag.code, notes=diag.notes)
+        for diag in semantic
+    ]
+    return sorted(collected, key=_position)
+
+
+def _from_error(error: errors.CompilerError, fallback: Optional[Span]) -> Diagnostic:
+    span, notes = fallback, ()
+    if isinstance(error, errors.LexerError):
+        span = error.anchor
+    elif isinstance(error, ParseError):
+        span, notes = error.span or fallback, tuple(error.notes)
+    return Diagnostic(severity=Severity.ERROR, message=str(error), span=span, notes=notes)
+
+
+def _position(diagnostic: Diagnostic) -> Tuple[int, int, bool]:
+    if diagnostic.span is None:
+        return (-1, -1, not diagnostic.is_error)
+    return (diagnostic.span.start, diagnostic.span.end, not diagnostic.is_error)
@@ -14,7 +14,7 @@ import importlib.util
 import json
 import pathlib
 import sys
-from dataclasses import dataclass, fields, is_dataclass
+from dataclasses import dataclass, field, fields, is_dataclass
 from typing import Any, Callable, Optional, Sequence
 
 if __package__ in (None, ""):
@@ -42,23 +42,30 @@ if __package__ in (None, ""):
 
 try:
     from . import errors, text, tokens
-    from .codegen import generate
-    from .ir import ModuleIr, lower_module
+    from .cache import BuildCache
+    from .codegen import CodegenOptions, generate
+    from .ir import ModuleIr, link_modules, lower_module, validate_module
     from .ir.interpreter import ExecutionResult, Interpreter
     from .lexer.lexer import LexerConfig, ScriptumLexer
+    from .modules import ModuleGraph, ModuleUnit
     from .parser.parser import ScriptumParser
-    from .sema.analyzer import SemanticAnalyzer, SemanticDiagnostic
+    from .sema.analyzer import AnalyzerOptions, SemanticAnalyzer, SemanticDiagnostic
+    from .sema.symbols import SymbolEntry
 except ImportError:  # pragma: no cover - standalone PyInstaller execution
     errors = importlib.import_module("scriptum.errors")
     text = importlib.import_module("scriptum.text")
     tokens = importlib.import_module("scriptum.tokens")
+    BuildCache = importlib.import_module("scriptum.cache").BuildCache
 
     codegen_module = importlib.import_module("scriptum.codegen")
+    CodegenOptions = codegen_module.CodegenOptions
     generate = codegen_module.generate
 
     ir_module = importlib.import_module("scriptum.ir")
     ModuleIr = ir_module.ModuleIr
+    link_modules = ir_module.link_modules
     lower_module = ir_module.lower_module
+    validate_module = ir_module.validate_module
 
     ir_interpreter_module = importlib.import_module("scriptum.ir.interpreter")
     ExecutionResult = ir_interpreter_module.ExecutionResult
@@ -68,12 +75,18 @@ except ImportError:  # pragma: no cover - standalone PyInstaller execution
     LexerConfig = lexer_module.LexerConfig
     ScriptumLexer = lexer_module.ScriptumLexer
 
+    modules_module = importlib.import_module("scriptum.modules")
+    ModuleGraph = modules_module.ModuleGraph
+    ModuleUnit = modules_module.ModuleUnit
+
     parser_module = importlib.import_module("scriptum.parser.parser")
     ScriptumParser = parser_module.ScriptumParser
 
     sema_module = importlib.import_module("scriptum.sema.analyzer")
+    AnalyzerOptions = sema_module.AnalyzerOptions
     SemanticAnalyzer = sema_module.SemanticAnalyzer
     SemanticDiagnostic = sema_module.SemanticDiagnostic
+    SymbolEntry = importlib.import_module("scriptum.sema.symbols").SymbolEntry
 
 VERSION = "0.3.2"
 CommandHandler = Callable[[argparse.Namespace], int]
@@ -96,6 +109,11 @@ class DriverConfig:
     """Configuration options for the compilation pipeline."""
 
     until: Stage = Stage.CODEGEN
+    analyzer: AnalyzerOptions = field(default_factory=AnalyzerOptions)
+    # How `Result.formatted` is laid out.
+    codegen: CodegenOptions = field(default_factory=CodegenOptions)
+    # When set, CODEGEN and FMT runs reuse the IR of unchanged sources stored here.
+    cache_dir: Optional[pathlib.Path] = None
 
 
 class CompilerDriver:
@@ -112,9 +130,13 @@ class CompilerDriver:
         tokens: Optional[list[tokens.Token]] = None
         ast: Optional["nodes.Module"] = None  # type: ignore[name-defined]
         diagnostics: Optional[list[SemanticDiagnostic]] = None
+        symbols: Optional[list[SymbolEntry]] = None
+        dependencies: Optional[list[ModuleUnit]] = None
         ir: Optional[ModuleIr] = None
         formatted: Optional[str] = None
         execution: Optional[ExecutionResult] = None
+        # True when the IR came from the build cache instead of the front end.
+        cached: bool = False
 
     def run(self, source: Optional[pathlib.Path], until: Optional[Stage] = None) -> "CompilerDriver.Result":
         """
@@ -135,6 +157,18 @@ class CompilerDriver:
 
         result = CompilerDriver.Result(source=source_file)
 
+        cache: Optional[BuildCache] = None
+        cache_key = ""
+        if self.config.cache_dir is not None and target_stage in {Stage.CODEGEN, Stage.FMT}:
+            cache = BuildCache(self.config.cache_dir, VERSION)
+            cache_key = cache.key(source_text, self.config.analyzer, str(source.resolve()) if source else "<stdin>")
+            cached_ir = cache.load(cache_key)
+            if cached_ir is not None:
+                result.ir = cached_ir
+                result.cached = True
+                result.formatted = generate(cached_ir, self.config.codegen).formatted
+                return result
+
         result.tokens = self.lex(source_file)
         if target_stage == Stage.LEXER:
             return result
@@ -143,23 +177,38 @@ class CompilerDriver:
         if target_stage == Stage.PARSER:
             return result
 
-        diagnostics = self.analyze(result.ast)
+        graph = ModuleGraph(self._parser, self.config.analyzer)
+        imports = graph.resolve_imports(result.ast, source) if source else {}
+        result.dependencies = graph.units
+
+        analyzer = SemanticAnalyzer(self.config.analyzer)
+        diagnostics = analyzer.analyze(result.ast, {path: unit.exports for path, unit in imports.items()})
         result.diagnostics = diagnostics
+        result.symbols = analyzer.symbol_index
         if target_stage == Stage.SEMANTIC:
             return result
-        if diagnostics:
+        if any(diagnostic.is_error for diagnostic in diagnostics):
             raise errors.SemanticError(diagnostics)
 
         result.ir = lower_module(result.ast)
         if target_stage == Stage.IR:
             return result
 
-        formatted_output = generate(result.ir)
+        exports = {unit.path.name: [symbol.name for symbol in unit.exports] for unit in result.dependencies}
+        dependencies = {unit.path.name: lower_module(unit.ast) for unit in result.dependencies}
+        program = link_modules(result.ir, dependencies, exports)
+        ir_problems = validate_module(program)
+        if ir_problems:
+            raise errors.IrValidationError(ir_problems)
+
+        formatted_output = generate(result.ir, self.config.codegen)
         result.formatted = formatted_output.formatted
+        if cache is not None:
+            cache.store(cache_key, result.ir, [(unit.path, unit.source.text) for unit in result.dependencies])
         if target_stage in {Stage.CODEGEN, Stage.FMT}:
             return result
 
-        interpreter = Interpreter(result.ir)
+        interpreter = Interpreter(program)
         result.execution = interpreter.execute()
         if target_stage == Stage.RUN:
             return result
@@ -188,9 +237,9 @@ class CompilerDriver:
     def parse(self, source: text.SourceFile):
         return self._parser.parse(source)
 
-    def analyze(self, module):
-        analyzer = SemanticAnalyzer()
-        return analyzer.analyze(module)
+    def analyze(self, module, imports=None):
+        analyzer = SemanticAnalyzer(self.config.analyzer)
+        return analyzer.analyze(module, imports)
 
 
 def _create_parser() -> argparse.ArgumentParser:
@@ -285,17 +334,18 @@ def _cmd_sema(args: argparse.Namespace) -> int:
     display_path = result.source.path or str(source)
 
     for diagnostic in diagnostics:
+        label = diagnostic.code if diagnostic.is_error else f"{diagnostic.code} (warning)"
         if diagnostic.span:
             line, column = diagnostic.span.line_col(source_text)
-            message = f"{diagnostic.code} {display_path}:{line}:{column}: {diagnostic.message}"
+            message = f"{label} {display_path}:{line}:{column}: {diagnostic.message}"
             _emit_error(message)
             snippet = diagnostic.span.highlight(source_text)
             if snippet:
                 for line_text in snippet.splitlines():
                     _emit_error(f"  {line_text}")
         else:
-            _emit_error(f"{diagnostic.code}: {diagnostic.message}")
-    return 1
+            _emit_error(f"{label}: {diagnostic.message}")
+    return 1 if any(diagnostic.is_error for diagnostic in diagnostics) else 0
 
 
 def _resolve_source(raw: str) -> pathlib.Path:
@@ -317,8 +367,8 @@ def _emit_error(message: str) -> None:
 def _ast_to_json(value: Any) -> Any:
     if is_dataclass(value):
         result: dict[str, Any] = {"__type__": value.__class__.__name__}
-        for field in fields(value):
-            result[field.name] = _ast_to_json(getattr(value, field.name))
+        for item in fields(value):
+            result[item.name] = _ast_to_json(getattr(value, item.name))
         return result
     if isinstance(value, enum.Enum):
         return value.name
@@ -7,6 +7,8 @@ with actionable feedback.
 
 from __future__ import annotations
 
+from typing import Optional
+
 
 class CompilerError(RuntimeError):
     """Base class for Scriptum-related errors."""
@@ -16,6 +18,10 @@ class CompilerInputError(CompilerError):
     """Raised when user-provided input cannot be consumed."""
 
 
+class ConfigError(CompilerInputError):
+    """Raised when `scriptum.toml` cannot be read or holds an invalid setting."""
+
+
 class CompilerInternalError(CompilerError):
     """Raised when the compiler encounters an unexpected internal condition."""
 
@@ -27,9 +33,11 @@ class CompilerNotImplemented(CompilerError):
 class LexerError(CompilerError):
     """Raised when the lexer cannot tokenise the provided input."""
 
-    def __init__(self, message: str, span: "Span") -> None:
+    def __init__(self, message: str, span: "Span", anchor: Optional["Span"] = None) -> None:
         super().__init__(message)
         self.span = span
+        # The position the message points at; may be narrower than *span*.
+        self.anchor = anchor or span
 
 
 from .text import Span  # noqa: E402  (late import to avoid circular dependency)
@@ -43,5 +51,22 @@ class SemanticError(CompilerError):
         self.diagnostics = diagnostics
 
 
+class IrValidationError(CompilerError):
+    """Raised when lowered IR violates structural invariants."""
+
+    def __init__(self, problems: list) -> None:
+        summary = "; ".join(problem.message for problem in problems[:3])
+        super().__init__(f"Invalid IR: {summary}")
+        self.problems = problems
+
+
+class IrFormatError(CompilerError):
+    """Raised when serialized IR cannot be loaded (wrong format, version or truncated data)."""
+
+
+class ModuleImportError(CompilerError):
+    """Raised when a module referenced through `importa` cannot be loaded or linked."""
+
+
 class ExecutionError(CompilerError):
     """Raised when executing Scriptum IR fails."""
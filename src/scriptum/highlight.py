@@ -0,0 +1,203 @@
+"""
+Semantic classification of Scriptum tokens for editor highlighting.
+
+The lexer alone cannot tell a function name from a variable or a parameter;
+`classify` combines its tokens with the parsed module, resolving each
+identifier to the declaration it refers to.
+"""
+
+from __future__ import annotations
+
+import bisect
+from dataclasses import dataclass
+from enum import Enum
+from typing import Dict, List, Optional, Union
+
+from . import tokens
+from .ast import nodes
+from .ast.visitors import iter_children, walk
+from .lexer.lexer import LexerConfig, ScriptumLexer
+from .parser.parser import ParseError, ScriptumParser
+from .sema.prelude import PRELUDE
+from .text import SourceFile, Span
+
+
+class TokenClass(Enum):
+    """What a token means; the values double as LSP semantic token type names."""
+
+    KEYWORD = "keyword"
+    # Built-in type keywords, type parameters and every name inside a type annotation.
+    TYPE = "type"
+    FUNCTION = "function"
+    PARAMETER = "parameter"
+    VARIABLE = "variable"
+    # Member names after `.`/`?.` and object literal keys.
+    PROPERTY = "property"
+    NUMBER = "number"
+    STRING = "string"
+    COMMENT = "comment"
+    OPERATOR = "operator"
+
+
+@dataclass(frozen=True, slots=True)
+class ClassifiedToken:
+    span: Span
+    lexeme: str
+    kind: TokenClass
+
+
+_BUILTIN_TYPES = {"numerus", "textus", "booleanum", "vacuum", "quodlibet"}
+
+_LEXICAL_CLASSES = {
+    tokens.TokenKind.NUMBER_LITERAL: TokenClass.NUMBER,
+    tokens.TokenKind.STRING_LITERAL: TokenClass.STRING,
+    tokens.TokenKind.COMMENT: TokenClass.COMMENT,
+    tokens.TokenKind.OPERATOR: TokenClass.OPERATOR,
+}
+
+_Function = Union[nodes.FunctionDeclaration, nodes.LambdaExpression]
+
+
+def classify(source: Union[SourceFile, str]) -> List[ClassifiedToken]:
+    """
+    Classify every token of *source* except whitespace and punctuation, in source order.
+
+    Lexer errors propagate. When the source does not parse, identifiers are
+    classified from their tokens alone: all of them are variables.
+    """
+
+    if isinstance(source, str):
+        source = SourceFile(path=None, text=source)
+    lexed = ScriptumLexer(LexerConfig(skip_whitespace=False)).tokenize(source)
+    try:
+        module: Optional[nodes.Module] = ScriptumParser().parse(source)
+    except ParseError:
+        module = None
+    roles = _IdentifierRoles(lexed).resolve(module) if module is not None else {}
+
+    classified: List[ClassifiedToken] = []
+    previous: Optional[tokens.Token] = None
+    for token in lexed:
+        kind = _token_class(token, previous, roles)
+        if token.kind not in {tokens.TokenKind.WHITESPACE, tokens.TokenKind.COMMENT}:
+            previous = token
+        if kind is not None:
+            classified.append(ClassifiedToken(token.span, source.slice(token.span), kind))
+    return classified
+
+
+def _token_class(
+    token: tokens.Token,
+    previous: Optional[tokens.Token],
+    roles: Dict[int, TokenClass],
+) -> Optional[TokenClass]:
+    after_dot = previous is not None and previous.lexeme in {".", "?."}
+    if token.kind is tokens.TokenKind.KEYWORD:
+        if after_dot:
+            return TokenClass.PROPERTY
+        return TokenClass.TYPE if token.lexeme in _BUILTIN_TYPES else TokenClass.KEYWORD
+    if token.kind is tokens.TokenKind.IDENTIFIER:
+        if after_dot:
+            return TokenClass.PROPERTY
+        return roles.get(token.span.start, TokenClass.VARIABLE)
+    return _LEXICAL_CLASSES.get(token.kind)
+
+
+class _IdentifierRoles:
+    """Maps the start offset of identifier tokens to their class, resolving names through nested scopes."""
+
+    def __init__(self, lexed: List[tokens.Token]) -> None:
+        self._identifiers = [token.span.start for token in lexed if token.kind is tokens.TokenKind.IDENTIFIER]
+        self.roles: Dict[int, TokenClass] = {}
+        self._scopes: List[Dict[str, TokenClass]] = []
+
+    def resolve(self, module: nodes.Module) -> Dict[int, TokenClass]:
+        # Annotations first, so a named type in front of a declaration is never taken for its name.
+        walk(self, module)
+        globals_: Dict[str, TokenClass] = {name: TokenClass.FUNCTION for name in PRELUDE}
+        for declaration in module.declarations:
+            if isinstance(declaration, nodes.FunctionDeclaration):
+                globals_[declaration.name] = TokenClass.FUNCTION
+            elif isinstance(declaration, nodes.VariableDeclaration):
+                globals_[declaration.name] = TokenClass.VARIABLE
+        self._scopes.append(globals_)
+        for declaration in module.declarations:
+            self._resolve(declaration)
+        return self.roles
+
+    def visit(self, node: nodes.Node) -> None:
+        if isinstance(node, (nodes.TypeAnnotation, nodes.TypeParameter)):
+            for start in self._identifiers_in(node.span):
+                self.roles[start] = TokenClass.TYPE
+
+    def _resolve(self, node: nodes.Node) -> None:
+        if isinstance(node, nodes.Identifier):
+            self.roles[node.span.start] = self._lookup(node.name)
+        elif isinstance(node, nodes.FunctionDeclaration):
+            self._name(node.span, TokenClass.FUNCTION)
+            self._resolve_function(node)
+        elif isinstance(node, nodes.LambdaExpression):
+            self._resolve_function(node)
+        elif isinstance(node, nodes.BlockStatement):
+            self._scopes.append({})
+            for statement in node.statements:
+                self._resolve(statement)
+            self._scopes.pop()
+        elif isinstance(node, nodes.VariableDeclaration):
+            if node.initializer is not None:
+                self._resolve(node.initializer)
+            self._name(node.span, TokenClass.VARIABLE)
+            self._scopes[-1][node.name] = TokenClass.VARIABLE
+        elif isinstance(node, nodes.DestructuringDeclaration):
+            self._resolve(node.initializer)
+            self._scopes[-1].update((name, TokenClass.VARIABLE) for name in node.names)
+        elif isinstance(node, nodes.ForStatement):
+            self._resolve(node.iterable)
+            self._name(node.target.span, TokenClass.VARIABLE)
+            self._scopes.append({node.target.name: TokenClass.VARIABLE})
+            self._resolve(node.body)
+            self._scopes.pop()
+        elif isinstance(node, nodes.CStyleForStatement):
+            self._scopes.append({})
+            for child in iter_children(node):
+                self._resolve(child)
+            self._scopes.pop()
+        elif isinstance(node, nodes.ObjectProperty):
+            # A quoted key is a string token, and the first identifier then belongs to the value.
+            if self._identifiers_in(node.span)[:1] == [node.span.start]:
+                self._name(node.span, TokenClass.PROPERTY)
+            self._resolve(node.value)
+        else:
+            for child in iter_children(node):
+                self._resolve(child)
+
+    def _resolve_function(self, function: _Function) -> None:
+        scope: Dict[str, TokenClass] = {}
+        self._scopes.append(scope)
+        for parameter in function.parameters:
+            if parameter.default_value is not None:
+                self._resolve(parameter.default_value)
+            self._name(parameter.span, TokenClass.PARAMETER)
+            scope[parameter.name] = TokenClass.PARAMETER
+        self._resolve(function.body)
+        self._scopes.pop()
+
+    def _lookup(self, name: str) -> TokenClass:
+        for scope in reversed(self._scopes):
+            if name in scope:
+                return scope[name]
+        # Imported and undeclared names.
+        return TokenClass.VARIABLE
+
+    def _name(self, span: Span, kind: TokenClass) -> None:
+        """Mark the first identifier of *span* that is not part of a type as the declared name."""
+
+        for start in self._identifiers_in(span):
+            if start not in self.roles:
+                self.roles[start] = kind
+                return
+
+    def _identifiers_in(self, span: Span) -> List[int]:
+        first = bisect.bisect_left(self._identifiers, span.start)
+        last = bisect.bisect_left(self._identifiers, span.end)
+        return self._identifiers[first:last]
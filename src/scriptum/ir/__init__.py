@@ -6,8 +6,10 @@ from .ir import (
     IrBinary,
     IrBreak,
     IrCall,
+    IrCast,
     IrConditional,
     IrContinue,
+    IrDestructuring,
     IrExpr,
     IrExpressionStatement,
     IrForIn,
@@ -15,6 +17,8 @@ from .ir import (
     IrFunction,
     IrIdentifier,
     IrIf,
+    IrImport,
+    IrImportName,
     IrIndex,
     IrLambda,
     IrLiteral,
@@ -23,16 +27,24 @@ from .ir import (
     IrObjectLiteral,
     IrObjectProperty,
     IrParameter,
+    IrRange,
     IrReturn,
     IrStatement,
+    IrTupleLiteral,
     IrUnary,
+    IrUpdate,
     IrVariable,
     IrVariableDeclaration,
     IrWhile,
     ModuleIr,
+    format_ir,
     format_module_ir,
 )
+from .binary import from_bytes, to_bytes
+from .link import link_modules
 from .lowering import lower_module
+from .passes import flatten_conditional_statements, simplify_expressions
+from .validate import IrError, validate_module
 
 __all__ = [
     "IrArrayLiteral",
@@ -40,8 +52,11 @@ __all__ = [
     "IrBinary",
     "IrBreak",
     "IrCall",
+    "IrCast",
     "IrConditional",
     "IrContinue",
+    "IrDestructuring",
+    "IrError",
     "IrExpr",
     "IrExpressionStatement",
     "IrForIn",
@@ -49,6 +64,8 @@ __all__ = [
     "IrFunction",
     "IrIdentifier",
     "IrIf",
+    "IrImport",
+    "IrImportName",
     "IrIndex",
     "IrLambda",
     "IrLiteral",
@@ -57,13 +74,23 @@ __all__ = [
     "IrObjectLiteral",
     "IrObjectProperty",
     "IrParameter",
+    "IrRange",
     "IrReturn",
     "IrStatement",
+    "IrTupleLiteral",
     "IrUnary",
+    "IrUpdate",
     "IrVariable",
     "IrVariableDeclaration",
     "IrWhile",
     "ModuleIr",
+    "flatten_conditional_statements",
+    "format_ir",
     "format_module_ir",
+    "from_bytes",
+    "link_modules",
     "lower_module",
+    "simplify_expressions",
+    "to_bytes",
+    "validate_module",
 ]
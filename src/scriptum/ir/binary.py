@@ -0,0 +1,188 @@
+"""Compact binary encoding of `ModuleIr`, used to cache lowered modules between runs."""
+
+from __future__ import annotations
+
+import struct
+from dataclasses import fields
+from typing import Any, Dict, List, Type
+
+from .. import errors
+from ..text import Span
+from . import ir
+
+MAGIC = b"SCIR"
+# Bump whenever an IR node gains, loses or reorders a field.
+FORMAT_VERSION = 7
+
+_HEADER = struct.Struct(">4sH")
+_FLOAT = struct.Struct(">d")
+
+_NONE = b"N"
+_TRUE = b"T"
+_FALSE = b"F"
+_INT = b"I"
+_FLOAT_TAG = b"D"
+_STRING = b"S"
+_LIST = b"L"
+_NODE = b"O"
+
+_NODE_TYPES: Dict[str, Type[ir.IrNode]] = {
+    cls.__name__: cls
+    for cls in vars(ir).values()
+    if isinstance(cls, type) and issubclass(cls, ir.IrNode) and cls is not ir.IrNode
+}
+
+
+def to_bytes(module: ir.ModuleIr) -> bytes:
+    """Serialize *module*; the result starts with a magic number and `FORMAT_VERSION`."""
+
+    out = bytearray(_HEADER.pack(MAGIC, FORMAT_VERSION))
+    _write_value(out, module)
+    return bytes(out)
+
+
+def from_bytes(data: bytes) -> ir.ModuleIr:
+    """
+    Rebuild a module written by `to_bytes`.
+
+    Raises `IrFormatError` when *data* is not Scriptum IR, was written with another
+    `FORMAT_VERSION`, or is truncated or corrupt.
+    """
+
+    if len(data) < _HEADER.size or data[:4] != MAGIC:
+        raise errors.IrFormatError("Data is not serialized Scriptum IR (missing 'SCIR' header).")
+    _, version = _HEADER.unpack_from(data)
+    if version != FORMAT_VERSION:
+        raise errors.IrFormatError(
+            f"Serialized IR uses format version {version}, but this compiler reads version {FORMAT_VERSION}; "
+            "recompile the module."
+        )
+    reader = _Reader(data, _HEADER.size)
+    try:
+        module = reader.value()
+    except (IndexError, struct.error, UnicodeDecodeError) as exc:
+        raise errors.IrFormatError(f"Serialized IR is truncated or corrupt at byte {reader.offset}.") from exc
+    if not isinstance(module, ir.ModuleIr):
+        raise errors.IrFormatError(f"Serialized IR holds {type(module).__name__}, not a module.")
+    if reader.offset != len(data):
+        raise errors.IrFormatError(f"Serialized IR has {len(data) - reader.offset} unexpected trailing bytes.")
+    return module
+
+
+# Writing -----------------------------------------------------------------------
+
+
+def _write_varint(out: bytearray, value: int) -> None:
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return
+
+
+def _write_string(out: bytearray, text: str) -> None:
+    encoded = text.encode("utf8")
+    _write_varint(out, len(encoded))
+    out += encoded
+
+
+def _write_value(out: bytearray, value: Any) -> None:
+    if value is None:
+        out += _NONE
+    elif isinstance(value, bool):
+        out += _TRUE if value else _FALSE
+    elif isinstance(value, int):
+        out += _INT
+        # Zigzag keeps small negative numbers short.
+        _write_varint(out, value * 2 if value >= 0 else -value * 2 - 1)
+    elif isinstance(value, float):
+        out += _FLOAT_TAG
+        out += _FLOAT.pack(value)
+    elif isinstance(value, str):
+        out += _STRING
+        _write_string(out, value)
+    elif isinstance(value, list):
+        out += _LIST
+        _write_varint(out, len(value))
+        for item in value:
+            _write_value(out, item)
+    elif isinstance(value, ir.IrNode):
+        out += _NODE
+        _write_string(out, type(value).__name__)
+        _write_varint(out, value.span.start)
+        _write_varint(out, value.span.end)
+        node_fields = [field.name for field in fields(value) if field.name != "span"]
+        _write_varint(out, len(node_fields))
+        for name in node_fields:
+            _write_value(out, getattr(value, name))
+    else:
+        raise errors.CompilerInternalError(f"Cannot serialize IR value of type {type(value).__name__}")
+
+
+# Reading -----------------------------------------------------------------------
+
+
+class _Reader:
+    def __init__(self, data: bytes, offset: int) -> None:
+        self.data = data
+        self.offset = offset
+
+    def value(self) -> Any:
+        tag = self._take(1)
+        if tag == _NONE:
+            return None
+        if tag == _TRUE:
+            return True
+        if tag == _FALSE:
+            return False
+        if tag == _INT:
+            encoded = self._varint()
+            return encoded >> 1 if not encoded & 1 else -((encoded + 1) >> 1)
+        if tag == _FLOAT_TAG:
+            return _FLOAT.unpack(self._take(_FLOAT.size))[0]
+        if tag == _STRING:
+            return self._string()
+        if tag == _LIST:
+            return [self.value() for _ in range(self._varint())]
+        if tag == _NODE:
+            return self._node()
+        raise errors.IrFormatError(f"Unknown value tag {tag!r} at byte {self.offset - 1} of serialized IR.")
+
+    def _node(self) -> ir.IrNode:
+        kind = self._string()
+        cls = _NODE_TYPES.get(kind)
+        if cls is None:
+            raise errors.IrFormatError(f"Serialized IR contains unknown node kind '{kind}'.")
+        span = Span(self._varint(), self._varint())
+        names = [field.name for field in fields(cls) if field.name != "span"]
+        count = self._varint()
+        if count != len(names):
+            raise errors.IrFormatError(
+                f"Serialized {kind} has {count} fields, but this compiler expects {len(names)}; recompile the module."
+            )
+        values: List[Any] = [self.value() for _ in names]
+        return cls(span=span, **dict(zip(names, values)))
+
+    def _varint(self) -> int:
+        result = 0
+        shift = 0
+        while True:
+            byte = self.data[self.offset]
+            self.offset += 1
+            result |= (byte & 0x7F) << shift
+            if not byte & 0x80:
+                return result
+            shift += 7
+
+    def _string(self) -> str:
+        return self._take(self._varint()).decode("utf8")
+
+    def _take(self, size: int) -> bytes:
+        chunk = self.data[self.offset : self.offset + size]
+        if len(chunk) != size:
+            raise IndexError("serialized IR ended early")
+        self.offset += size
+        return chunk
@@ -1,18 +1,24 @@
 from __future__ import annotations
 
+import json
+import math
+import sys
 from dataclasses import dataclass
-from typing import Any, Iterable, List, Optional
+from typing import Any, Callable, Dict, Iterable, Iterator, List, Optional, TextIO
 
 from .. import errors
 from ..text import Span
+from ..tokens import property_key
 from .ir import (
     IrArrayLiteral,
     IrAssignment,
     IrBinary,
     IrBreak,
     IrCall,
+    IrCast,
     IrConditional,
     IrContinue,
+    IrDestructuring,
     IrExpr,
     IrExpressionStatement,
     IrForIn,
@@ -28,9 +34,12 @@ from .ir import (
     IrObjectLiteral,
     IrObjectProperty,
     IrParameter,
+    IrRange,
     IrReturn,
     IrStatement,
+    IrTupleLiteral,
     IrUnary,
+    IrUpdate,
     IrVariable,
     IrVariableDeclaration,
     IrWhile,
@@ -38,6 +47,11 @@ from .ir import (
 )
 
 
+# Call, member and index expressions chain; a `?.` or `?[` that meets nullum skips the rest of its chain.
+_CHAIN_LINKS = (IrCall, IrMemberAccess, IrIndex)
+_SHORT_CIRCUIT = object()
+
+
 @dataclass(slots=True)
 class ExecutionResult:
     value: Any
@@ -70,6 +84,15 @@ class Environment:
         env = self._resolve(name)
         return env.bindings[name].value
 
+    def binding(self, name: str) -> RuntimeBinding:
+        return self._resolve(name).bindings[name]
+
+    def copy(self) -> "Environment":
+        """A sibling scope holding fresh bindings with the same values as this one."""
+        clone = Environment(parent=self.parent)
+        clone.bindings = {name: RuntimeBinding(b.mutable, b.value) for name, b in self.bindings.items()}
+        return clone
+
     def _resolve(self, name: str) -> "Environment":
         env: Optional[Environment] = self
         while env is not None:
@@ -79,17 +102,39 @@ class Environment:
         raise errors.ExecutionError(f"Name '{name}' is not defined.")
 
 
+@dataclass(slots=True, frozen=True)
+class NumericRange:
+    """`start..end` (half-open) or `start..=end` range, iterated lazily without building a list."""
+
+    start: Any
+    end: Any
+    inclusive: bool = False
+
+    def __iter__(self) -> Iterator[Any]:
+        if isinstance(self.start, int) and isinstance(self.end, int):
+            return iter(range(self.start, self.end + 1 if self.inclusive else self.end))
+        return self._stepped()
+
+    def _stepped(self) -> Iterator[Any]:
+        current = self.start
+        while current < self.end or (self.inclusive and current == self.end):
+            yield current
+            current += 1
+
+
 class ReturnSignal(Exception):
     def __init__(self, value: Any) -> None:
         self.value = value
 
 
 class BreakSignal(Exception):
-    pass
+    def __init__(self, label: Optional[str] = None) -> None:
+        self.label = label
 
 
 class ContinueSignal(Exception):
-    pass
+    def __init__(self, label: Optional[str] = None) -> None:
+        self.label = label
 
 
 @dataclass(slots=True)
@@ -107,15 +152,31 @@ class RuntimeLambda:
     body_statements: List[IrStatement]
     body_expression: Optional[IrExpr]
     closure: Environment
+    # Methods take the object they were called on as an implicit first argument, bound to `ipse`.
+    method: bool = False
 
     def call(self, interpreter: "Interpreter", args: List[Any]) -> Any:
         return interpreter._invoke_lambda(self, args)
 
 
+@dataclass(slots=True, frozen=True)
+class RuntimeNative:
+    """A builtin function from `sema.prelude`, implemented in Python."""
+
+    name: str
+    function: Callable[["Interpreter", List[Any]], Any]
+
+    def call(self, interpreter: "Interpreter", args: List[Any]) -> Any:
+        return self.function(interpreter, args)
+
+
 class Interpreter:
-    def __init__(self, module: ModuleIr) -> None:
+    def __init__(self, module: ModuleIr, output: Optional[TextIO] = None) -> None:
         self.module = module
-        self.global_env = Environment()
+        # Where `imprime` writes; None means the `sys.stdout` of the moment.
+        self.output = output
+        # Builtins sit in an enclosing scope, so the module's own names shadow them.
+        self.global_env = Environment(parent=_native_environment())
 
     def execute(self, entry_point: str = "main") -> ExecutionResult:
         self._register_functions()
@@ -158,6 +219,9 @@ class Interpreter:
 
     def _invoke_lambda(self, runtime_lambda: RuntimeLambda, args: List[Any]) -> Any:
         call_env = Environment(parent=runtime_lambda.closure)
+        if runtime_lambda.method:
+            receiver, *args = args
+            call_env.declare("ipse", receiver, mutable=False)
         evaluated_args = self._bind_parameters(runtime_lambda.parameters, args, runtime_lambda.closure)
         for (param, value) in evaluated_args:
             call_env.declare(param.name, value, mutable=False)
@@ -203,6 +267,16 @@ class Interpreter:
             env.declare(stmt.name, value, mutable=stmt.mutable)
             return
 
+        if isinstance(stmt, IrDestructuring):
+            value = self._evaluate_expression(stmt.initializer, env)
+            if not isinstance(value, (list, tuple)):
+                raise errors.ExecutionError(f"Cannot destructure {runtime_type_name(value)}; expected tuple or array.")
+            if len(value) != len(stmt.names):
+                raise errors.ExecutionError(f"Cannot destructure {len(value)} values into {len(stmt.names)} names.")
+            for name, item in zip(stmt.names, value):
+                env.declare(name, item, mutable=stmt.mutable)
+            return
+
         if isinstance(stmt, IrExpressionStatement):
             self._evaluate_expression(stmt.expression, env)
             return
@@ -214,44 +288,65 @@ class Interpreter:
             raise ReturnSignal(value)
 
         if isinstance(stmt, IrIf):
-            condition = self._truthy(self._evaluate_expression(stmt.condition, env))
+            condition = self._condition(self._evaluate_expression(stmt.condition, env), "si")
             branch = stmt.then_branch if condition else stmt.else_branch
             branch_env = Environment(parent=env)
             self._execute_statements(branch, branch_env)
             return
 
         if isinstance(stmt, IrWhile):
-            while self._truthy(self._evaluate_expression(stmt.condition, env)):
-                loop_env = Environment(parent=env)
+            # The initialiser of a C-style `pro` is scoped to the loop, not to the enclosing block.
+            header_env = Environment(parent=env) if stmt.init is not None else env
+            if stmt.init is not None:
+                self._execute_statement(stmt.init, header_env)
+                header_env = header_env.copy()
+            keyword = "dum" if stmt.init is None and stmt.update is None else "pro"
+            while stmt.condition is None or self._condition(
+                self._evaluate_expression(stmt.condition, header_env), keyword
+            ):
+                loop_env = Environment(parent=header_env)
                 try:
                     self._execute_statements(stmt.body, loop_env)
-                except ContinueSignal:
-                    continue
-                except BreakSignal:
+                except ContinueSignal as signal:
+                    if signal.label not in (None, stmt.label):
+                        raise
+                except BreakSignal as signal:
+                    if signal.label not in (None, stmt.label):
+                        raise
                     break
+                # Like `let` in JavaScript, every iteration gets its own copy of the loop variables, so closures
+                # created in the body keep the value they saw.
+                if stmt.init is not None:
+                    header_env = header_env.copy()
+                # `perge` still runs the update, as in C.
+                if stmt.update is not None:
+                    self._evaluate_expression(stmt.update, header_env)
             return
 
         if isinstance(stmt, IrForIn):
             iterable_value = self._evaluate_expression(stmt.iterable, env)
-            elements = list(self._ensure_iterable(iterable_value))
-            loop_env = Environment(parent=env)
-            loop_env.declare(stmt.target.name, None, mutable=True)
+            elements = self._ensure_iterable(iterable_value)
             for element in elements:
-                loop_env.assign(stmt.target.name, element)
-                iteration_env = Environment(parent=loop_env)
+                # A fresh binding per iteration, so closures created in the body keep their own element.
+                iteration_env = Environment(parent=env)
+                iteration_env.declare(stmt.target.name, element, mutable=stmt.target.mutable)
                 try:
                     self._execute_statements(stmt.body, iteration_env)
-                except ContinueSignal:
+                except ContinueSignal as signal:
+                    if signal.label not in (None, stmt.label):
+                        raise
                     continue
-                except BreakSignal:
+                except BreakSignal as signal:
+                    if signal.label not in (None, stmt.label):
+                        raise
                     break
             return
 
         if isinstance(stmt, IrBreak):
-            raise BreakSignal()
+            raise BreakSignal(stmt.label)
 
         if isinstance(stmt, IrContinue):
-            raise ContinueSignal()
+            raise ContinueSignal(stmt.label)
 
         raise errors.ExecutionError(f"Unsupported statement type: {type(stmt).__name__}")
 
@@ -271,12 +366,39 @@ class Interpreter:
             if expr.operator == "POSITIVE" or expr.operator == "+":
                 return +operand
             if expr.operator == "NOT" or expr.operator == "!":
-                return not self._truthy(operand)
+                return not self._condition(operand, "!")
+            if expr.operator == "BIT_NOT" or expr.operator == "~":
+                if runtime_type_name(operand) != "numerus":
+                    raise errors.ExecutionError(f"Operator '~' requires numerus, got {runtime_type_name(operand)}.")
+                return ~_to_int32(operand)
             raise errors.ExecutionError(f"Unknown unary operator '{expr.operator}'.")
 
         if isinstance(expr, IrBinary):
             return self._evaluate_binary(expr, env)
 
+        if isinstance(expr, IrUpdate):
+            binding = env.binding(expr.target.name)
+            if not binding.mutable:
+                raise errors.ExecutionError(f"Cannot assign to immutable binding '{expr.target.name}'.")
+            previous = binding.value
+            if isinstance(previous, bool) or not isinstance(previous, (int, float)):
+                raise errors.ExecutionError(
+                    f"Operand of update must be numerus, got {runtime_type_name(previous)}."
+                )
+            binding.value = previous + 1 if expr.operator == "INCREMENT" else previous - 1
+            return binding.value if expr.prefix else previous
+
+        if isinstance(expr, IrRange):
+            start = self._evaluate_expression(expr.start, env)
+            end = self._evaluate_expression(expr.end, env)
+            for bound in (start, end):
+                if isinstance(bound, bool) or not isinstance(bound, (int, float)):
+                    raise errors.ExecutionError(f"Range bounds must be numerus, got {runtime_type_name(bound)}.")
+            return NumericRange(start, end, expr.inclusive)
+
+        if isinstance(expr, IrCast):
+            return _cast(self._evaluate_expression(expr.value, env), expr.type_name)
+
         if isinstance(expr, IrAssignment):
             if not isinstance(expr.target, IrIdentifier):
                 raise errors.ExecutionError("Only identifier assignments are supported.")
@@ -285,32 +407,16 @@ class Interpreter:
             return value
 
         if isinstance(expr, IrConditional):
-            condition = self._truthy(self._evaluate_expression(expr.condition, env))
+            condition = self._condition(self._evaluate_expression(expr.condition, env), "?:")
             branch = expr.consequent if condition else expr.alternate
             return self._evaluate_expression(branch, env)
 
-        if isinstance(expr, IrCall):
-            callee = self._evaluate_expression(expr.callee, env)
-            arguments = [self._evaluate_expression(arg, env) for arg in expr.arguments]
-            if hasattr(callee, "call"):
-                return callee.call(self, arguments)
-            raise errors.ExecutionError("Attempted to call a non-callable value.")
+        if isinstance(expr, _CHAIN_LINKS):
+            value = self._evaluate_link(expr, env)
+            return None if value is _SHORT_CIRCUIT else value
 
-        if isinstance(expr, IrMemberAccess):
-            obj = self._evaluate_expression(expr.object, env)
-            if isinstance(obj, dict):
-                return obj.get(expr.property)
-            raise errors.ExecutionError("Member access requires an object literal.")
-
-        if isinstance(expr, IrIndex):
-            collection = self._evaluate_expression(expr.collection, env)
-            index = self._evaluate_expression(expr.index, env)
-            try:
-                return collection[index]
-            except Exception as exc:  # pragma: no cover - safe guard
-                raise errors.ExecutionError("Index operation failed.") from exc
-
-        if isinstance(expr, IrArrayLiteral):
+        if isinstance(expr, (IrArrayLiteral, IrTupleLiteral)):
+            # Tuples run as fixed-size arrays.
             return [self._evaluate_expression(elem, env) for elem in expr.elements]
 
         if isinstance(expr, IrObjectLiteral):
@@ -325,24 +431,71 @@ class Interpreter:
                 body_statements=expr.body_statements,
                 body_expression=expr.body_expression,
                 closure=env,
+                method=expr.method,
             )
 
         raise errors.ExecutionError(f"Unsupported expression type: {type(expr).__name__}")
 
+    def _evaluate_link(self, expr: IrExpr, env: Environment) -> Any:
+        """Evaluate one call, member or index link; `_SHORT_CIRCUIT` once a `?.` or `?[` met nullum."""
+
+        if isinstance(expr, IrCall):
+            receiver = None
+            if isinstance(expr.callee, IrMemberAccess):
+                receiver = self._evaluate_chain_base(expr.callee.object, env)
+                callee = self._member(expr.callee, receiver)
+            else:
+                callee = self._evaluate_chain_base(expr.callee, env)
+            if callee is _SHORT_CIRCUIT:
+                return _SHORT_CIRCUIT
+            arguments = [self._evaluate_expression(arg, env) for arg in expr.arguments]
+            if isinstance(callee, RuntimeLambda) and callee.method:
+                # A method called without an object, say through a variable, sees `ipse` as nullum.
+                arguments.insert(0, receiver)
+            if hasattr(callee, "call"):
+                return callee.call(self, arguments)
+            raise errors.ExecutionError("Attempted to call a non-callable value.")
+
+        if isinstance(expr, IrMemberAccess):
+            return self._member(expr, self._evaluate_chain_base(expr.object, env))
+
+        collection = self._evaluate_chain_base(expr.collection, env)
+        if collection is _SHORT_CIRCUIT or (collection is None and expr.optional):
+            return _SHORT_CIRCUIT
+        index = self._evaluate_expression(expr.index, env)
+        if isinstance(collection, str):
+            return _index_text(collection, index)
+        try:
+            return collection[index]
+        except Exception as exc:  # pragma: no cover - safe guard
+            raise errors.ExecutionError("Index operation failed.") from exc
+
+    def _member(self, expr: IrMemberAccess, obj: Any) -> Any:
+        if obj is _SHORT_CIRCUIT or (obj is None and expr.optional):
+            return _SHORT_CIRCUIT
+        if isinstance(obj, dict):
+            return obj.get(expr.property)
+        raise errors.ExecutionError("Member access requires an object literal.")
+
+    def _evaluate_chain_base(self, expr: IrExpr, env: Environment) -> Any:
+        if isinstance(expr, _CHAIN_LINKS):
+            return self._evaluate_link(expr, env)
+        return self._evaluate_expression(expr, env)
+
     def _evaluate_binary(self, expr: IrBinary, env: Environment) -> Any:
         op = expr.operator
 
         if op in {"OR", "||"}:
-            left = self._truthy(self._evaluate_expression(expr.left, env))
+            left = self._condition(self._evaluate_expression(expr.left, env), "||")
             if left:
                 return True
-            return self._truthy(self._evaluate_expression(expr.right, env))
+            return self._condition(self._evaluate_expression(expr.right, env), "||")
 
         if op in {"AND", "&&"}:
-            left = self._truthy(self._evaluate_expression(expr.left, env))
+            left = self._condition(self._evaluate_expression(expr.left, env), "&&")
             if not left:
                 return False
-            return self._truthy(self._evaluate_expression(expr.right, env))
+            return self._condition(self._evaluate_expression(expr.right, env), "&&")
 
         if op in {"NULLISH", "??"}:
             left_val = self._evaluate_expression(expr.left, env)
@@ -353,47 +506,251 @@ class Interpreter:
         left = self._evaluate_expression(expr.left, env)
         right = self._evaluate_expression(expr.right, env)
 
-        mapping = {
-            "ADD": lambda a, b: a + b,
-            "+": lambda a, b: a + b,
-            "SUB": lambda a, b: a - b,
-            "-": lambda a, b: a - b,
-            "MUL": lambda a, b: a * b,
-            "*": lambda a, b: a * b,
-            "DIV": lambda a, b: a / b,
-            "/": lambda a, b: a / b,
-            "MOD": lambda a, b: a % b,
-            "%": lambda a, b: a % b,
-            "POW": lambda a, b: a ** b,
-            "**": lambda a, b: a ** b,
-            "GT": lambda a, b: a > b,
-            ">": lambda a, b: a > b,
-            "GE": lambda a, b: a >= b,
-            ">=": lambda a, b: a >= b,
-            "LT": lambda a, b: a < b,
-            "<": lambda a, b: a < b,
-            "LE": lambda a, b: a <= b,
-            "<=": lambda a, b: a <= b,
-            "EQ": lambda a, b: a == b,
-            "==": lambda a, b: a == b,
-            "NE": lambda a, b: a != b,
-            "!=": lambda a, b: a != b,
-            "STRICT_EQ": lambda a, b: a == b,
-            "===": lambda a, b: a == b,
-            "STRICT_NE": lambda a, b: a != b,
-            "!==": lambda a, b: a != b,
-        }
-
-        if op not in mapping:
+
+        operation = _BINARY_OPERATIONS.get(op)
+        if operation is None:
             raise errors.ExecutionError(f"Unsupported binary operator '{op}'.")
-        return mapping[op](left, right)
+        return operation(left, right)
 
     # Helpers -----------------------------------------------------------------
 
-    def _truthy(self, value: Any) -> bool:
-        return bool(value)
+    def _condition(self, value: Any, construct: str) -> bool:
+        if not isinstance(value, bool):
+            raise errors.ExecutionError(
+                f"Condition for '{construct}' must be booleanum, got {runtime_type_name(value)}."
+            )
+        return value
 
     def _ensure_iterable(self, value: Any) -> Iterable[Any]:
         if isinstance(value, (list, tuple)):
+            # Snapshot arrays so the loop body may modify them safely.
+            return list(value)
+        if isinstance(value, NumericRange):
             return value
         raise errors.ExecutionError("Value is not iterable for 'pro' loop.")
+
+
+def _cast(value: Any, type_name: str) -> Any:
+    """Check a `ut` cast to a primitive type; casts to other types are unchecked."""
+
+    target = type_name.strip().lower()
+    optional = target.endswith("?")
+    target = target.rstrip("?")
+    if target not in {"numerus", "textus", "booleanum"} or (optional and value is None):
+        return value
+    actual = runtime_type_name(value)
+    if actual != target:
+        raise errors.ExecutionError(f"Cannot cast {actual} to {type_name}.")
+    return value
+
+
+def _to_int32(value: Any) -> int:
+    """Truncate *value* to a signed 32-bit integer, as JavaScript does for bitwise operands."""
+
+    if isinstance(value, float) and not math.isfinite(value):
+        return 0
+    wrapped = int(value) & 0xFFFFFFFF
+    return wrapped - (1 << 32) if wrapped & 0x80000000 else wrapped
+
+
+def _shift(operator: str, value: Any, amount: Any) -> int:
+    """`<<` and `>>` on signed 32-bit integers, as in JavaScript; the amount is taken modulo 32."""
+
+    for operand in (value, amount):
+        if runtime_type_name(operand) != "numerus":
+            raise errors.ExecutionError(f"Operator '{operator}' requires numerus, got {runtime_type_name(operand)}.")
+    bits = _to_int32(amount) & 31
+    return _to_int32(_to_int32(value) << bits) if operator == "<<" else _to_int32(value) >> bits
+
+
+def _power(base: Any, exponent: Any) -> Any:
+    """`**` on doubles, as `Math.pow`: negative and fractional exponents never truncate."""
+
+    if isinstance(base, int) and isinstance(exponent, int) and exponent >= 0:
+        # Exact only while the result is a safe integer, so `2 ** 1000000000` is infinitum at once.
+        if exponent == 0 or abs(base) <= 1 or exponent * math.log2(abs(base)) <= 53:
+            return base**exponent
+    base, exponent = _to_double(base), _to_double(exponent)
+    if math.isinf(exponent) and abs(base) == 1:
+        return math.nan
+    odd = float(exponent).is_integer() and int(exponent) % 2 == 1
+    try:
+        return math.pow(base, exponent)
+    except ValueError:
+        # Python rejects 0 ** -n and fractional powers of negative numbers.
+        if base == 0:
+            return math.copysign(math.inf, base) if odd else math.inf
+        return math.nan
+    except OverflowError:
+        return -math.inf if base < 0 and odd else math.inf
+
+
+def _to_double(value: Any) -> float:
+    """*value* as a double; an int too large for one becomes infinitum with its sign."""
+
+    try:
+        return float(value)
+    except OverflowError:
+        return math.inf if value > 0 else -math.inf
+
+
+def _divide(left: Any, right: Any) -> Any:
+    """`/` on doubles: dividing by zero gives infinitum with the sign of the quotient, and 0 / 0 gives nan."""
+
+    if right == 0:
+        if left == 0 or math.isnan(left):
+            return math.nan
+        return math.copysign(math.inf, left) * math.copysign(1, right)
+    return left / right
+
+
+def _remainder(left: Any, right: Any) -> Any:
+    """`%` truncates like JavaScript: the result has the sign of *left*, and `x % infinitum` is `x`."""
+
+    if right == 0 or (isinstance(left, float) and math.isinf(left)):
+        # Python raises where doubles produce nan.
+        return math.nan
+    if isinstance(left, int) and isinstance(right, int):
+        # Exact for whole numbers beyond the range of a double.
+        remainder = abs(left) % abs(right)
+        return -remainder if left < 0 else remainder
+    return math.fmod(left, right)
+
+
+def display_value(value: Any, nested: bool = False) -> str:
+    """
+    How `imprime` shows *value*, with Scriptum spellings for keywords and special numbers.
+
+    Text is written as is at the top level and quoted inside arrays and objects. Tuples are
+    lists at run time, so they are shown like arrays.
+    """
+
+    if value is None:
+        return "nullum"
+    if isinstance(value, bool):
+        return "verum" if value else "falsum"
+    if isinstance(value, float):
+        if math.isnan(value):
+            return "nan"
+        if math.isinf(value):
+            return "infinitum" if value > 0 else "-infinitum"
+        return str(int(value)) if value.is_integer() and abs(value) < 1e21 else repr(value)
+    if isinstance(value, str):
+        return json.dumps(value, ensure_ascii=False) if nested else value
+    if isinstance(value, list):
+        return "[" + ", ".join(display_value(item, nested=True) for item in value) + "]"
+    if isinstance(value, dict):
+        fields = (f"{property_key(key)}: {display_value(item, nested=True)}" for key, item in value.items())
+        return "{" + ", ".join(fields) + "}"
+    if isinstance(value, NumericRange):
+        return f"{display_value(value.start)}{'..=' if value.inclusive else '..'}{display_value(value.end)}"
+    if hasattr(value, "call"):
+        return "functio"
+    return str(value)
+
+
+def _imprime(interpreter: Interpreter, args: List[Any]) -> None:
+    output = interpreter.output or sys.stdout
+    output.write(" ".join(display_value(arg) for arg in args) + "\n")
+
+
+NATIVES: Dict[str, RuntimeNative] = {
+    "imprime": RuntimeNative("imprime", _imprime),
+}
+
+
+def _native_environment() -> Environment:
+    env = Environment()
+    for name, native in NATIVES.items():
+        env.declare(name, native, mutable=False)
+    return env
+
+
+def _index_text(text: str, index: Any) -> str:
+    """`texto[i]` and `texto[a..b]`, counted in characters, so a multi-byte character is never split."""
+
+    if isinstance(index, NumericRange):
+        start, end = _text_position(index.start), _text_position(index.end)
+        stop = end + 1 if index.inclusive else end
+        if not 0 <= start <= stop <= len(text):
+            operator = "..=" if index.inclusive else ".."
+            raise errors.ExecutionError(
+                f"Slice {start}{operator}{end} is out of range for a textus of length {len(text)}."
+            )
+        return text[start:stop]
+    position = _text_position(index)
+    if not 0 <= position < len(text):
+        raise errors.ExecutionError(f"Index {position} is out of range for a textus of length {len(text)}.")
+    return text[position]
+
+
+def _text_position(value: Any) -> int:
+    whole = isinstance(value, int) or (isinstance(value, float) and value.is_integer())
+    if isinstance(value, bool) or not whole:
+        raise errors.ExecutionError(f"A textus is indexed by a whole numerus or a range, got {display_value(value)}.")
+    return int(value)
+
+
+def runtime_type_name(value: Any) -> str:
+    """Name of the Scriptum type that *value* carries at runtime."""
+
+    if value is None:
+        return "nullum"
+    if isinstance(value, bool):
+        return "booleanum"
+    if isinstance(value, (int, float)):
+        return "numerus"
+    if isinstance(value, str):
+        return "textus"
+    if isinstance(value, list):
+        return "array"
+    if isinstance(value, dict):
+        return "structura"
+    if isinstance(value, NumericRange):
+        return "range"
+    if hasattr(value, "call"):
+        return "functio"
+    return "quodlibet"
+
+
+def _values_equal(left: Any, right: Any) -> bool:
+    # Python treats True == 1; Scriptum booleans never compare equal to numbers.
+    if isinstance(left, bool) != isinstance(right, bool):
+        return False
+    return left == right
+
+
+_BINARY_OPERATIONS = {
+    "ADD": lambda a, b: a + b,
+    "+": lambda a, b: a + b,
+    "SUB": lambda a, b: a - b,
+    "-": lambda a, b: a - b,
+    "MUL": lambda a, b: a * b,
+    "*": lambda a, b: a * b,
+    "DIV": _divide,
+    "/": _divide,
+    "MOD": _remainder,
+    "%": _remainder,
+    "POW": _power,
+    "**": _power,
+    "SHL": lambda a, b: _shift("<<", a, b),
+    "<<": lambda a, b: _shift("<<", a, b),
+    "SHR": lambda a, b: _shift(">>", a, b),
+    ">>": lambda a, b: _shift(">>", a, b),
+    "GT": lambda a, b: a > b,
+    ">": lambda a, b: a > b,
+    "GE": lambda a, b: a >= b,
+    ">=": lambda a, b: a >= b,
+    "LT": lambda a, b: a < b,
+    "<": lambda a, b: a < b,
+    "LE": lambda a, b: a <= b,
+    "<=": lambda a, b: a <= b,
+    "EQ": _values_equal,
+    "==": _values_equal,
+    "NE": lambda a, b: not _values_equal(a, b),
+    "!=": lambda a, b: not _values_equal(a, b),
+    "STRICT_EQ": _values_equal,
+    "===": _values_equal,
+    "STRICT_NE": lambda a, b: not _values_equal(a, b),
+    "!==": lambda a, b: not _values_equal(a, b),
+}
@@ -3,7 +3,7 @@
 from __future__ import annotations
 
 import json
-from dataclasses import dataclass, fields
+from dataclasses import MISSING, dataclass, field, fields
 from typing import Any, List, Optional
 
 from ..text import Span
@@ -18,18 +18,34 @@ class IrNode:
 class ModuleIr(IrNode):
     globals: List["IrVariable"]
     functions: List["IrFunction"]
+    imports: List["IrImport"] = field(default_factory=list)
 
 
 # Backwards-compatible alias.
 IrModule = ModuleIr
 
 
+@dataclass(slots=True)
+class IrImportName(IrNode):
+    name: str
+    alias: Optional[str] = None
+
+
+@dataclass(slots=True)
+class IrImport(IrNode):
+    path: str
+    alias: Optional[str] = None
+    names: List[IrImportName] = field(default_factory=list)
+
+
 @dataclass(slots=True)
 class IrVariable(IrNode):
     name: str
     mutable: bool
     type_annotation: Optional[str]
     initializer: Optional["IrExpr"]
+    visibility: Optional[str] = None
+    doc: List[str] = field(default_factory=list)
 
 
 @dataclass(slots=True)
@@ -45,11 +61,15 @@ class IrFunction(IrNode):
     parameters: List[IrParameter]
     return_annotation: Optional[str]
     body: List["IrStatement"]
+    visibility: Optional[str] = None
+    type_parameters: List[str] = field(default_factory=list)
+    doc: List[str] = field(default_factory=list)
 
 
 @dataclass(slots=True)
 class IrStatement(IrNode):
-    pass
+    # `node_id` of the AST node this was lowered from, or None for nodes a pass made up.
+    node_id: Optional[int] = field(default=None, kw_only=True)
 
 
 @dataclass(slots=True)
@@ -65,6 +85,13 @@ class IrVariableDeclaration(IrStatement):
     initializer: Optional["IrExpr"]
 
 
+@dataclass(slots=True)
+class IrDestructuring(IrStatement):
+    names: List[str]
+    mutable: bool
+    initializer: "IrExpr"
+
+
 @dataclass(slots=True)
 class IrIf(IrStatement):
     condition: "IrExpr"
@@ -74,8 +101,13 @@ class IrIf(IrStatement):
 
 @dataclass(slots=True)
 class IrWhile(IrStatement):
-    condition: "IrExpr"
+    """`dum`, and also C-style `pro`: *init* runs once before the loop and *update* after every iteration."""
+
+    condition: Optional["IrExpr"]
     body: List[IrStatement]
+    label: Optional[str] = None
+    init: Optional[IrStatement] = None
+    update: Optional["IrExpr"] = None
 
 
 @dataclass(slots=True)
@@ -90,6 +122,7 @@ class IrForIn(IrStatement):
     target: IrForTarget
     iterable: "IrExpr"
     body: List[IrStatement]
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
@@ -99,17 +132,18 @@ class IrReturn(IrStatement):
 
 @dataclass(slots=True)
 class IrBreak(IrStatement):
-    pass
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
 class IrContinue(IrStatement):
-    pass
+    label: Optional[str] = None
 
 
 @dataclass(slots=True)
 class IrExpr(IrNode):
-    pass
+    # `node_id` of the AST node this was lowered from, or None for nodes a pass made up.
+    node_id: Optional[int] = field(default=None, kw_only=True)
 
 
 @dataclass(slots=True)
@@ -136,6 +170,26 @@ class IrBinary(IrExpr):
     right: IrExpr
 
 
+@dataclass(slots=True)
+class IrUpdate(IrExpr):
+    operator: str
+    target: IrIdentifier
+    prefix: bool
+
+
+@dataclass(slots=True)
+class IrCast(IrExpr):
+    value: IrExpr
+    type_name: str
+
+
+@dataclass(slots=True)
+class IrRange(IrExpr):
+    start: IrExpr
+    end: IrExpr
+    inclusive: bool = False
+
+
 @dataclass(slots=True)
 class IrAssignment(IrExpr):
     target: IrExpr
@@ -153,18 +207,22 @@ class IrConditional(IrExpr):
 class IrCall(IrExpr):
     callee: IrExpr
     arguments: List[IrExpr]
+    type_arguments: List[str] = field(default_factory=list)
+    trailing_lambda: bool = False
 
 
 @dataclass(slots=True)
 class IrMemberAccess(IrExpr):
     object: IrExpr
     property: str
+    optional: bool = False
 
 
 @dataclass(slots=True)
 class IrIndex(IrExpr):
     collection: IrExpr
     index: IrExpr
+    optional: bool = False
 
 
 @dataclass(slots=True)
@@ -172,10 +230,16 @@ class IrArrayLiteral(IrExpr):
     elements: List[IrExpr]
 
 
+@dataclass(slots=True)
+class IrTupleLiteral(IrExpr):
+    elements: List[IrExpr]
+
+
 @dataclass(slots=True)
 class IrObjectProperty(IrNode):
     key: str
     value: IrExpr
+    comment: Optional[str] = None
 
 
 @dataclass(slots=True)
@@ -189,6 +253,8 @@ class IrLambda(IrExpr):
     return_annotation: Optional[str]
     body_expression: Optional[IrExpr]
     body_statements: List[IrStatement]
+    # Written as a property of an object literal: calls through a member receive the object as `ipse`.
+    method: bool = False
 
 
 def _serialize_span(span: Span) -> list[int]:
@@ -227,3 +293,44 @@ def format_module_ir(module: ModuleIr) -> str:
 
     payload = _serialize_node(module)
     return json.dumps(payload, indent=2, ensure_ascii=False, sort_keys=False)
+
+
+def format_ir(module: ModuleIr) -> str:
+    """
+    Return a readable tree of *module*, one node per line with its span, for debugging passes.
+
+    Statements and expressions lowered from the AST show its `node_id` as `#id` after the span.
+    Scalar fields are shown inline as `name=value` and child nodes below, indented under the
+    field that holds them. Fields that are absent or still hold their default are left out.
+    """
+
+    lines: List[str] = []
+    _format_ir_node(module, "", 0, lines)
+    return "\n".join(lines)
+
+
+def _format_ir_node(node: IrNode, prefix: str, depth: int, lines: List[str]) -> None:
+    inline: List[str] = []
+    children: List[tuple[str, Any]] = []
+    for item in fields(node):
+        if item.name in {"span", "node_id"}:
+            continue
+        value = getattr(node, item.name)
+        if value is None or value == [] or (item.default is not MISSING and value == item.default):
+            continue
+        if isinstance(value, IrNode) or (isinstance(value, list) and isinstance(value[0], IrNode)):
+            children.append((item.name, value))
+        else:
+            inline.append(f"{item.name}={json.dumps(value, ensure_ascii=False, default=str)}")
+    header = f"{node.__class__.__name__} {node.span.start}..{node.span.end}"
+    if getattr(node, "node_id", None) is not None:
+        header += f" #{node.node_id}"
+    lines.append("  " * depth + prefix + " ".join([header, *inline]))
+    for name, value in children:
+        if isinstance(value, IrNode):
+            _format_ir_node(value, f"{name}: ", depth + 1, lines)
+            continue
+        lines.append("  " * (depth + 1) + f"{name}:")
+        for child in value:
+            _format_ir_node(child, "", depth + 2, lines)
+
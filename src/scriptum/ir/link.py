@@ -0,0 +1,187 @@
+"""Combine the IR of a module and its imports into one executable module."""
+
+from __future__ import annotations
+
+from dataclasses import fields, replace
+from typing import Dict, List, Mapping, Optional, Set, TypeVar
+
+from .. import errors
+from .ir import (
+    IrDestructuring,
+    IrForIn,
+    IrFunction,
+    IrIdentifier,
+    IrIf,
+    IrImport,
+    IrLambda,
+    IrMemberAccess,
+    IrNode,
+    IrObjectLiteral,
+    IrObjectProperty,
+    IrStatement,
+    IrVariable,
+    IrVariableDeclaration,
+    IrWhile,
+    ModuleIr,
+)
+
+_Node = TypeVar("_Node", bound=IrNode)
+
+
+def link_modules(
+    module: ModuleIr,
+    dependencies: Mapping[str, ModuleIr],
+    exports: Optional[Mapping[str, List[str]]] = None,
+) -> ModuleIr:
+    """
+    Merge *dependencies* and *module* into a single module without imports.
+
+    *dependencies* maps an import path to the IR of that module and must be
+    in load order so that every global is initialised before the modules that
+    import it. *exports* maps an import path to the names that module
+    exports; it is needed for namespace imports (`importa "m.stm" ut m;`) and
+    for imports of a whole module (`importa "m.stm";`).
+
+    Every top-level name of a dependency becomes `path::name`, so it cannot
+    clash with a name of another module. Imported names, renamed or not, and
+    `m.name` on a namespace are resolved to that global, so they read its
+    current value rather than a copy taken at load time.
+    """
+
+    exports = exports or {}
+    globals_: List[IrVariable] = []
+    functions: List[IrFunction] = []
+    seen: Set[str] = set()
+    for path, part in (*dependencies.items(), (None, module)):
+        own = {} if path is None else {
+            item.name: _linked(path, item.name) for item in (*part.globals, *part.functions)
+        }
+        imported: Dict[str, str] = {}
+        namespaces: Dict[str, Dict[str, str]] = {}
+        namespace_globals: List[IrVariable] = []
+        for imp in part.imports:
+            members = {name: _linked(imp.path, name) for name in exports.get(imp.path, ())}
+            if imp.alias is not None:
+                namespaces[imp.alias] = members
+                own[imp.alias] = imp.alias if path is None else _linked(path, imp.alias)
+                namespace_globals.append(_namespace(own[imp.alias], imp, members))
+            elif imp.names:
+                imported.update((item.alias or item.name, _linked(imp.path, item.name)) for item in imp.names)
+            else:
+                imported.update(members)
+        resolver = _Resolver({**imported, **own}, namespaces)
+        part_globals = [*namespace_globals, *(resolver.variable(item) for item in part.globals)]
+        part_functions = [resolver.function(item) for item in part.functions]
+        for item in (*part_globals, *part_functions):
+            if item.name in seen:
+                raise errors.ModuleImportError(f"Top-level name '{item.name}' is defined by more than one module")
+            seen.add(item.name)
+        globals_.extend(part_globals)
+        functions.extend(part_functions)
+    return ModuleIr(span=module.span, globals=globals_, functions=functions)
+
+
+def _linked(path: str, name: str) -> str:
+    return f"{path}::{name}"
+
+
+def _namespace(name: str, imp: IrImport, members: Mapping[str, str]) -> IrVariable:
+    """The object a namespace evaluates to when used as a value rather than through `m.name`."""
+
+    properties = [
+        IrObjectProperty(span=imp.span, key=key, value=IrIdentifier(span=imp.span, name=linked))
+        for key, linked in members.items()
+    ]
+    initializer = IrObjectLiteral(span=imp.span, properties=properties)
+    return IrVariable(span=imp.span, name=name, mutable=False, type_annotation=None, initializer=initializer)
+
+
+class _Resolver:
+    """Respells the top-level names one module uses as the globals they are linked to."""
+
+    def __init__(self, names: Mapping[str, str], namespaces: Mapping[str, Mapping[str, str]]) -> None:
+        self.names = names
+        self.namespaces = namespaces
+        # Locals in scope, innermost last; they shadow the module's top-level names.
+        self.scopes: List[Set[str]] = []
+
+    def variable(self, var: IrVariable) -> IrVariable:
+        return replace(var, name=self.names.get(var.name, var.name), initializer=self._node(var.initializer))
+
+    def function(self, func: IrFunction) -> IrFunction:
+        parameters = [self._node(param) for param in func.parameters]
+        self.scopes.append({param.name for param in func.parameters})
+        body = self._block(func.body)
+        self.scopes.pop()
+        return replace(func, name=self.names.get(func.name, func.name), parameters=parameters, body=body)
+
+    def _global(self, name: str) -> bool:
+        return not any(name in scope for scope in self.scopes)
+
+    def _block(self, statements: List[IrStatement], *bound: str) -> List[IrStatement]:
+        self.scopes.append(set(bound))
+        result = [self._node(stmt) for stmt in statements]
+        self.scopes.pop()
+        return result
+
+    def _node(self, node: Optional[_Node]) -> Optional[_Node]:
+        if node is None:
+            return None
+        if isinstance(node, IrIdentifier):
+            if node.name in self.names and self._global(node.name):
+                return replace(node, name=self.names[node.name])
+            return node
+        if (
+            isinstance(node, IrMemberAccess)
+            and isinstance(node.object, IrIdentifier)
+            and node.property in self.namespaces.get(node.object.name, {})
+            and self._global(node.object.name)
+        ):
+            return IrIdentifier(span=node.span, name=self.namespaces[node.object.name][node.property])
+        if isinstance(node, (IrVariableDeclaration, IrDestructuring)):
+            result = replace(node, initializer=self._node(node.initializer))
+            names = [node.name] if isinstance(node, IrVariableDeclaration) else node.names
+            self.scopes[-1].update(names)
+            return result
+        if isinstance(node, IrIf):
+            return replace(
+                node,
+                condition=self._node(node.condition),
+                then_branch=self._block(node.then_branch),
+                else_branch=self._block(node.else_branch),
+            )
+        if isinstance(node, IrWhile):
+            # The initialiser of a C-style `pro` is scoped to the loop.
+            self.scopes.append(set())
+            result = replace(
+                node,
+                init=self._node(node.init),
+                condition=self._node(node.condition),
+                body=self._block(node.body),
+                update=self._node(node.update),
+            )
+            self.scopes.pop()
+            return result
+        if isinstance(node, IrForIn):
+            iterable = self._node(node.iterable)
+            return replace(node, iterable=iterable, body=self._block(node.body, node.target.name))
+        if isinstance(node, IrLambda):
+            parameters = [self._node(param) for param in node.parameters]
+            bound = {param.name for param in node.parameters} | ({"ipse"} if node.method else set())
+            self.scopes.append(bound)
+            body_expression = self._node(node.body_expression)
+            self.scopes.pop()
+            return replace(
+                node,
+                parameters=parameters,
+                body_expression=body_expression,
+                body_statements=self._block(node.body_statements, *bound),
+            )
+        changes = {}
+        for item in fields(node):
+            value = getattr(node, item.name)
+            if isinstance(value, IrNode):
+                changes[item.name] = self._node(value)
+            elif isinstance(value, list) and any(isinstance(element, IrNode) for element in value):
+                changes[item.name] = [self._node(element) for element in value]
+        return replace(node, **changes)
@@ -11,8 +11,10 @@ from .ir import (
     IrBinary,
     IrBreak,
     IrCall,
+    IrCast,
     IrConditional,
     IrContinue,
+    IrDestructuring,
     IrExpr,
     IrExpressionStatement,
     IrForIn,
@@ -20,6 +22,8 @@ from .ir import (
     IrFunction,
     IrIdentifier,
     IrIf,
+    IrImport,
+    IrImportName,
     IrIndex,
     IrLambda,
     IrLiteral,
@@ -28,9 +32,12 @@ from .ir import (
     IrObjectLiteral,
     IrObjectProperty,
     IrParameter,
+    IrRange,
     IrReturn,
     IrStatement,
+    IrTupleLiteral,
     IrUnary,
+    IrUpdate,
     IrVariable,
     IrVariableDeclaration,
     IrWhile,
@@ -42,14 +49,26 @@ from .ir import IrNode  # re-exported for type checking
 def lower_module(module: nodes.Module) -> ModuleIr:
     globals_ir: List[IrVariable] = []
     functions_ir: List[IrFunction] = []
+    imports_ir: List[IrImport] = []
 
     for declaration in module.declarations:
-        if isinstance(declaration, nodes.FunctionDeclaration):
+        if isinstance(declaration, nodes.ImportDeclaration):
+            imports_ir.append(
+                IrImport(
+                    span=declaration.span,
+                    path=declaration.path,
+                    alias=declaration.alias,
+                    names=[
+                        IrImportName(span=item.span, name=item.name, alias=item.alias) for item in declaration.names
+                    ],
+                )
+            )
+        elif isinstance(declaration, nodes.FunctionDeclaration):
             functions_ir.append(_lower_function(declaration))
         elif isinstance(declaration, nodes.VariableDeclaration):
             globals_ir.append(_lower_global_variable(declaration))
 
-    return IrModule(span=module.span, globals=globals_ir, functions=functions_ir)
+    return IrModule(span=module.span, globals=globals_ir, functions=functions_ir, imports=imports_ir)
 
 
 def _lower_global_variable(decl: nodes.VariableDeclaration) -> IrVariable:
@@ -61,6 +80,8 @@ def _lower_global_variable(decl: nodes.VariableDeclaration) -> IrVariable:
         mutable=decl.mutable,
         type_annotation=annotation,
         initializer=initializer,
+        visibility=_visibility_name(decl.visibility),
+        doc=list(decl.doc),
     )
 
 
@@ -74,6 +95,9 @@ def _lower_function(func: nodes.FunctionDeclaration) -> IrFunction:
         parameters=parameters,
         return_annotation=return_annotation,
         body=body_statements,
+        visibility=_visibility_name(func.visibility),
+        type_parameters=[param.name for param in func.type_parameters],
+        doc=list(func.doc),
     )
 
 
@@ -109,23 +133,33 @@ def _lower_single_statement(stmt: nodes.Statement) -> IrStatement:
         annotation = _annotation_name(stmt.type_annotation)
         return IrVariableDeclaration(
             span=stmt.span,
+            node_id=stmt.node_id,
             name=stmt.name,
             mutable=stmt.mutable,
             type_annotation=annotation,
             initializer=initializer,
         )
+    if isinstance(stmt, nodes.DestructuringDeclaration):
+        return IrDestructuring(
+            span=stmt.span,
+            node_id=stmt.node_id,
+            names=list(stmt.names),
+            mutable=stmt.mutable,
+            initializer=_lower_expression(stmt.initializer),
+        )
     if isinstance(stmt, nodes.ExpressionStatement):
         expr = _lower_expression(stmt.expression)
-        return IrExpressionStatement(span=stmt.span, expression=expr)
+        return IrExpressionStatement(span=stmt.span, node_id=stmt.node_id, expression=expr)
     if isinstance(stmt, nodes.ReturnStatement):
         value = _lower_expression(stmt.value) if stmt.value else None
-        return IrReturn(span=stmt.span, value=value)
+        return IrReturn(span=stmt.span, node_id=stmt.node_id, value=value)
     if isinstance(stmt, nodes.IfStatement):
         condition = _lower_expression(stmt.condition)
         then_branch = _lower_statement(stmt.then_branch)
         else_branch = _lower_statement(stmt.else_branch) if stmt.else_branch else []
         return IrIf(
             span=stmt.span,
+            node_id=stmt.node_id,
             condition=condition,
             then_branch=then_branch,
             else_branch=else_branch,
@@ -133,7 +167,19 @@ def _lower_single_statement(stmt: nodes.Statement) -> IrStatement:
     if isinstance(stmt, nodes.WhileStatement):
         condition = _lower_expression(stmt.condition)
         body = _lower_statement(stmt.body)
-        return IrWhile(span=stmt.span, condition=condition, body=body)
+        return IrWhile(span=stmt.span, node_id=stmt.node_id, condition=condition, body=body, label=stmt.label)
+    if isinstance(stmt, nodes.CStyleForStatement):
+        # Lowered to a `dum` that carries its initialiser and update; a missing condition stays None (always true).
+        init = _lower_single_statement(stmt.init) if stmt.init is not None else None
+        return IrWhile(
+            span=stmt.span,
+            node_id=stmt.node_id,
+            condition=_lower_expression(stmt.condition),
+            body=_lower_statement(stmt.body),
+            label=stmt.label,
+            init=init,
+            update=_lower_expression(stmt.update),
+        )
     if isinstance(stmt, nodes.ForStatement):
         iterable = _lower_expression(stmt.iterable)
         target_annotation = _annotation_name(stmt.target.type_annotation)
@@ -144,11 +190,13 @@ def _lower_single_statement(stmt: nodes.Statement) -> IrStatement:
             type_annotation=target_annotation,
         )
         body = _lower_statement(stmt.body)
-        return IrForIn(span=stmt.span, target=target, iterable=iterable, body=body)
+        return IrForIn(
+            span=stmt.span, node_id=stmt.node_id, target=target, iterable=iterable, body=body, label=stmt.label
+        )
     if isinstance(stmt, nodes.BreakStatement):
-        return IrBreak(span=stmt.span)
+        return IrBreak(span=stmt.span, node_id=stmt.node_id, label=stmt.label)
     if isinstance(stmt, nodes.ContinueStatement):
-        return IrContinue(span=stmt.span)
+        return IrContinue(span=stmt.span, node_id=stmt.node_id, label=stmt.label)
     raise TypeError(f"Unsupported statement type: {type(stmt)!r}")
 
 
@@ -156,28 +204,49 @@ def _lower_expression(expr: Optional[nodes.Expression]) -> Optional[IrExpr]:
     if expr is None:
         return None
     if isinstance(expr, nodes.Identifier):
-        return IrIdentifier(span=expr.span, name=expr.name)
+        return IrIdentifier(span=expr.span, node_id=expr.node_id, name=expr.name)
     if isinstance(expr, nodes.Literal):
-        return IrLiteral(span=expr.span, value=expr.value, raw=expr.raw)
+        return IrLiteral(span=expr.span, node_id=expr.node_id, value=expr.value, raw=expr.raw)
     if isinstance(expr, nodes.UnaryExpression):
         operand = _lower_expression(expr.operand)
         operator = expr.operator.name if isinstance(expr.operator, nodes.UnaryOperator) else str(expr.operator)
-        return IrUnary(span=expr.span, operator=operator, operand=operand)
+        return IrUnary(span=expr.span, node_id=expr.node_id, operator=operator, operand=operand)
     if isinstance(expr, nodes.BinaryExpression):
         left = _lower_expression(expr.left)
         right = _lower_expression(expr.right)
         operator = expr.operator.name if hasattr(expr.operator, "name") else str(expr.operator)
-        return IrBinary(span=expr.span, operator=operator, left=left, right=right)
+        return IrBinary(span=expr.span, node_id=expr.node_id, operator=operator, left=left, right=right)
+    if isinstance(expr, nodes.UpdateExpression):
+        target = IrIdentifier(span=expr.operand.span, node_id=expr.operand.node_id, name=expr.operand.name)
+        return IrUpdate(
+            span=expr.span, node_id=expr.node_id, operator=expr.operator.name, target=target, prefix=expr.prefix
+        )
+    if isinstance(expr, nodes.CastExpression):
+        return IrCast(
+            span=expr.span,
+            node_id=expr.node_id,
+            value=_lower_expression(expr.expression),
+            type_name=expr.target_type.name,
+        )
+    if isinstance(expr, nodes.RangeExpression):
+        return IrRange(
+            span=expr.span,
+            node_id=expr.node_id,
+            start=_lower_expression(expr.start),
+            end=_lower_expression(expr.end),
+            inclusive=expr.inclusive,
+        )
     if isinstance(expr, nodes.AssignmentExpression):
         target = _lower_expression(expr.target)
         value = _lower_expression(expr.value)
-        return IrAssignment(span=expr.span, target=target, value=value)
+        return IrAssignment(span=expr.span, node_id=expr.node_id, target=target, value=value)
     if isinstance(expr, nodes.ConditionalExpression):
         condition = _lower_expression(expr.condition)
         consequent = _lower_expression(expr.consequent)
         alternate = _lower_expression(expr.alternate)
         return IrConditional(
             span=expr.span,
+            node_id=expr.node_id,
             condition=condition,
             consequent=consequent,
             alternate=alternate,
@@ -185,23 +254,41 @@ def _lower_expression(expr: Optional[nodes.Expression]) -> Optional[IrExpr]:
     if isinstance(expr, nodes.CallExpression):
         callee = _lower_expression(expr.callee)
         arguments = [_lower_expression(arg) for arg in expr.arguments]
-        return IrCall(span=expr.span, callee=callee, arguments=arguments)
+        type_arguments = [argument.name for argument in expr.type_arguments]
+        return IrCall(
+            span=expr.span,
+            node_id=expr.node_id,
+            callee=callee,
+            arguments=arguments,
+            type_arguments=type_arguments,
+            trailing_lambda=expr.trailing_lambda,
+        )
     if isinstance(expr, nodes.MemberExpression):
         obj = _lower_expression(expr.object)
-        return IrMemberAccess(span=expr.span, object=obj, property=expr.property)
+        return IrMemberAccess(
+            span=expr.span, node_id=expr.node_id, object=obj, property=expr.property, optional=expr.optional
+        )
     if isinstance(expr, nodes.IndexExpression):
         collection = _lower_expression(expr.collection)
         index = _lower_expression(expr.index)
-        return IrIndex(span=expr.span, collection=collection, index=index)
+        return IrIndex(
+            span=expr.span, node_id=expr.node_id, collection=collection, index=index, optional=expr.optional
+        )
     if isinstance(expr, nodes.ArrayLiteral):
         elements = [_lower_expression(element) for element in expr.elements]
-        return IrArrayLiteral(span=expr.span, elements=elements)
+        return IrArrayLiteral(span=expr.span, node_id=expr.node_id, elements=elements)
+    if isinstance(expr, nodes.TupleLiteral):
+        elements = [_lower_expression(element) for element in expr.elements]
+        return IrTupleLiteral(span=expr.span, node_id=expr.node_id, elements=elements)
     if isinstance(expr, nodes.ObjectLiteral):
         properties = [
-            IrObjectProperty(span=prop.span, key=prop.key, value=_lower_expression(prop.value))
+            IrObjectProperty(span=prop.span, key=prop.key, value=_lower_expression(prop.value), comment=prop.comment)
             for prop in expr.properties
         ]
-        return IrObjectLiteral(span=expr.span, properties=properties)
+        for prop in properties:
+            if isinstance(prop.value, IrLambda):
+                prop.value.method = True
+        return IrObjectLiteral(span=expr.span, node_id=expr.node_id, properties=properties)
     if isinstance(expr, nodes.LambdaExpression):
         parameters = [_lower_parameter(param) for param in expr.parameters]
         return_annotation = _annotation_name(expr.return_type)
@@ -213,6 +300,7 @@ def _lower_expression(expr: Optional[nodes.Expression]) -> Optional[IrExpr]:
             body_expression = _lower_expression(expr.body)
         return IrLambda(
             span=expr.span,
+            node_id=expr.node_id,
             parameters=parameters,
             return_annotation=return_annotation,
             body_expression=body_expression,
@@ -225,3 +313,9 @@ def _annotation_name(annotation: Optional[nodes.TypeAnnotation]) -> Optional[str
     if annotation is None:
         return None
     return annotation.name
+
+
+def _visibility_name(visibility: Optional[nodes.Visibility]) -> Optional[str]:
+    if visibility is None:
+        return None
+    return visibility.value
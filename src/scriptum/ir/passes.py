@@ -0,0 +1,292 @@
+"""Optional IR-to-IR transformations."""
+
+from __future__ import annotations
+
+from dataclasses import fields
+from typing import Dict, Iterator, List, Optional
+
+from .ir import (
+    IrAssignment,
+    IrBinary,
+    IrCall,
+    IrCast,
+    IrConditional,
+    IrDestructuring,
+    IrExpr,
+    IrExpressionStatement,
+    IrForIn,
+    IrFunction,
+    IrIdentifier,
+    IrIf,
+    IrLambda,
+    IrLiteral,
+    IrNode,
+    IrRange,
+    IrStatement,
+    IrUnary,
+    IrUpdate,
+    IrVariableDeclaration,
+    IrWhile,
+    ModuleIr,
+)
+
+
+def flatten_conditional_statements(module: ModuleIr) -> ModuleIr:
+    """
+    Rewrite `c ? a : b;` used as a statement into `si (c) { a; } aliter { b; }`.
+
+    Only conditionals whose branches have side effects (calls or assignments)
+    are rewritten; a branch without effects becomes an empty block. The module
+    is updated in place and returned for convenience.
+    """
+
+    for func in module.functions:
+        func.body = _flatten_block(func.body)
+    for var in module.globals:
+        if var.initializer is not None:
+            _flatten_lambdas(var.initializer)
+    return module
+
+
+def _flatten_block(statements: List[IrStatement]) -> List[IrStatement]:
+    return [_flatten_statement(stmt) for stmt in statements]
+
+
+def _flatten_statement(stmt: IrStatement) -> IrStatement:
+    if isinstance(stmt, IrExpressionStatement) and isinstance(stmt.expression, IrConditional):
+        conditional = stmt.expression
+        if has_side_effects(conditional.consequent) or has_side_effects(conditional.alternate):
+            _flatten_lambdas(conditional.condition)
+            return IrIf(
+                span=stmt.span,
+                node_id=stmt.node_id,
+                condition=conditional.condition,
+                then_branch=_branch(conditional.consequent),
+                else_branch=_branch(conditional.alternate),
+            )
+    if isinstance(stmt, IrIf):
+        stmt.then_branch = _flatten_block(stmt.then_branch)
+        stmt.else_branch = _flatten_block(stmt.else_branch)
+    elif isinstance(stmt, (IrWhile, IrForIn)):
+        stmt.body = _flatten_block(stmt.body)
+    if isinstance(stmt, IrWhile) and stmt.init is not None:
+        # The initialiser sits in the loop header, where only its lambdas may change shape.
+        _flatten_lambdas(stmt.init)
+    for child in _child_nodes(stmt):
+        if not isinstance(child, IrStatement):
+            _flatten_lambdas(child)
+    return stmt
+
+
+def _branch(expr: IrExpr) -> List[IrStatement]:
+    if not has_side_effects(expr):
+        return []
+    return [_flatten_statement(IrExpressionStatement(span=expr.span, node_id=expr.node_id, expression=expr))]
+
+
+def _flatten_lambdas(node: IrNode) -> None:
+    if isinstance(node, IrLambda):
+        node.body_statements = _flatten_block(node.body_statements)
+    for child in _child_nodes(node):
+        if not isinstance(child, IrStatement):
+            _flatten_lambdas(child)
+
+
+def simplify_expressions(module: ModuleIr) -> ModuleIr:
+    """
+    Apply algebraic identities: `x + 0`, `0 + x`, `x - 0`, `x * 1` and `1 * x` become `x`,
+    `x && verum`, `verum && x`, `x || falsum` and `falsum || x` become `x`, and `x * 0` becomes `0`.
+
+    The IR is untyped, so a rule only fires when the shape of `x` or the annotation of the binding it
+    names shows the operator could not have done anything else: `+` also joins textus, `&&`/`||`
+    reject values that are not booleanum, and `nan * 0` is nan. For the same reason `x * 0` only
+    drops an `x` without side effects whose value is a whole number, such as `n << 2`. The module is
+    updated in place and returned for convenience.
+    """
+
+    simplifier = _Simplifier({var.name: var.type_annotation for var in module.globals})
+    for var in module.globals:
+        if var.initializer is not None:
+            var.initializer = simplifier.rewrite(var.initializer)
+    for func in module.functions:
+        simplifier.rewrite(func)
+    return module
+
+
+_NUMERIC_OPERATORS = {"SUB", "DIV", "MOD", "POW", "SHL", "SHR"}
+_BOOLEAN_OPERATORS = {"GT", "GE", "LT", "LE", "EQ", "NE", "STRICT_EQ", "STRICT_NE", "AND", "OR"}
+
+
+class _Simplifier:
+    def __init__(self, globals_: Dict[str, Optional[str]]) -> None:
+        # The annotation of every binding in scope, innermost last.
+        self._scopes: List[Dict[str, Optional[str]]] = [globals_]
+
+    def rewrite(self, node: IrNode) -> IrNode:
+        if isinstance(node, (IrFunction, IrLambda)):
+            self._scopes.append({param.name: param.type_annotation for param in node.parameters})
+            self._rewrite_fields(node)
+            self._scopes.pop()
+            return node
+        if isinstance(node, IrWhile):
+            self._scopes.append({})
+            if node.init is not None:
+                node.init = self.rewrite(node.init)
+            self._rewrite_fields(node, skip="init")
+            self._scopes.pop()
+            return node
+        if isinstance(node, IrForIn):
+            node.iterable = self.rewrite(node.iterable)
+            # Ranges only yield numerus.
+            element = "numerus" if isinstance(node.iterable, IrRange) else node.target.type_annotation
+            self._scopes.append({node.target.name: node.target.type_annotation or element})
+            node.body = self._rewrite_block(node.body)
+            self._scopes.pop()
+            return node
+        self._rewrite_fields(node)
+        if isinstance(node, IrVariableDeclaration):
+            self._scopes[-1][node.name] = node.type_annotation
+        elif isinstance(node, IrDestructuring):
+            self._scopes[-1].update((name, None) for name in node.names)
+        elif isinstance(node, IrBinary):
+            return self._simplify(node)
+        return node
+
+    def _rewrite_fields(self, node: IrNode, skip: str = "") -> None:
+        for item in fields(node):
+            value = getattr(node, item.name)
+            if item.name == skip:
+                continue
+            if isinstance(value, IrNode):
+                setattr(node, item.name, self.rewrite(value))
+            elif isinstance(value, list) and any(isinstance(entry, IrStatement) for entry in value):
+                setattr(node, item.name, self._rewrite_block(value))
+            elif isinstance(value, list):
+                rewritten = [self.rewrite(entry) if isinstance(entry, IrNode) else entry for entry in value]
+                setattr(node, item.name, rewritten)
+
+    def _rewrite_block(self, statements: List[IrStatement]) -> List[IrStatement]:
+        self._scopes.append({})
+        rewritten = [self.rewrite(stmt) for stmt in statements]
+        self._scopes.pop()
+        return rewritten
+
+    def _simplify(self, expr: IrBinary) -> IrExpr:
+        left, right, op = expr.left, expr.right, expr.operator
+        if op in {"ADD", "SUB", "MUL"}:
+            identity = 1 if op == "MUL" else 0
+            if _is_number(right, identity) and self._is_numeric(left):
+                return left
+            if op != "SUB" and _is_number(left, identity) and self._is_numeric(right):
+                return right
+        if op == "MUL":
+            for zero, other in ((right, left), (left, right)):
+                # An integer zero: `-4 * 0.0` would be -0.0, which the literal cannot stand for.
+                whole_zero = _is_number(zero, 0) and isinstance(zero.value, int)
+                if whole_zero and self._is_whole(other) and not has_side_effects(other):
+                    return zero
+        if op in {"AND", "OR"}:
+            identity = op == "AND"
+            if _is_boolean_literal(right, identity) and self._is_boolean(left):
+                return left
+            if _is_boolean_literal(left, identity) and self._is_boolean(right):
+                return right
+        return expr
+
+    def _annotation(self, name: str) -> Optional[str]:
+        for scope in reversed(self._scopes):
+            if name in scope:
+                return scope[name]
+        return None
+
+    def _is_numeric(self, expr: IrExpr) -> bool:
+        """Whether *expr* evaluates to a numerus or fails trying."""
+
+        if isinstance(expr, IrLiteral):
+            return isinstance(expr.value, (int, float)) and not isinstance(expr.value, bool)
+        if isinstance(expr, IrIdentifier):
+            return self._annotation(expr.name) == "numerus"
+        if isinstance(expr, IrUnary):
+            return expr.operator in {"NEGATE", "POSITIVE", "BIT_NOT"}
+        if isinstance(expr, IrBinary):
+            if expr.operator in {"ADD", "MUL"}:
+                return self._is_numeric(expr.left) and self._is_numeric(expr.right)
+            return expr.operator in _NUMERIC_OPERATORS
+        if isinstance(expr, IrCast):
+            return expr.type_name == "numerus"
+        if isinstance(expr, IrAssignment):
+            return self._is_numeric(expr.value)
+        if isinstance(expr, IrConditional):
+            return self._is_numeric(expr.consequent) and self._is_numeric(expr.alternate)
+        return isinstance(expr, IrUpdate)
+
+    def _is_whole(self, expr: IrExpr) -> bool:
+        """Whether *expr* is a whole number, never nan or infinitum, without failing."""
+
+        if isinstance(expr, IrLiteral):
+            return isinstance(expr.value, int) and not isinstance(expr.value, bool)
+        # Bitwise results are 32-bit integers, even for nan or infinitum operands.
+        if isinstance(expr, IrUnary) and expr.operator == "BIT_NOT":
+            return self._is_plain_numeric(expr.operand)
+        if isinstance(expr, IrBinary) and expr.operator in {"SHL", "SHR"}:
+            return self._is_plain_numeric(expr.left) and self._is_plain_numeric(expr.right)
+        return False
+
+    def _is_plain_numeric(self, expr: IrExpr) -> bool:
+        if isinstance(expr, IrIdentifier):
+            return self._annotation(expr.name) == "numerus"
+        return self._is_whole(expr)
+
+    def _is_boolean(self, expr: IrExpr) -> bool:
+        """Whether *expr* evaluates to a booleanum or fails trying."""
+
+        if isinstance(expr, IrLiteral):
+            return isinstance(expr.value, bool)
+        if isinstance(expr, IrIdentifier):
+            return self._annotation(expr.name) == "booleanum"
+        if isinstance(expr, IrUnary):
+            return expr.operator == "NOT"
+        if isinstance(expr, IrBinary):
+            return expr.operator in _BOOLEAN_OPERATORS
+        if isinstance(expr, IrCast):
+            return expr.type_name == "booleanum"
+        if isinstance(expr, IrAssignment):
+            return self._is_boolean(expr.value)
+        if isinstance(expr, IrConditional):
+            return self._is_boolean(expr.consequent) and self._is_boolean(expr.alternate)
+        return False
+
+
+def _is_number(expr: IrExpr, value: int) -> bool:
+    return isinstance(expr, IrLiteral) and not isinstance(expr.value, bool) and expr.value == value
+
+
+def _is_boolean_literal(expr: IrExpr, value: bool) -> bool:
+    return isinstance(expr, IrLiteral) and expr.value is value
+
+
+def has_side_effects(node: IrNode) -> bool:
+    """Whether evaluating *node* may call a function or assign a binding."""
+
+    if isinstance(node, (IrCall, IrAssignment, IrUpdate)):
+        return True
+    if isinstance(node, IrLambda):
+        return False
+    return any(has_side_effects(child) for child in _child_nodes(node) if not isinstance(child, IrStatement))
+
+
+def mentions_name(node: IrNode, name: str) -> bool:
+    """Whether an identifier spelled *name* appears anywhere inside *node*."""
+
+    if isinstance(node, IrIdentifier):
+        return node.name == name
+    return any(mentions_name(child, name) for child in _child_nodes(node))
+
+
+def _child_nodes(node: IrNode) -> Iterator[IrNode]:
+    for field in fields(node):
+        value = getattr(node, field.name)
+        if isinstance(value, IrNode):
+            yield value
+        elif isinstance(value, list):
+            yield from (item for item in value if isinstance(item, IrNode))
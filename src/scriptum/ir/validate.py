@@ -0,0 +1,243 @@
+"""Structural invariants checked on the IR before codegen or execution."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import List, Optional, Set
+
+from ..text import Span
+from .interpreter import NATIVES
+from .ir import (
+    IrArrayLiteral,
+    IrAssignment,
+    IrBinary,
+    IrBreak,
+    IrCall,
+    IrCast,
+    IrConditional,
+    IrContinue,
+    IrDestructuring,
+    IrExpr,
+    IrExpressionStatement,
+    IrForIn,
+    IrFunction,
+    IrIdentifier,
+    IrIf,
+    IrIndex,
+    IrLambda,
+    IrLiteral,
+    IrMemberAccess,
+    IrObjectLiteral,
+    IrParameter,
+    IrRange,
+    IrReturn,
+    IrStatement,
+    IrTupleLiteral,
+    IrUnary,
+    IrUpdate,
+    IrVariableDeclaration,
+    IrWhile,
+    ModuleIr,
+)
+
+
+@dataclass(slots=True)
+class IrError:
+    message: str
+    span: Optional[Span]
+
+
+def validate_module(module: ModuleIr) -> List[IrError]:
+    """
+    Check *module* for invariants that backends rely on.
+
+    Returns an empty list when the IR is well formed. Statements only live inside
+    function and lambda bodies, so `IrReturn` placement is guaranteed by the
+    structure itself; the remaining rules are verified here.
+    """
+
+    return _IrValidator().run(module)
+
+
+class _IrValidator:
+    def __init__(self) -> None:
+        self.errors: List[IrError] = []
+        self.scopes: List[Set[str]] = []
+        self.loop_depth = 0
+        self.loop_labels: List[str] = []
+
+    def run(self, module: ModuleIr) -> List[IrError]:
+        self.scopes = [set(NATIVES), {func.name for func in module.functions}]
+        for var in module.globals:
+            if var.initializer is not None:
+                self._check_expression(var.initializer)
+            self.scopes[1].add(var.name)
+        for func in module.functions:
+            self._check_function(func)
+        return self.errors
+
+    # Functions ---------------------------------------------------------------
+
+    def _check_function(self, func: IrFunction) -> None:
+        previous_loop_depth, previous_loop_labels = self.loop_depth, self.loop_labels
+        self.loop_depth, self.loop_labels = 0, []
+        self._push()
+        self._declare_parameters(func.parameters)
+        self._check_statements(func.body)
+        self._pop()
+        self.loop_depth, self.loop_labels = previous_loop_depth, previous_loop_labels
+
+    def _declare_parameters(self, parameters: List[IrParameter]) -> None:
+        for param in parameters:
+            if param.default_value is not None:
+                self._check_expression(param.default_value)
+            self.scopes[-1].add(param.name)
+
+    # Statements --------------------------------------------------------------
+
+    def _check_statements(self, statements: List[IrStatement]) -> None:
+        for stmt in statements:
+            self._check_statement(stmt)
+
+    def _check_block(self, statements: List[IrStatement]) -> None:
+        self._push()
+        self._check_statements(statements)
+        self._pop()
+
+    def _check_statement(self, stmt: IrStatement) -> None:
+        if isinstance(stmt, IrVariableDeclaration):
+            if stmt.initializer is not None:
+                self._check_expression(stmt.initializer)
+            self.scopes[-1].add(stmt.name)
+        elif isinstance(stmt, IrDestructuring):
+            self._check_expression(stmt.initializer)
+            self.scopes[-1].update(stmt.names)
+        elif isinstance(stmt, IrExpressionStatement):
+            self._check_expression(stmt.expression)
+        elif isinstance(stmt, IrReturn):
+            if stmt.value is not None:
+                self._check_expression(stmt.value)
+        elif isinstance(stmt, IrIf):
+            self._check_expression(stmt.condition)
+            self._check_block(stmt.then_branch)
+            self._check_block(stmt.else_branch)
+        elif isinstance(stmt, IrWhile):
+            self._push()
+            if stmt.init is not None:
+                self._check_statement(stmt.init)
+            if stmt.condition is not None:
+                self._check_expression(stmt.condition)
+            self._check_loop_body(stmt.body, stmt.label, stmt.span)
+            if stmt.update is not None:
+                self._check_expression(stmt.update)
+            self._pop()
+        elif isinstance(stmt, IrForIn):
+            self._check_expression(stmt.iterable)
+            self._push()
+            self.scopes[-1].add(stmt.target.name)
+            self._check_loop_body(stmt.body, stmt.label, stmt.span)
+            self._pop()
+        elif isinstance(stmt, IrBreak):
+            if self.loop_depth == 0:
+                self._error("'frange' outside of a loop", stmt.span)
+            self._check_jump_label(stmt.label, stmt.span)
+        elif isinstance(stmt, IrContinue):
+            if self.loop_depth == 0:
+                self._error("'perge' outside of a loop", stmt.span)
+            self._check_jump_label(stmt.label, stmt.span)
+        else:
+            self._error(f"Unknown statement node {type(stmt).__name__}", stmt.span)
+
+    def _check_loop_body(self, body: List[IrStatement], label: Optional[str], span: Optional[Span]) -> None:
+        if label is not None:
+            if label in self.loop_labels:
+                self._error(f"Label '{label}' shadows an enclosing loop label", span)
+            self.loop_labels.append(label)
+        self.loop_depth += 1
+        self._check_block(body)
+        self.loop_depth -= 1
+        if label is not None:
+            self.loop_labels.pop()
+
+    def _check_jump_label(self, label: Optional[str], span: Optional[Span]) -> None:
+        if label is not None and self.loop_depth > 0 and label not in self.loop_labels:
+            self._error(f"Unknown loop label '{label}'", span)
+
+    # Expressions -------------------------------------------------------------
+
+    def _check_expression(self, expr: Optional[IrExpr]) -> None:
+        if expr is None:
+            self._error("Missing expression", None)
+            return
+        if isinstance(expr, IrIdentifier):
+            if not self._is_resolvable(expr.name):
+                self._error(f"Unresolved name '{expr.name}'", expr.span)
+        elif isinstance(expr, IrLiteral):
+            return
+        elif isinstance(expr, IrUnary):
+            self._check_expression(expr.operand)
+        elif isinstance(expr, IrBinary):
+            self._check_expression(expr.left)
+            self._check_expression(expr.right)
+        elif isinstance(expr, IrUpdate):
+            self._check_expression(expr.target)
+        elif isinstance(expr, IrCast):
+            self._check_expression(expr.value)
+        elif isinstance(expr, IrRange):
+            self._check_expression(expr.start)
+            self._check_expression(expr.end)
+        elif isinstance(expr, IrAssignment):
+            self._check_expression(expr.target)
+            self._check_expression(expr.value)
+        elif isinstance(expr, IrConditional):
+            self._check_expression(expr.condition)
+            self._check_expression(expr.consequent)
+            self._check_expression(expr.alternate)
+        elif isinstance(expr, IrCall):
+            self._check_expression(expr.callee)
+            for argument in expr.arguments:
+                self._check_expression(argument)
+        elif isinstance(expr, IrMemberAccess):
+            self._check_expression(expr.object)
+        elif isinstance(expr, IrIndex):
+            self._check_expression(expr.collection)
+            self._check_expression(expr.index)
+        elif isinstance(expr, (IrArrayLiteral, IrTupleLiteral)):
+            for element in expr.elements:
+                self._check_expression(element)
+        elif isinstance(expr, IrObjectLiteral):
+            for prop in expr.properties:
+                self._check_expression(prop.value)
+        elif isinstance(expr, IrLambda):
+            self._check_lambda(expr)
+        else:
+            self._error(f"Unknown expression node {type(expr).__name__}", expr.span)
+
+    def _check_lambda(self, expr: IrLambda) -> None:
+        if expr.body_expression is not None and expr.body_statements:
+            self._error("Lambda has both an expression body and a statement body", expr.span)
+        previous_loop_depth, previous_loop_labels = self.loop_depth, self.loop_labels
+        self.loop_depth, self.loop_labels = 0, []
+        self._push()
+        if expr.method:
+            self.scopes[-1].add("ipse")
+        self._declare_parameters(expr.parameters)
+        if expr.body_expression is not None:
+            self._check_expression(expr.body_expression)
+        self._check_statements(expr.body_statements)
+        self._pop()
+        self.loop_depth, self.loop_labels = previous_loop_depth, previous_loop_labels
+
+    # Helpers -----------------------------------------------------------------
+
+    def _is_resolvable(self, name: str) -> bool:
+        return any(name in scope for scope in self.scopes)
+
+    def _push(self) -> None:
+        self.scopes.append(set())
+
+    def _pop(self) -> None:
+        self.scopes.pop()
+
+    def _error(self, message: str, span: Optional[Span]) -> None:
+        self.errors.append(IrError(message=message, span=span))
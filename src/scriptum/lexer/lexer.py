@@ -5,6 +5,7 @@ Deterministic lexer implementation backed by pre-generated DFA tables.
 from __future__ import annotations
 
 import json
+import re
 import unicodedata
 from dataclasses import dataclass
 from pathlib import Path
@@ -60,6 +61,8 @@ class ScriptumLexer:
         text_data = "".join(ch if ord(ch) < 128 else " " for ch in normalized_text)
         position = 0
         length = len(text_data)
+        # `///` lines waiting to be attached to the next significant token.
+        doc_lines: List[str] = []
 
         while position < length:
             if text_data.startswith("/*", position):
@@ -69,6 +72,8 @@ class ScriptumLexer:
 
             match = self._match_token(text_data, position)
             if match is None:
+                if text_data[position] in "\"'":
+                    raise self._unterminated_string(source, position)
                 raise self._lex_error(source, position)
 
             accept, end_pos = match
@@ -77,13 +82,22 @@ class ScriptumLexer:
 
             position = end_pos
 
+            if accept.name == "COMMENT_LINE":
+                _attach_trailing_comment(result, text_data, span, normalized_text[span.start : span.end])
             if accept.ignore and self.config.skip_whitespace:
+                if accept.name == "DOC_COMMENT":
+                    doc_lines.append(_comment_text(normalized_text[span.start : span.end], "///"))
+                elif accept.kind is tokens.TokenKind.COMMENT:
+                    doc_lines = []
                 continue
 
             kind = accept.kind
 
             if kind is tokens.TokenKind.IDENTIFIER and tokens.is_keyword(lexeme):
                 kind = tokens.TokenKind.KEYWORD
+            if kind is tokens.TokenKind.STRING_LITERAL:
+                # The DFA sees characters outside ASCII as blanks; the literal itself keeps them.
+                lexeme = normalized_text[span.start : span.end]
 
             value = self._compute_value(kind, lexeme)
             token = tokens.Token(
@@ -93,6 +107,13 @@ class ScriptumLexer:
                 value=value,
                 metadata={"pattern": accept.name, "index": accept.index},
             )
+            if kind is tokens.TokenKind.NUMBER_LITERAL and lexeme.endswith(tokens.NUMBER_SUFFIXES):
+                if lexeme.endswith("i") and not isinstance(value, int):
+                    raise self._fractional_integer_literal(source, span, lexeme)
+                token.metadata["suffix"] = lexeme[-1]
+            if doc_lines:
+                token.metadata["doc"] = doc_lines
+                doc_lines = []
             result.append(token)
 
         eof_span = text.Span(length, length)
@@ -149,18 +170,22 @@ class ScriptumLexer:
     def _compute_value(self, kind: tokens.TokenKind, lexeme: str):
         if kind is tokens.TokenKind.NUMBER_LITERAL:
             sanitized = lexeme.replace("_", "")
+            suffix = sanitized[-1] if sanitized.endswith(tokens.NUMBER_SUFFIXES) else ""
+            sanitized = sanitized.removesuffix(suffix) if suffix else sanitized
             try:
-                if "." in sanitized or "e" in sanitized.lower():
+                if suffix == "f" or "." in sanitized or "e" in sanitized.lower():
                     return float(sanitized)
                 return int(sanitized)
             except ValueError:
                 return sanitized
         if kind is tokens.TokenKind.STRING_LITERAL:
-            inner = lexeme[1:-1]
+            # The escapes are JSON's plus `\'`; `strict=False` lets a literal span lines. Unlike
+            # "unicode_escape", this keeps characters outside ASCII intact.
+            body = _STRING_ESCAPE.sub(_json_escape, lexeme[1:-1])
             try:
-                return bytes(inner, "utf8").decode("unicode_escape")
-            except UnicodeDecodeError:
-                return inner
+                return json.loads(f'"{body}"', strict=False)
+            except ValueError:
+                return lexeme[1:-1]
         return lexeme
 
     def _lex_error(self, source: text.SourceFile, position: int) -> errors.LexerError:
@@ -170,12 +195,27 @@ class ScriptumLexer:
         span = text.Span(position, min(position + 1, len(source.text)))
         return errors.LexerError(message, span)
 
+    def _fractional_integer_literal(self, source: text.SourceFile, span: text.Span, lexeme: str) -> errors.LexerError:
+        line, column = self._line_col(source.text, span.start)
+        message = (
+            f"Integer suffix 'i' needs a number without fraction or exponent, found {lexeme!r} "
+            f"at line {line}, column {column}"
+        )
+        return errors.LexerError(message, span)
+
     def _unterminated_block_comment(self, source: text.SourceFile, position: int) -> errors.LexerError:
         line, column = self._line_col(source.text, position)
         span = text.Span(position, len(source.text))
         message = f"Unterminated block comment starting at line {line}, column {column}"
         return errors.LexerError(message, span)
 
+    def _unterminated_string(self, source: text.SourceFile, position: int) -> errors.LexerError:
+        line, column = self._line_col(source.text, position)
+        quote = source.text[position]
+        message = f"Unterminated string literal starting at line {line}, column {column}; add a closing {quote!r}"
+        span = text.Span(position, len(source.text))
+        return errors.LexerError(message, span, anchor=text.Span(position, position + 1))
+
     @staticmethod
     def _line_col(content: str, pos: int) -> tuple[int, int]:
         line = content.count("\n", 0, pos) + 1
@@ -243,3 +283,41 @@ def _symbol_to_code(symbol: str) -> int:
     if symbol:
         return ord(symbol[0])
     raise ValueError("Invalid symbol representation in DFA table.")
+
+
+_TRIVIA_KINDS = {tokens.TokenKind.WHITESPACE, tokens.TokenKind.COMMENT}
+
+
+def _comment_text(lexeme: str, marker: str) -> str:
+    """Strip the comment *marker* and the single space that conventionally follows it."""
+
+    content = lexeme[len(marker) :].rstrip()
+    return content[1:] if content.startswith(" ") else content
+
+
+def _attach_trailing_comment(result: List[tokens.Token], text_data: str, span: text.Span, lexeme: str) -> None:
+    """
+    Record a `//` comment that ends the line of the last significant token in that token's metadata.
+
+    The parser keeps these for the constructs whose comments the formatter
+    re-emits, such as object literal properties.
+    """
+
+    previous = next((token for token in reversed(result) if token.kind not in _TRIVIA_KINDS), None)
+    if previous is None or "\n" in text_data[previous.span.end : span.start]:
+        return
+    previous.metadata["comment"] = _comment_text(lexeme, "//")
+
+
+_STRING_ESCAPE = re.compile(r'\\u[0-9a-fA-F]{4}|\\.|"', re.S)
+
+
+def _json_escape(match: re.Match[str]) -> str:
+    """Rewrite one piece of a string body so that it reads the same inside a JSON `"..."`."""
+
+    piece = match.group()
+    if piece == "\\'":
+        return "'"
+    if piece == '"':
+        return '\\"'
+    return piece
@@ -66,6 +66,7 @@ _LITERAL_NAMES = {
     "{": "LBRACE",
     "|": "BAR",
     "}": "RBRACE",
+    "~": "TILDE",
 }
 
 
@@ -84,6 +85,13 @@ TOKEN_PATTERNS: List[TokenPattern] = [
         priority=100,
         ignore=True,
     ),
+    TokenPattern(
+        name="DOC_COMMENT",
+        kind=tokens.TokenKind.COMMENT,
+        pattern=r"///[^\r\n]*",
+        priority=95,
+        ignore=True,
+    ),
     TokenPattern(
         name="COMMENT_LINE",
         kind=tokens.TokenKind.COMMENT,
@@ -101,13 +109,17 @@ TOKEN_PATTERNS: List[TokenPattern] = [
     TokenPattern(
         name="NUMBER_LITERAL",
         kind=tokens.TokenKind.NUMBER_LITERAL,
-        pattern=r"-?(?:0|[1-9][0-9_]*)(?:\.[0-9_]+)?(?:[eE][+-]?[0-9_]+)?",
+        pattern=r"-?(?:0|[1-9][0-9_]*)(?:\.[0-9_]+)?(?:[eE][+-]?[0-9_]+)?[if]?",
         priority=70,
     ),
     TokenPattern(
         name="STRING_LITERAL",
         kind=tokens.TokenKind.STRING_LITERAL,
-        pattern=r'"(?:[^"\\]|\\["\\\/bfnrt]|\\u[0-9a-fA-F]{4})*"',
+        # `"..."` or `'...'`, the same string either way; `\'` is accepted inside both.
+        pattern=(
+            r'''"(?:[^"\\]|\\["'\\\/bfnrt]|\\u[0-9a-fA-F]{4})*"'''
+            r"""|'(?:[^'\\]|\\["'\\\/bfnrt]|\\u[0-9a-fA-F]{4})*'"""
+        ),
         priority=70,
     ),
     TokenPattern(
@@ -0,0 +1,319 @@
+"""
+Minimal Language Server Protocol server for Scriptum (`scriptum lsp`).
+
+JSON-RPC 2.0 messages travel over stdio with `Content-Length` framing. Open
+documents are re-analyzed on every `didOpen`/`didChange` (full text sync) and
+their lexer, parser, import and semantic diagnostics are published; `hover`
+shows the type the analyzer inferred for the expression under the cursor, and
+semantic tokens come from `highlight.classify`.
+"""
+
+from __future__ import annotations
+
+import json
+import pathlib
+from dataclasses import dataclass, field
+from typing import Any, BinaryIO, Callable, Dict, List, Optional
+from urllib.parse import unquote, urlparse
+
+from . import __version__, errors
+from .ast import nodes
+from .ast.visitors import nodes_at
+from .diagnostics import Diagnostic, collect_diagnostics
+from .highlight import TokenClass, classify
+from .modules import ModuleGraph
+from .parser.parser import ParseError, ScriptumParser
+from .sema.analyzer import AnalyzerOptions, SemanticAnalyzer, Severity
+from .sema.types import Type
+from .text import SourceFile, Span
+
+# LSP constants used below.
+_SYNC_FULL = 1
+_SEVERITY = {Severity.ERROR: 1, Severity.WARNING: 2}
+# Semantic token types, indexed by position in the legend sent on `initialize`.
+_TOKEN_TYPES = [token_class.value for token_class in TokenClass]
+_METHOD_NOT_FOUND = -32601
+_INTERNAL_ERROR = -32603
+_MESSAGE_ERROR = 1
+
+
+@dataclass(slots=True)
+class Document:
+    """The latest analysis of one open document."""
+
+    uri: str
+    source: SourceFile
+    # None when the text does not lex or parse.
+    module: Optional[nodes.Module] = None
+    expression_types: Dict[int, Type] = field(default_factory=dict)
+    # LSP `Diagnostic` objects, ready to publish.
+    diagnostics: List[Dict[str, Any]] = field(default_factory=list)
+
+    def hover(self, line: int, character: int) -> Optional[Dict[str, Any]]:
+        """The LSP `Hover` for the innermost typed expression at the position, if any."""
+
+        if self.module is None:
+            return None
+        offset = offset_at(self.source.text, line, character)
+        for node in reversed(nodes_at(self.module, offset)):
+            if not isinstance(node, nodes.Expression):
+                # Past the enclosing statement nothing under the cursor has a type of its own.
+                return None
+            node_type = self.expression_types.get(node.node_id)
+            if node_type is None:
+                continue
+            label = node_type.pretty()
+            if isinstance(node, nodes.Identifier):
+                label = f"{node.name}: {label}"
+            return {
+                "contents": {"kind": "markdown", "value": f"```scriptum\n{label}\n```"},
+                "range": _range(self.source.text, node.span),
+            }
+        return None
+
+    def semantic_tokens(self) -> List[int]:
+        """`classify` in the LSP relative encoding: five integers per token, split at line breaks."""
+
+        text = self.source.text
+        try:
+            classified = classify(self.source)
+        except errors.LexerError:
+            return []
+        data: List[int] = []
+        previous_line = previous_character = 0
+        for token in classified:
+            offset = token.span.start
+            for piece in token.lexeme.split("\n"):
+                if piece:
+                    position = position_of(text, offset)
+                    line, character = position["line"], position["character"]
+                    delta = character - previous_character if line == previous_line else character
+                    token_type = _TOKEN_TYPES.index(token.kind.value)
+                    data.extend([line - previous_line, delta, _utf16_length(piece), token_type, 0])
+                    previous_line, previous_character = line, character
+                offset += len(piece) + 1
+        return data
+
+
+def analyze_document(uri: str, text: str, options: Optional[AnalyzerOptions] = None) -> Document:
+    """Parse and check *text*; imports resolve relative to *uri* when it is a `file:` URI."""
+
+    source = SourceFile(path=uri, text=text)
+    document = Document(uri=uri, source=source)
+    document.diagnostics = [_lsp_diagnostic(uri, text, diag) for diag in _check(document, options)]
+    return document
+
+
+def _check(document: Document, options: Optional[AnalyzerOptions]) -> List[Diagnostic]:
+    parser = ScriptumParser()
+    try:
+        document.module = parser.parse(document.source)
+    except (errors.LexerError, ParseError) as exc:
+        return collect_diagnostics(exc)
+
+    origin = _uri_to_path(document.uri)
+    imports = {}
+    if origin is not None:
+        try:
+            imports = ModuleGraph(parser, options).resolve_imports(document.module, origin)
+        except errors.ModuleImportError as exc:
+            first_import = next(
+                (decl for decl in document.module.declarations if isinstance(decl, nodes.ImportDeclaration)), None
+            )
+            return collect_diagnostics(exc, error_span=first_import and first_import.span)
+
+    analyzer = SemanticAnalyzer(options)
+    semantic = analyzer.analyze(document.module, {path: unit.exports for path, unit in imports.items()})
+    document.expression_types = analyzer.expression_types
+    return collect_diagnostics(semantic=semantic)
+
+
+class LanguageServer:
+    """Serves one client over a pair of binary streams until it sends `exit`."""
+
+    def __init__(self, reader: BinaryIO, writer: BinaryIO, options: Optional[AnalyzerOptions] = None) -> None:
+        self.reader = reader
+        self.writer = writer
+        self.options = options or AnalyzerOptions()
+        self.documents: Dict[str, Document] = {}
+        self._shutdown = False
+        self._handlers: Dict[str, Callable[[Dict[str, Any]], Any]] = {
+            "initialize": self._initialize,
+            "initialized": lambda params: None,
+            "shutdown": self._shutdown_request,
+            "textDocument/didOpen": self._did_open,
+            "textDocument/didChange": self._did_change,
+            "textDocument/didClose": self._did_close,
+            "textDocument/hover": self._hover,
+            "textDocument/semanticTokens/full": self._semantic_tokens,
+        }
+
+    def serve(self) -> int:
+        """Handle messages until `exit`; returns the process exit code the protocol asks for."""
+
+        while True:
+            message = read_message(self.reader)
+            if message is None or message.get("method") == "exit":
+                return 0 if self._shutdown else 1
+            self._dispatch(message)
+
+    def _dispatch(self, message: Dict[str, Any]) -> None:
+        method = message.get("method")
+        handler = self._handlers.get(method) if method else None
+        params = message.get("params") or {}
+        if "id" not in message:
+            # Notifications get no reply, and unknown ones (such as `$/cancelRequest`) are ignored.
+            if handler is not None:
+                try:
+                    handler(params)
+                except Exception as exc:  # keep serving; the client shows the failure in its server log
+                    message = f"{method} failed: {type(exc).__name__}: {exc}"
+                    self._send({"method": "window/logMessage", "params": {"type": _MESSAGE_ERROR, "message": message}})
+            return
+        if handler is None:
+            error = {"code": _METHOD_NOT_FOUND, "message": f"Unknown method {method!r}"}
+            self._send({"id": message["id"], "error": error})
+            return
+        try:
+            result = handler(params)
+        except Exception as exc:  # pragma: no cover - keep serving after an internal error
+            self._send({"id": message["id"], "error": {"code": _INTERNAL_ERROR, "message": str(exc)}})
+            return
+        self._send({"id": message["id"], "result": result})
+
+    def _initialize(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        return {
+            "capabilities": {
+                "textDocumentSync": _SYNC_FULL,
+                "hoverProvider": True,
+                "semanticTokensProvider": {"legend": {"tokenTypes": _TOKEN_TYPES, "tokenModifiers": []}, "full": True},
+            },
+            "serverInfo": {"name": "scriptum", "version": __version__},
+        }
+
+    def _shutdown_request(self, params: Dict[str, Any]) -> None:
+        self._shutdown = True
+
+    def _did_open(self, params: Dict[str, Any]) -> None:
+        item = params["textDocument"]
+        self._update(item["uri"], item["text"])
+
+    def _did_change(self, params: Dict[str, Any]) -> None:
+        # With full sync the last change holds the whole new text.
+        changes = params.get("contentChanges") or []
+        if changes:
+            self._update(params["textDocument"]["uri"], changes[-1]["text"])
+
+    def _did_close(self, params: Dict[str, Any]) -> None:
+        uri = params["textDocument"]["uri"]
+        self.documents.pop(uri, None)
+        self._publish(uri, [])
+
+    def _hover(self, params: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        document = self.documents.get(params["textDocument"]["uri"])
+        if document is None:
+            return None
+        position = params["position"]
+        return document.hover(position["line"], position["character"])
+
+    def _semantic_tokens(self, params: Dict[str, Any]) -> Dict[str, List[int]]:
+        document = self.documents.get(params["textDocument"]["uri"])
+        return {"data": document.semantic_tokens() if document is not None else []}
+
+    def _update(self, uri: str, text: str) -> None:
+        document = analyze_document(uri, text, self.options)
+        self.documents[uri] = document
+        self._publish(uri, document.diagnostics)
+
+    def _publish(self, uri: str, diagnostics: List[Dict[str, Any]]) -> None:
+        self._send({"method": "textDocument/publishDiagnostics", "params": {"uri": uri, "diagnostics": diagnostics}})
+
+    def _send(self, payload: Dict[str, Any]) -> None:
+        write_message(self.writer, {"jsonrpc": "2.0", **payload})
+
+
+def read_message(stream: BinaryIO) -> Optional[Dict[str, Any]]:
+    """Read one framed JSON-RPC message; None at end of input."""
+
+    headers: Dict[str, str] = {}
+    while True:
+        line = stream.readline()
+        if not line:
+            return None
+        line = line.strip()
+        if not line:
+            break
+        name, _, value = line.decode("ascii").partition(":")
+        headers[name.strip().lower()] = value.strip()
+    body = stream.read(int(headers["content-length"]))
+    return json.loads(body.decode("utf8"))
+
+
+def write_message(stream: BinaryIO, payload: Dict[str, Any]) -> None:
+    body = json.dumps(payload, ensure_ascii=False).encode("utf8")
+    stream.write(f"Content-Length: {len(body)}\r\n\r\n".encode("ascii") + body)
+    stream.flush()
+
+
+def offset_at(text: str, line: int, character: int) -> int:
+    """Source offset of an LSP position; *character* counts UTF-16 code units, as the protocol requires."""
+
+    start = 0
+    for _ in range(line):
+        newline = text.find("\n", start)
+        if newline == -1:
+            return len(text)
+        start = newline + 1
+    end = text.find("\n", start)
+    end = len(text) if end == -1 else end
+    offset, units = start, 0
+    while offset < end and units < character:
+        units += _utf16_units(text[offset])
+        offset += 1
+    return offset
+
+
+def position_of(text: str, offset: int) -> Dict[str, int]:
+    """LSP position (0-based line, UTF-16 character) of a source offset."""
+
+    line_start = text.rfind("\n", 0, offset) + 1
+    return {"line": text.count("\n", 0, offset), "character": _utf16_length(text[line_start:offset])}
+
+
+def _utf16_units(char: str) -> int:
+    return 2 if ord(char) > 0xFFFF else 1
+
+
+def _utf16_length(text: str) -> int:
+    return sum(_utf16_units(char) for char in text)
+
+
+def _range(text: str, span: Optional[Span]) -> Dict[str, Dict[str, int]]:
+    if span is None:
+        # Diagnostics without a location are shown at the start of the document.
+        span = Span(0, 0)
+    return {"start": position_of(text, span.start), "end": position_of(text, span.end)}
+
+
+def _lsp_diagnostic(uri: str, text: str, diagnostic: Diagnostic) -> Dict[str, Any]:
+    payload: Dict[str, Any] = {
+        "range": _range(text, diagnostic.span),
+        "severity": _SEVERITY[diagnostic.severity],
+        "source": "scriptum",
+        "message": diagnostic.message,
+    }
+    if diagnostic.code is not None:
+        payload["code"] = diagnostic.code
+    if diagnostic.notes:
+        payload["relatedInformation"] = [
+            {"location": {"uri": uri, "range": _range(text, span)}, "message": label}
+            for label, span in diagnostic.notes
+        ]
+    return payload
+
+
+def _uri_to_path(uri: str) -> Optional[pathlib.Path]:
+    parsed = urlparse(uri)
+    if parsed.scheme != "file":
+        return None
+    return pathlib.Path(unquote(parsed.path))
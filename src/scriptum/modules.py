@@ -0,0 +1,107 @@
+"""Loading and checking of Scriptum modules referenced through `importa`."""
+
+from __future__ import annotations
+
+import pathlib
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional
+
+from . import errors, text
+from .ast import nodes
+from .parser.parser import ScriptumParser
+from .sema.analyzer import AnalyzerOptions, SemanticAnalyzer, SemanticDiagnostic
+from .sema.symbols import Symbol
+
+
+@dataclass(slots=True)
+class ModuleUnit:
+    """A dependency that was parsed and checked successfully."""
+
+    path: pathlib.Path
+    source: text.SourceFile
+    ast: nodes.Module
+    diagnostics: List[SemanticDiagnostic] = field(default_factory=list)
+    exports: List[Symbol] = field(default_factory=list)
+
+
+class ModuleGraph:
+    """
+    Resolves `importa` declarations into checked modules.
+
+    Each file is loaded once; later imports of the same path reuse the cached
+    unit. Only paths relative to the importing file's directory are supported
+    for now (no sub-directories).
+    """
+
+    def __init__(self, parser: Optional[ScriptumParser] = None, options: Optional[AnalyzerOptions] = None) -> None:
+        self._parser = parser or ScriptumParser()
+        self._options = options
+        self._units: Dict[pathlib.Path, ModuleUnit] = {}
+        self._loading: List[pathlib.Path] = []
+
+    @property
+    def units(self) -> List[ModuleUnit]:
+        """Loaded dependencies, each one listed after the modules it imports."""
+
+        return list(self._units.values())
+
+    def resolve_imports(self, module: nodes.Module, origin: pathlib.Path) -> Dict[str, ModuleUnit]:
+        """Load and check every module imported by *module*, which was read from *origin*."""
+
+        origin = origin.resolve()
+        self._loading.append(origin)
+        try:
+            return {
+                declaration.path: self._load(declaration, origin)
+                for declaration in module.declarations
+                if isinstance(declaration, nodes.ImportDeclaration)
+            }
+        finally:
+            self._loading.pop()
+
+    def _load(self, declaration: nodes.ImportDeclaration, origin: pathlib.Path) -> ModuleUnit:
+        path = self._resolve_path(declaration, origin)
+        cached = self._units.get(path)
+        if cached is not None:
+            return cached
+        if path in self._loading:
+            chain = self._loading[self._loading.index(path):] + [path]
+            raise errors.ModuleImportError("Import cycle detected: " + " -> ".join(item.name for item in chain))
+
+        try:
+            source_text = path.read_text(encoding="utf8")
+        except FileNotFoundError as exc:
+            raise errors.ModuleImportError(
+                f"Imported module '{declaration.path}' not found (imported from {origin.name})"
+            ) from exc
+        except OSError as exc:
+            raise errors.ModuleImportError(f"Unable to read imported module '{declaration.path}'") from exc
+
+        source = text.SourceFile(path=str(path), text=source_text)
+        ast = self._parser.parse(source)
+        dependencies = self.resolve_imports(ast, path)
+
+        analyzer = SemanticAnalyzer(self._options)
+        diagnostics = analyzer.analyze(ast, {key: unit.exports for key, unit in dependencies.items()})
+        failures = [diagnostic for diagnostic in diagnostics if diagnostic.is_error]
+        if failures:
+            summary = "; ".join(f"{diagnostic.code} {diagnostic.message}" for diagnostic in failures[:3])
+            raise errors.ModuleImportError(f"Imported module '{declaration.path}' has semantic errors: {summary}")
+
+        unit = ModuleUnit(path=path, source=source, ast=ast, diagnostics=diagnostics, exports=analyzer.exports)
+        self._units[path] = unit
+        return unit
+
+    @staticmethod
+    def _resolve_path(declaration: nodes.ImportDeclaration, origin: pathlib.Path) -> pathlib.Path:
+        raw = declaration.path
+        if "/" in raw or "\\" in raw or raw in {"", ".", ".."}:
+            raise errors.ModuleImportError(
+                f"Only modules in the same directory can be imported; got '{raw}'"
+            )
+        if not raw.lower().endswith(".stm"):
+            raise errors.ModuleImportError(f"Imported module '{raw}' must use the .stm extension")
+        return (origin.parent / raw).resolve()
+
+
+__all__ = ["ModuleGraph", "ModuleUnit"]
@@ -2,9 +2,10 @@
 
 from __future__ import annotations
 
+import math
 import re
-from dataclasses import dataclass, field
-from typing import Dict, List, Optional, Tuple
+from dataclasses import dataclass, field, replace
+from typing import Dict, List, Optional, Sequence, Tuple
 
 from ll1calc.first_follow import EPSILON as LL1_EPSILON
 from ll1calc.lexer import LexerError as LL1LexerError
@@ -12,6 +13,7 @@ from ll1calc.parser import LL1Parser, ParseError as LL1ParseError, ParseTreeNode
 
 from .. import errors, text, tokens
 from ..ast import nodes
+from ..ast.visitors import count_nodes, is_optional_chain
 from ..lexer.lexer import ScriptumLexer
 from ..text import Span
 from .precedence import binding_powers
@@ -33,11 +35,25 @@ TYPE_KEYWORDS = {
 class ParseError(errors.CompilerError):
     """Raised when a syntactic error is encountered."""
 
+    def __init__(
+        self,
+        message: str,
+        span: Optional[Span] = None,
+        notes: Optional[List[Tuple[str, Span]]] = None,
+    ) -> None:
+        super().__init__(message)
+        # Where the error was detected, when known; editors place the diagnostic there.
+        self.span = span
+        # Secondary locations, such as where a delimiter that is still open was opened.
+        self.notes = notes or []
+
 
 @dataclass(slots=True)
 class ParserConfig:
     allow_lambda_shortcut: bool = True
     max_depth: int = 2048
+    # Largest module accepted, in AST nodes (see `count_nodes`); None means no limit.
+    max_nodes: Optional[int] = None
 
 
 @dataclass(slots=True)
@@ -79,6 +95,23 @@ class _LL1Trace:
     derivations: List[str]
 
 
+# Keywords that only make sense after another construct; seen on their own they get a targeted message.
+_ORPHAN_KEYWORDS = {
+    "aliter": "'aliter' without a matching 'si'",
+    "in": "'in' outside of a 'pro' loop header",
+}
+
+# Tokens that can follow a type annotation; before one of them, `??` is two optional markers, not '??'.
+_ANNOTATION_FOLLOW = {"=", ",", ")", "]", "}", ";", "{", "=>", "in"}
+
+_TRIVIA_KINDS = {tokens.TokenKind.WHITESPACE, tokens.TokenKind.COMMENT}
+
+# Numeric values that have no digit spelling; `-infinitum` is the negation of the literal.
+_FLOAT_KEYWORDS = {"infinitum": math.inf, "nan": math.nan}
+
+# Opening delimiters and the symbol that closes each.
+_DELIMITER_PAIRS = {"(": ")", "[": "]", "{": "}"}
+
 _LL1_ALLOWED_RE = re.compile(r"^[0-9+\-*/()\s]+$")
 
 
@@ -101,37 +134,145 @@ class ScriptumParser:
     # Public API -----------------------------------------------------------------
 
     def parse(self, source: text.SourceFile, trace: ParserTrace | None = None) -> nodes.Module:
+        return self.parse_tokens(source, self._lexer.tokenize(source), trace)
+
+    def parse_tokens(
+        self,
+        source: text.SourceFile,
+        token_list: Sequence[tokens.Token],
+        trace: ParserTrace | None = None,
+    ) -> nodes.Module:
+        """
+        Parse tokens already produced by `ScriptumLexer.tokenize(source)`.
+
+        Whitespace and comment tokens (kept when `LexerConfig.skip_whitespace` is
+        off) are dropped, and a missing EOF token is supplied. *token_list* itself
+        is not modified.
+        """
+
         self._source = source
-        self._tokens = self._lexer.tokenize(source)
+        self._tokens = [token for token in token_list if token.kind not in _TRIVIA_KINDS]
+        if not self._tokens or self._tokens[-1].kind is not tokens.TokenKind.EOF:
+            end = len(source.text)
+            self._tokens.append(tokens.Token(kind=tokens.TokenKind.EOF, lexeme="", span=Span(end, end)))
         self._index = 0
         self._node_counter = 0
         self._expr_call_depth = 0
+        # Opening '(', '[' and '{' tokens that have not been closed yet, innermost last.
+        self._delimiters: List[tokens.Token] = []
         self._trace = trace
         self._ll1_traces = {}
         declarations: List[nodes.Declaration] = []
         try:
             while not self._is_at_end():
-                declarations.append(self._parse_declaration(global_scope=True))
+                doc = (self._peek().metadata or {}).get("doc", [])
+                parsed = self._parse_declarations(global_scope=True)
+                if isinstance(parsed[0], (nodes.FunctionDeclaration, nodes.VariableDeclaration)):
+                    parsed[0].doc = list(doc)
+                declarations.extend(parsed)
             module_span = Span(0, len(source.text))
-            return nodes.Module(node_id=self._next_id(), span=module_span, declarations=declarations)
+            module = nodes.Module(node_id=self._next_id(), span=module_span, declarations=declarations)
+            self._check_node_limit(module)
+            return module
         finally:
             self._trace = None
 
+    def _check_node_limit(self, module: nodes.Module) -> None:
+        limit = self.config.max_nodes
+        if limit is None:
+            return
+        total = 1
+        for declaration in module.declarations:
+            total += count_nodes(declaration)
+            if total > limit:
+                # The diagnostic points at the declaration that crossed the limit.
+                raise ParseError(
+                    f"Module exceeds the limit of {limit} AST nodes at {declaration.span}.",
+                    span=declaration.span,
+                )
+
     # Declaration parsing --------------------------------------------------------
 
-    def _parse_declaration(self, global_scope: bool) -> nodes.Declaration:
+    def _parse_declarations(self, global_scope: bool) -> List[nodes.Declaration]:
+        if global_scope and (self._check_keyword("publicus") or self._check_keyword("privatus")):
+            return self._parse_visible_declarations()
+        if global_scope and self._check_keyword("importa"):
+            return [self._parse_import_declaration()]
         if self._check_keyword("functio"):
-            return self._parse_function_declaration()
+            return [self._parse_function_declaration()]
         if self._check_keyword("mutabilis") or self._check_keyword("constans"):
-            return self._parse_variable_declaration(global_scope=global_scope)
+            return list(self._parse_variable_declarations(global_scope=global_scope))
+        start = self._peek()
+        if global_scope and self._check_keyword("redde"):
+            raise ParseError(f"'redde' outside of a function at {start.span}.", span=start.span)
         stmt = self._parse_statement()
         if isinstance(stmt, nodes.Declaration):
-            return stmt
-        raise ParseError("Unexpected top-level statement.")
+            return [stmt]
+        raise ParseError(f"Unexpected top-level statement at {start.span}.", span=start.span)
+
+    def _parse_visible_declarations(self) -> List[nodes.Declaration]:
+        modifier = self._advance()
+        visibility = nodes.Visibility(modifier.lexeme)
+        declarations: List[nodes.FunctionDeclaration | nodes.VariableDeclaration]
+        if self._check_keyword("functio"):
+            declarations = [self._parse_function_declaration()]
+        elif self._check_keyword("mutabilis") or self._check_keyword("constans"):
+            declarations = list(self._parse_variable_declarations(global_scope=True))
+        else:
+            token = self._peek()
+            raise ParseError(
+                f"Expected 'functio', 'constans' or 'mutabilis' after '{modifier.lexeme}'. "
+                f"Found {token.lexeme!r} at {token.span}.",
+                span=token.span,
+            )
+        for declaration in declarations:
+            declaration.visibility = visibility
+        declarations[0].span = self._combine_spans(modifier.span, declarations[0].span)
+        return list(declarations)
+
+    def _parse_import_declaration(self) -> nodes.ImportDeclaration:
+        keyword = self._consume_keyword("importa")
+        names: List[nodes.ImportSpecifier] = []
+        if self._match_symbol("{"):
+            names = self._parse_import_specifiers()
+            self._consume_keyword("de")
+        path_token = self._consume(tokens.TokenKind.STRING_LITERAL, "Expected module path string after 'importa'.")
+        alias = None
+        if not names and self._match_keyword("ut"):
+            alias = self._consume(tokens.TokenKind.IDENTIFIER, "Expected module alias after 'ut'.").lexeme
+        semicolon = self._consume_symbol(";", "Expected ';' after import declaration.")
+        return nodes.ImportDeclaration(
+            node_id=self._next_id(),
+            span=self._combine_spans(keyword.span, semicolon.span),
+            path=path_token.value,
+            alias=alias,
+            names=names,
+        )
+
+    def _parse_import_specifiers(self) -> List[nodes.ImportSpecifier]:
+        specifiers: List[nodes.ImportSpecifier] = []
+        while True:
+            name_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected imported name.")
+            alias_token = None
+            if self._match_keyword("ut"):
+                alias_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected alias after 'ut'.")
+            specifiers.append(
+                nodes.ImportSpecifier(
+                    node_id=self._next_id(),
+                    span=self._combine_spans(name_token.span, (alias_token or name_token).span),
+                    name=name_token.lexeme,
+                    alias=alias_token.lexeme if alias_token else None,
+                )
+            )
+            if not self._match_symbol(","):
+                break
+        self._consume_symbol("}", "Expected '}' after imported names.")
+        return specifiers
 
     def _parse_function_declaration(self) -> nodes.FunctionDeclaration:
         start = self._consume_keyword("functio")
         name_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected function name.")
+        type_parameters = self._parse_type_parameters()
 
         self._consume_symbol("(", "Expected '(' after function name.")
         parameters = self._parse_parameters()
@@ -150,30 +291,84 @@ class ScriptumParser:
             parameters=parameters,
             return_type=return_type,
             body=body,
+            type_parameters=type_parameters,
         )
 
-    def _parse_variable_declaration(self, global_scope: bool) -> nodes.VariableDeclaration:
+    def _parse_variable_declarations(self, global_scope: bool) -> List[nodes.Declaration]:
+        """
+        Parse `mutabilis a = 1, b = 2;` into one declaration per name.
+
+        A name without its own annotation reuses the one before it, so in
+        `constans numerus a = 1, b = 2;` both are numerus. The first declaration
+        starts at the keyword and the last one ends at the `;`.
+        """
+
         keyword = self._advance()
         mutable = keyword.lexeme == "mutabilis"
-        name_token, type_annotation, _ = self._parse_binding(
-            allow_type_prefix=True,
-            message="Expected identifier for variable declaration.",
-        )
+        if self._check_symbol("("):
+            return [self._parse_destructuring_declaration(keyword, global_scope)]
+        declarations: List[nodes.VariableDeclaration] = []
+        previous_annotation: Optional[nodes.TypeAnnotation] = None
+        while True:
+            name_token, type_annotation, binding_span = self._parse_binding(
+                allow_type_prefix=True,
+                message="Expected identifier for variable declaration.",
+            )
+            if type_annotation is None and previous_annotation is not None:
+                type_annotation = replace(previous_annotation, node_id=self._next_id())
+            previous_annotation = type_annotation
+
+            initializer = None
+            if self._match_symbol("="):
+                initializer = self._parse_expression()
+
+            start = keyword.span if not declarations else binding_span
+            end = initializer.span if initializer is not None else binding_span
+            declarations.append(
+                nodes.VariableDeclaration(
+                    node_id=self._next_id(),
+                    span=self._combine_spans(start, end),
+                    mutable=mutable,
+                    name=name_token.lexeme,
+                    type_annotation=type_annotation,
+                    initializer=initializer,
+                    is_global=global_scope,
+                )
+            )
+            if not self._match_symbol(","):
+                break
 
-        initializer = None
-        if self._match_symbol("="):
-            initializer = self._parse_expression()
+        semicolon = self._consume_symbol(";", "Expected ';' after variable declaration.")
+        declarations[-1].span = self._combine_spans(declarations[-1].span, semicolon.span)
+        return declarations
 
+    def _parse_destructuring_declaration(
+        self, keyword: tokens.Token, global_scope: bool
+    ) -> nodes.DestructuringDeclaration:
+        """Parse the `(q, r) = expr;` after `constans`/`mutabilis`; the value is required."""
+
+        if global_scope:
+            raise ParseError(
+                f"Destructuring declarations are only allowed inside functions at {keyword.span}.",
+                span=keyword.span,
+            )
+        self._consume_symbol("(", "Expected '(' to open the destructuring pattern.")
+        names: List[str] = []
+        while True:
+            name = self._consume(tokens.TokenKind.IDENTIFIER, "Expected a name in the destructuring pattern.")
+            names.append(name.lexeme)
+            if not self._match_symbol(","):
+                break
+        self._consume_symbol(")", "Expected ')' to close the destructuring pattern.")
+        self._consume_symbol("=", "Expected '=' after the destructuring pattern.")
+        initializer = self._parse_expression()
         semicolon = self._consume_symbol(";", "Expected ';' after variable declaration.")
-        span = self._combine_spans(keyword.span, semicolon.span)
-        return nodes.VariableDeclaration(
+        return nodes.DestructuringDeclaration(
             node_id=self._next_id(),
-            span=span,
-            mutable=mutable,
-            name=name_token.lexeme,
-            type_annotation=type_annotation,
+            span=self._combine_spans(keyword.span, semicolon.span),
+            mutable=keyword.lexeme == "mutabilis",
+            names=names,
             initializer=initializer,
-            is_global=global_scope,
         )
 
     def _parse_parameters(self) -> List[nodes.Parameter]:
@@ -209,7 +404,27 @@ class ScriptumParser:
         if self._match_symbol("{"):
             return self._parse_block_statement(already_open=True)
         if self._check_keyword("mutabilis") or self._check_keyword("constans"):
-            return self._parse_variable_declaration(global_scope=False)
+            declarations = self._parse_variable_declarations(global_scope=False)
+            if len(declarations) == 1:
+                return declarations[0]
+            span = self._combine_spans(declarations[0].span, declarations[-1].span)
+            return nodes.BlockStatement(node_id=self._next_id(), span=span, statements=list(declarations))
+        if self._check_keyword("publicus") or self._check_keyword("privatus"):
+            token = self._peek()
+            raise ParseError(
+                f"Visibility modifier {token.lexeme!r} at {token.span} is only allowed on top-level declarations.",
+                span=token.span,
+            )
+        if self._check_keyword("importa"):
+            token = self._peek()
+            raise ParseError(
+                f"'importa' at {token.span} is only allowed at the top level of a module.", span=token.span
+            )
+        token = self._peek()
+        if token.kind is tokens.TokenKind.IDENTIFIER and self._peek_next().lexeme == ":":
+            return self._parse_labeled_loop()
+        if token.kind is tokens.TokenKind.KEYWORD and token.lexeme in _ORPHAN_KEYWORDS:
+            raise ParseError(f"{_ORPHAN_KEYWORDS[token.lexeme]} at {token.span}.", span=token.span)
         if self._match_keyword("si"):
             return self._parse_if_statement()
         if self._match_keyword("dum"):
@@ -220,20 +435,48 @@ class ScriptumParser:
             return self._parse_return_statement()
         if self._match_keyword("frange"):
             keyword = self._previous()
+            label = self._parse_jump_label()
             semicolon = self._consume_symbol(";", "Expected ';' after 'frange'.")
             return nodes.BreakStatement(
                 node_id=self._next_id(),
                 span=self._combine_spans(keyword.span, semicolon.span),
+                label=label,
             )
         if self._match_keyword("perge"):
             keyword = self._previous()
+            label = self._parse_jump_label()
             semicolon = self._consume_symbol(";", "Expected ';' after 'perge'.")
             return nodes.ContinueStatement(
                 node_id=self._next_id(),
                 span=self._combine_spans(keyword.span, semicolon.span),
+                label=label,
             )
         return self._parse_expression_statement()
 
+    def _parse_labeled_loop(self) -> nodes.Statement:
+        label_token = self._advance()
+        self._advance()  # ':'
+        loop: nodes.WhileStatement | nodes.ForStatement | nodes.CStyleForStatement
+        if self._match_keyword("dum"):
+            loop = self._parse_while_statement()
+        elif self._match_keyword("pro"):
+            loop = self._parse_for_statement()
+        else:
+            token = self._peek()
+            raise ParseError(
+                f"Label '{label_token.lexeme}' must be followed by a 'dum' or 'pro' loop. "
+                f"Found {token.lexeme!r} at {token.span}.",
+                span=token.span,
+            )
+        loop.label = label_token.lexeme
+        loop.span = self._combine_spans(label_token.span, loop.span)
+        return loop
+
+    def _parse_jump_label(self) -> Optional[str]:
+        if self._check(tokens.TokenKind.IDENTIFIER):
+            return self._advance().lexeme
+        return None
+
     def _parse_block_statement(self, already_open: bool = False) -> nodes.BlockStatement:
         if not already_open:
             open_token = self._consume_symbol("{", "Expected '{'.")
@@ -241,7 +484,10 @@ class ScriptumParser:
             open_token = self._previous()
         statements: List[nodes.Statement] = []
         while not self._check_symbol("}") and not self._is_at_end():
-            statements.append(self._parse_statement())
+            if self._check_keyword("mutabilis") or self._check_keyword("constans"):
+                statements.extend(self._parse_variable_declarations(global_scope=False))
+            else:
+                statements.append(self._parse_statement())
         close_token = self._consume_symbol("}", "Expected '}' to close block.")
         span = self._combine_spans(open_token.span, close_token.span)
         return nodes.BlockStatement(node_id=self._next_id(), span=span, statements=statements)
@@ -279,9 +525,11 @@ class ScriptumParser:
             body=body,
         )
 
-    def _parse_for_statement(self) -> nodes.ForStatement:
+    def _parse_for_statement(self) -> nodes.ForStatement | nodes.CStyleForStatement:
         keyword_token = self._previous()
         using_parentheses = self._match_symbol("(")
+        if using_parentheses and self._is_c_style_header():
+            return self._parse_c_style_for(keyword_token)
 
         mutable = False
         binding_keyword: Optional[tokens.Token] = None
@@ -318,6 +566,55 @@ class ScriptumParser:
             body=body,
         )
 
+    def _is_c_style_header(self) -> bool:
+        """Whether the header after `pro (` has a `;` before its closing `)`, as in `pro (i = 0; i < n; i++)`."""
+
+        depth = 0
+        for token in self._tokens[self._index :]:
+            if token.kind is tokens.TokenKind.EOF:
+                return False
+            if token.lexeme in _DELIMITER_PAIRS:
+                depth += 1
+            elif token.lexeme in {")", "]", "}"}:
+                if depth == 0:
+                    return False
+                depth -= 1
+            elif token.lexeme == ";" and depth == 0:
+                return True
+        return False
+
+    def _parse_c_style_for(self, keyword_token: tokens.Token) -> nodes.CStyleForStatement:
+        init: Optional[nodes.Statement] = None
+        if self._check_keyword("mutabilis") or self._check_keyword("constans"):
+            declarations = self._parse_variable_declarations(global_scope=False)
+            if len(declarations) != 1 or not isinstance(declarations[0], nodes.VariableDeclaration):
+                raise ParseError(
+                    f"The initialiser of a C-style 'pro' declares a single variable at {declarations[0].span}.",
+                    span=declarations[0].span,
+                )
+            init = declarations[0]
+        elif not self._match_symbol(";"):
+            init = self._parse_expression_statement()
+
+        condition = None
+        if not self._check_symbol(";"):
+            condition = self._parse_expression()
+        self._consume_symbol(";", "Expected ';' after the condition of 'pro'.")
+
+        update = None
+        if not self._check_symbol(")"):
+            update = self._parse_expression()
+        self._consume_symbol(")", "Expected ')' after the update of 'pro'.")
+        body = self._parse_statement()
+        return nodes.CStyleForStatement(
+            node_id=self._next_id(),
+            span=self._combine_spans(keyword_token.span, body.span),
+            init=init,
+            condition=condition,
+            update=update,
+            body=body,
+        )
+
     def _parse_return_statement(self) -> nodes.ReturnStatement:
         keyword = self._previous()
         if self._check_symbol(";"):
@@ -337,7 +634,8 @@ class ScriptumParser:
             token = self._peek()
             raise ParseError(
                 f"Parser depth limit exceeded ({self.config.max_depth}). "
-                f"Last token: {token.lexeme!r} at {token.span}."
+                f"Last token: {token.lexeme!r} at {token.span}.",
+                span=token.span,
             )
 
     def _leave_depth(self) -> None:
@@ -358,16 +656,30 @@ class ScriptumParser:
                     if self._trace is not None:
                         self._trace.log(f"CALL {expr.span.start}:{expr.span.end}")
                     continue
-                if self._match_symbol("["):
-                    expr = self._finish_index(expr)
+                if self._check_symbol("[") or self._check_symbol("?["):
+                    expr = self._finish_index(expr, optional=self._advance().lexeme == "?[")
                     if self._trace is not None:
                         self._trace.log(f"INDEX {expr.span.start}:{expr.span.end}")
                     continue
-                if self._match_symbol("."):
-                    expr = self._finish_member(expr)
+                if self._check_symbol(".") or self._check_symbol("?."):
+                    expr = self._finish_member(expr, optional=self._advance().lexeme == "?.")
                     if self._trace is not None:
                         self._trace.log(f"MEMBER {expr.span.start}:{expr.span.end}")
                     continue
+                if self._check_symbol("<"):
+                    type_arguments = self._try_parse_type_arguments()
+                    if type_arguments is not None:
+                        expr = self._finish_call(expr, type_arguments)
+                        continue
+                if self._check_symbol("++") or self._check_symbol("--"):
+                    expr = self._finish_update(self._advance(), expr, prefix=False)
+                    continue
+                if self._check_keyword("functio") and isinstance(expr, (nodes.Identifier, nodes.MemberExpression)):
+                    # `forEach functio (x) { ... }` calls the callee with the lambda as its only argument.
+                    expr = self._attach_trailing_lambda(
+                        nodes.CallExpression(node_id=self._next_id(), span=expr.span, callee=expr, arguments=[])
+                    )
+                    continue
 
                 token = self._peek()
                 binding = binding_powers(token.lexeme)
@@ -375,9 +687,22 @@ class ScriptumParser:
                     break
 
                 operator_token = self._advance()
+                if operator_token.lexeme == "ut":
+                    target_type = self._parse_type_annotation()
+                    expr = nodes.CastExpression(
+                        node_id=self._next_id(),
+                        span=self._combine_spans(expr.span, target_type.span),
+                        expression=expr,
+                        target_type=target_type,
+                    )
+                    continue
                 if operator_token.lexeme == "?":
                     true_expr = self._parse_expression()
-                    self._consume_symbol(":", "Expected ':' in conditional expression.")
+                    # Stop at the missing ':' instead of reading the rest as the alternate branch.
+                    self._consume_symbol(
+                        ":",
+                        f"Expected ':' to complete the conditional expression started by '?' at {operator_token.span}.",
+                    )
                     # Allow lower-precedence operators (e.g. assignment) inside the alternate branch.
                     false_min_bp = binding[1] - 1 if binding[1] > 0 else 0
                     false_expr = self._parse_expression(false_min_bp)
@@ -394,7 +719,19 @@ class ScriptumParser:
 
                 right = self._parse_expression(binding[1])
                 span = self._combine_spans(expr.span, right.span)
-                if operator_token.lexeme == "=":
+                if operator_token.lexeme in {"..", "..="}:
+                    expr = nodes.RangeExpression(
+                        node_id=self._next_id(),
+                        span=span,
+                        start=expr,
+                        end=right,
+                        inclusive=operator_token.lexeme == "..=",
+                    )
+                elif operator_token.lexeme == "=":
+                    if is_optional_chain(expr):
+                        raise ParseError(
+                            f"Cannot assign to an optional chain ('?.' or '?[') at {expr.span}.", span=expr.span
+                        )
                     expr = nodes.AssignmentExpression(
                         node_id=self._next_id(),
                         span=span,
@@ -464,10 +801,19 @@ class ScriptumParser:
             children = [self._expression_to_trace(expr.object)]
         elif isinstance(expr, nodes.IndexExpression):
             children = [self._expression_to_trace(expr.collection), self._expression_to_trace(expr.index)]
+        elif isinstance(expr, nodes.UpdateExpression):
+            lexeme = expr.operator.value
+            children = [self._expression_to_trace(expr.operand)]
+        elif isinstance(expr, nodes.CastExpression):
+            lexeme = expr.target_type.name
+            children = [self._expression_to_trace(expr.expression)]
+        elif isinstance(expr, nodes.RangeExpression):
+            lexeme = "..=" if expr.inclusive else ".."
+            children = [self._expression_to_trace(expr.start), self._expression_to_trace(expr.end)]
         elif isinstance(expr, nodes.UnaryExpression):
             lexeme = expr.operator.name if isinstance(expr.operator, nodes.UnaryOperator) else str(expr.operator)
             children = [self._expression_to_trace(expr.operand)]
-        elif isinstance(expr, nodes.ArrayLiteral):
+        elif isinstance(expr, (nodes.ArrayLiteral, nodes.TupleLiteral)):
             children = [self._expression_to_trace(element) for element in expr.elements]
         elif isinstance(expr, nodes.ObjectLiteral):
             for prop in expr.properties:
@@ -577,6 +923,8 @@ class ScriptumParser:
                 nodes.BinaryOperator.DIV: "/",
                 nodes.BinaryOperator.MOD: "%",
                 nodes.BinaryOperator.POW: "**",
+                nodes.BinaryOperator.SHL: "<<",
+                nodes.BinaryOperator.SHR: ">>",
                 nodes.BinaryOperator.GT: ">",
                 nodes.BinaryOperator.LT: "<",
                 nodes.BinaryOperator.GE: ">=",
@@ -596,13 +944,26 @@ class ScriptumParser:
         return None
 
     def _parse_prefix(self) -> nodes.Expression:
+        if self._is_at_end():
+            # `_advance` stays on EOF and would hand back the previous token again.
+            token = self._peek()
+            message = f"Unexpected end of input at {token.span}."
+            if self._delimiters:
+                raise self._unclosed_delimiter_error(message, token.span, self._delimiters[-1])
+            raise ParseError(message, span=token.span)
         token = self._advance()
 
         if token.kind is tokens.TokenKind.IDENTIFIER:
             return nodes.Identifier(node_id=self._next_id(), span=token.span, name=token.lexeme)
 
         if token.kind is tokens.TokenKind.NUMBER_LITERAL:
-            return nodes.Literal(node_id=self._next_id(), span=token.span, value=token.value, raw=token.lexeme)
+            return nodes.Literal(
+                node_id=self._next_id(),
+                span=token.span,
+                value=token.value,
+                raw=token.lexeme,
+                suffix=(token.metadata or {}).get("suffix"),
+            )
 
         if token.kind is tokens.TokenKind.STRING_LITERAL:
             return nodes.Literal(node_id=self._next_id(), span=token.span, value=token.value, raw=token.lexeme)
@@ -616,13 +977,22 @@ class ScriptumParser:
                 return nodes.Literal(node_id=self._next_id(), span=token.span, value=None, raw=token.lexeme)
             if token.lexeme == "indefinitum":
                 return nodes.Literal(node_id=self._next_id(), span=token.span, value="indefinitum", raw=token.lexeme)
+            if token.lexeme in _FLOAT_KEYWORDS:
+                # `raw` keeps the keyword, so the formatter never prints Python's `inf`/`nan`.
+                value = _FLOAT_KEYWORDS[token.lexeme]
+                return nodes.Literal(node_id=self._next_id(), span=token.span, value=value, raw=token.lexeme)
             if token.lexeme == "structura":
                 return self._parse_object_literal(token)
             if token.lexeme == "functio":
                 return self._parse_lambda_expression(token)
+            if token.lexeme == "ipse":
+                # The analyzer declares `ipse` in every method, so it resolves like any other name.
+                return nodes.Identifier(node_id=self._next_id(), span=token.span, name=token.lexeme)
 
         if token.lexeme == "(":
             expr = self._parse_expression()
+            if self._check_symbol(","):
+                return self._parse_tuple_literal(token, expr)
             closing = self._consume_symbol(")", "Expected ')' after expression.")
             expr.span = self._combine_spans(token.span, closing.span)
             return expr
@@ -630,8 +1000,11 @@ class ScriptumParser:
         if token.lexeme == "[":
             return self._parse_array_literal(token)
 
-        if token.lexeme in {"!", "-", "+"}:
-            operand = self._parse_expression(10)
+        if token.lexeme in {"++", "--"}:
+            return self._finish_update(token, self._parse_expression(11), prefix=True)
+
+        if token.lexeme in {"!", "-", "+", "~"}:
+            operand = self._parse_expression(11)
             span = self._combine_spans(token.span, operand.span)
             return nodes.UnaryExpression(
                 node_id=self._next_id(),
@@ -640,9 +1013,49 @@ class ScriptumParser:
                 operand=operand,
             )
 
-        raise ParseError(f"Unexpected token {token.lexeme!r} at {token.span}.")
+        raise ParseError(f"Unexpected token {token.lexeme!r} at {token.span}.", span=token.span)
+
+    def _finish_update(self, operator: tokens.Token, operand: nodes.Expression, prefix: bool) -> nodes.Expression:
+        if not isinstance(operand, nodes.Identifier):
+            raise ParseError(
+                f"Operand of {operator.lexeme!r} must be an identifier at {operand.span}.",
+                span=operand.span,
+            )
+        if prefix:
+            span = self._combine_spans(operator.span, operand.span)
+        else:
+            span = self._combine_spans(operand.span, operator.span)
+        return nodes.UpdateExpression(
+            node_id=self._next_id(),
+            span=span,
+            operator=nodes.UpdateOperator(operator.lexeme),
+            operand=operand,
+            prefix=prefix,
+        )
+
+    def _try_parse_type_arguments(self) -> Optional[List[nodes.TypeAnnotation]]:
+        """Parse `<T, ...>(` after a callee, or rewind and return None if this is a comparison."""
+
+        saved_index, saved_counter, saved_delimiters = self._index, self._node_counter, list(self._delimiters)
+        self._advance()
+        type_arguments: List[nodes.TypeAnnotation] = []
+        try:
+            while True:
+                type_arguments.append(self._parse_type_annotation())
+                if not self._match_symbol(","):
+                    break
+            if self._match_symbol(">") and self._match_symbol("("):
+                return type_arguments
+        except ParseError:
+            pass
+        self._index, self._node_counter, self._delimiters = saved_index, saved_counter, saved_delimiters
+        return None
 
-    def _finish_call(self, callee: nodes.Expression) -> nodes.Expression:
+    def _finish_call(
+        self,
+        callee: nodes.Expression,
+        type_arguments: Optional[List[nodes.TypeAnnotation]] = None,
+    ) -> nodes.Expression:
         arguments: List[nodes.Expression] = []
         if not self._check_symbol(")"):
             while True:
@@ -650,14 +1063,25 @@ class ScriptumParser:
                 if not self._match_symbol(","):
                     break
         closing = self._consume_symbol(")", "Expected ')' after arguments.")
-        return nodes.CallExpression(
+        call = nodes.CallExpression(
             node_id=self._next_id(),
             span=self._combine_spans(callee.span, closing.span),
             callee=callee,
             arguments=arguments,
+            type_arguments=type_arguments or [],
         )
+        if self._check_keyword("functio"):
+            return self._attach_trailing_lambda(call)
+        return call
+
+    def _attach_trailing_lambda(self, call: nodes.CallExpression) -> nodes.CallExpression:
+        lambda_expr = self._parse_lambda_expression(self._advance())
+        call.arguments.append(lambda_expr)
+        call.trailing_lambda = True
+        call.span = self._combine_spans(call.span, lambda_expr.span)
+        return call
 
-    def _finish_index(self, collection: nodes.Expression) -> nodes.Expression:
+    def _finish_index(self, collection: nodes.Expression, optional: bool = False) -> nodes.Expression:
         index_expr = self._parse_expression()
         closing = self._consume_symbol("]", "Expected ']' after index expression.")
         return nodes.IndexExpression(
@@ -665,15 +1089,22 @@ class ScriptumParser:
             span=self._combine_spans(collection.span, closing.span),
             collection=collection,
             index=index_expr,
+            optional=optional,
         )
 
-    def _finish_member(self, obj: nodes.Expression) -> nodes.Expression:
-        name_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected property name after '.'.")
+    def _finish_member(self, obj: nodes.Expression, optional: bool = False) -> nodes.Expression:
+        dot = "?." if optional else "."
+        if self._check(tokens.TokenKind.KEYWORD):
+            # After '.' a keyword can only be a member name, so `obj.redde` reads like `obj.type` elsewhere.
+            name_token = self._advance()
+        else:
+            name_token = self._consume(tokens.TokenKind.IDENTIFIER, f"Expected property name after '{dot}'.")
         return nodes.MemberExpression(
             node_id=self._next_id(),
             span=self._combine_spans(obj.span, name_token.span),
             object=obj,
             property=name_token.lexeme,
+            optional=optional,
         )
 
     def _parse_array_literal(self, start_token: tokens.Token) -> nodes.ArrayLiteral:
@@ -690,24 +1121,38 @@ class ScriptumParser:
             elements=elements,
         )
 
+    def _parse_tuple_literal(self, start_token: tokens.Token, first: nodes.Expression) -> nodes.TupleLiteral:
+        elements = [first]
+        while self._match_symbol(","):
+            elements.append(self._parse_expression())
+        closing = self._consume_symbol(")", "Expected ')' after tuple elements.")
+        return nodes.TupleLiteral(
+            node_id=self._next_id(),
+            span=self._combine_spans(start_token.span, closing.span),
+            elements=elements,
+        )
+
     def _parse_object_literal(self, start_token: tokens.Token) -> nodes.ObjectLiteral:
         open_brace = self._consume_symbol("{", "Expected '{' after 'structura'.")
         properties: List[nodes.ObjectProperty] = []
         if not self._check_symbol("}"):
             while True:
-                key_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected property identifier.")
+                if self._check(tokens.TokenKind.STRING_LITERAL):
+                    # `"com-traço": 1`: the key is the string's value, so `"x"` and `x` are the same key.
+                    key_token = self._advance()
+                    key = key_token.value
+                else:
+                    key_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected property identifier or string.")
+                    key = key_token.lexeme
                 self._consume_symbol(":", "Expected ':' after property name.")
                 value_expr = self._parse_expression()
                 prop_span = self._combine_spans(key_token.span, value_expr.span)
-                properties.append(
-                    nodes.ObjectProperty(
-                        node_id=self._next_id(),
-                        span=prop_span,
-                        key=key_token.lexeme,
-                        value=value_expr,
-                    )
-                )
-                if not self._match_symbol(","):
+                prop = nodes.ObjectProperty(node_id=self._next_id(), span=prop_span, key=key, value=value_expr)
+                properties.append(prop)
+                more = self._match_symbol(",")
+                # A comment closing the line, after the value or its comma, belongs to this property.
+                prop.comment = (self._previous().metadata or {}).get("comment")
+                if not more:
                     break
         closing = self._consume_symbol("}", "Expected '}' after object literal.")
         return nodes.ObjectLiteral(
@@ -746,30 +1191,109 @@ class ScriptumParser:
             body=body_block,
         )
 
+    def _parse_type_parameters(self) -> List[nodes.TypeParameter]:
+        if not self._match_symbol("<"):
+            return []
+        parameters: List[nodes.TypeParameter] = []
+        while True:
+            name_token = self._consume(tokens.TokenKind.IDENTIFIER, "Expected type parameter name.")
+            parameters.append(
+                nodes.TypeParameter(node_id=self._next_id(), span=name_token.span, name=name_token.lexeme)
+            )
+            if not self._match_symbol(","):
+                break
+        self._consume_symbol(">", "Expected '>' after type parameters.")
+        return parameters
+
     def _parse_type_annotation(self) -> nodes.TypeAnnotation:
         parts: List[str] = []
         start_span: Optional[Span] = None
         end_span: Optional[Span] = None
+        open_brackets = 0
+        open_parens = 0
+        open_braces = 0
         while not self._is_at_end():
             token = self._peek()
             if token.kind is tokens.TokenKind.KEYWORD and token.lexeme in TYPE_KEYWORDS:
                 parts.append(self._advance().lexeme)
             elif token.kind is tokens.TokenKind.IDENTIFIER:
                 parts.append(self._advance().lexeme)
-            elif token.lexeme in {"[", "]", "?", "->"}:
+            elif token.lexeme == "{" and (not parts or parts[-1] in {"(", "[", ": ", ", "}):
+                # An object type; after a complete type, `{` opens a body instead.
+                open_braces += 1
+                parts.append(self._advance().lexeme)
+            elif token.lexeme == "}" and open_braces > 0:
+                open_braces -= 1
+                parts.append(self._advance().lexeme)
+            elif (token.lexeme in {":", ","} and open_braces > 0) or (token.lexeme == "," and open_parens > 0):
+                # Field separators inside an object type and tuple elements, spaced as they are printed.
+                parts.append(self._advance().lexeme + " ")
+            elif token.lexeme == "]" and open_brackets == 0:
+                # Closes an enclosing expression, e.g. `[x ut numerus]`.
+                break
+            elif token.lexeme == ")" and open_parens == 0:
+                # Closes an enclosing expression, e.g. `(x ut numerus)`.
+                break
+            elif token.lexeme == "(" and parts and parts[-1] not in {"(", "[", "->", ": ", ", "}:
+                # Only a grouping paren can start a type; this one is a call, e.g. `x ut T (`.
+                break
+            elif (token.lexeme == "?" and not self._question_opens_conditional()) or (
+                token.lexeme == "??" and parts and self._peek_next().lexeme in _ANNOTATION_FOLLOW
+            ):
+                # A type is optional at most once: `numerus??` (lexed as '??') and `numerus? ?` mean `numerus?`.
+                self._advance()
+                if parts[-1:] != ["?"]:
+                    parts.append("?")
+            elif token.lexeme in {"[", "]", "(", ")", "->"}:
+                open_brackets += {"[": 1, "]": -1}.get(token.lexeme, 0)
+                open_parens += {"(": 1, ")": -1}.get(token.lexeme, 0)
                 parts.append(self._advance().lexeme)
             else:
                 break
             start_span = start_span or token.span
             end_span = token.span
         if not parts or start_span is None or end_span is None:
-            raise ParseError("Expected type annotation.")
+            token = self._peek()
+            raise ParseError(f"Expected type annotation. Found {token.lexeme!r} at {token.span}.", span=token.span)
+        if open_braces:
+            token = self._peek()
+            raise ParseError(
+                f"Expected '}}' to close the object type. Found {token.lexeme!r} at {token.span}.",
+                span=token.span,
+            )
         return nodes.TypeAnnotation(
             node_id=self._next_id(),
             span=self._combine_spans(start_span, end_span),
             name="".join(parts),
         )
 
+    def _question_opens_conditional(self) -> bool:
+        """
+        Whether the `?` at the cursor starts the branches of a conditional rather than making a type optional.
+
+        It does when an expression follows and a `:` closes it before the enclosing statement, argument or
+        group ends, as in `x ut booleanum ? a : b`; `c ? x ut numerus? : y` keeps the optional type.
+        """
+
+        following = self._peek_next().lexeme
+        if following in _ANNOTATION_FOLLOW or following in {":", "?", "??"}:
+            return False
+        depth = 0
+        for token in self._tokens[self._index + 1 :]:
+            if token.kind is tokens.TokenKind.EOF:
+                break
+            if token.lexeme in {"(", "[", "{", "?["}:
+                depth += 1
+            elif token.lexeme in {")", "]", "}"}:
+                if depth == 0:
+                    break
+                depth -= 1
+            elif depth == 0 and token.lexeme == ":":
+                return True
+            elif depth == 0 and token.lexeme in {";", ","}:
+                break
+        return False
+
     # Binding helpers -------------------------------------------------------------
 
     def _parse_binding(self, allow_type_prefix: bool, message: str) -> Tuple[tokens.Token, Optional[nodes.TypeAnnotation], Span]:
@@ -786,9 +1310,11 @@ class ScriptumParser:
         name_token = self._consume(tokens.TokenKind.IDENTIFIER, message)
         if start_span is None:
             start_span = name_token.span
+        # The binding ends at its name for `numerus x`, not at the prefix type.
+        end_span = name_token.span
         if self._match_symbol(":"):
             type_annotation = self._parse_type_annotation()
-        end_span = type_annotation.span if type_annotation else name_token.span
+            end_span = type_annotation.span
         return name_token, type_annotation, self._combine_spans(start_span, end_span)
 
     def _is_type_prefix(self) -> bool:
@@ -821,6 +1347,8 @@ class ScriptumParser:
             "/": nodes.BinaryOperator.DIV,
             "%": nodes.BinaryOperator.MOD,
             "**": nodes.BinaryOperator.POW,
+            "<<": nodes.BinaryOperator.SHL,
+            ">>": nodes.BinaryOperator.SHR,
             "||": nodes.BinaryOperator.OR,
             "&&": nodes.BinaryOperator.AND,
             "??": nodes.BinaryOperator.NULLISH,
@@ -840,6 +1368,7 @@ class ScriptumParser:
             "-": nodes.UnaryOperator.NEGATE,
             "+": nodes.UnaryOperator.POSITIVE,
             "!": nodes.UnaryOperator.NOT,
+            "~": nodes.UnaryOperator.BIT_NOT,
         }
         return mapping[lexeme]
 
@@ -850,19 +1379,31 @@ class ScriptumParser:
         if self._check(kind):
             return self._advance()
         token = self._peek()
-        raise ParseError(f"{message} Found {token.lexeme!r} at {token.span}.")
+        raise ParseError(f"{message} Found {token.lexeme!r} at {token.span}.", span=token.span)
 
     def _consume_symbol(self, symbol: str, message: str) -> tokens.Token:
         if self._match_symbol(symbol):
             return self._previous()
         token = self._peek()
-        raise ParseError(f"{message} Found {token.lexeme!r} at {token.span}.")
+        message = f"{message} Found {token.lexeme!r} at {token.span}."
+        opener = self._delimiters[-1] if self._delimiters else None
+        if opener is None or _DELIMITER_PAIRS[opener.lexeme] != symbol:
+            raise ParseError(message, span=token.span)
+        raise self._unclosed_delimiter_error(message, token.span, opener)
+
+    def _unclosed_delimiter_error(self, message: str, span: Span, opener: tokens.Token) -> ParseError:
+        line, column = self._source.line_col(opener.span)
+        return ParseError(
+            f"{message} The '{opener.lexeme}' opened at line {line}, column {column} is still open.",
+            span=span,
+            notes=[(f"'{opener.lexeme}' opened here", opener.span)],
+        )
 
     def _consume_keyword(self, keyword: str) -> tokens.Token:
         if self._match_keyword(keyword):
             return self._previous()
         token = self._peek()
-        raise ParseError(f"Expected keyword '{keyword}', found {token.lexeme!r}.")
+        raise ParseError(f"Expected keyword '{keyword}', found {token.lexeme!r} at {token.span}.", span=token.span)
 
     def _check(self, kind: tokens.TokenKind) -> bool:
         if self._is_at_end():
@@ -906,8 +1447,17 @@ class ScriptumParser:
     def _advance(self) -> tokens.Token:
         if not self._is_at_end():
             self._index += 1
+            self._track_delimiter(self._tokens[self._index - 1])
         return self._tokens[self._index - 1]
 
+    def _track_delimiter(self, token: tokens.Token) -> None:
+        if token.kind is not tokens.TokenKind.DELIMITER:
+            return
+        if token.lexeme in _DELIMITER_PAIRS:
+            self._delimiters.append(token)
+        elif self._delimiters and _DELIMITER_PAIRS[self._delimiters[-1].lexeme] == token.lexeme:
+            self._delimiters.pop()
+
     def _peek(self) -> tokens.Token:
         return self._tokens[self._index]
 
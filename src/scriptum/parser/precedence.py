@@ -34,12 +34,17 @@ PRECEDENCE_TABLE: Dict[str, PrecedenceRule] = {
     ">=": PrecedenceRule(7, Associativity.LEFT),
     "<": PrecedenceRule(7, Associativity.LEFT),
     "<=": PrecedenceRule(7, Associativity.LEFT),
-    "+": PrecedenceRule(8, Associativity.LEFT),
-    "-": PrecedenceRule(8, Associativity.LEFT),
-    "*": PrecedenceRule(9, Associativity.LEFT),
-    "/": PrecedenceRule(9, Associativity.LEFT),
-    "%": PrecedenceRule(9, Associativity.LEFT),
-    "**": PrecedenceRule(10, Associativity.RIGHT),
+    "..": PrecedenceRule(7, Associativity.LEFT),
+    "..=": PrecedenceRule(7, Associativity.LEFT),
+    "ut": PrecedenceRule(7, Associativity.LEFT),
+    "<<": PrecedenceRule(8, Associativity.LEFT),
+    ">>": PrecedenceRule(8, Associativity.LEFT),
+    "+": PrecedenceRule(9, Associativity.LEFT),
+    "-": PrecedenceRule(9, Associativity.LEFT),
+    "*": PrecedenceRule(10, Associativity.LEFT),
+    "/": PrecedenceRule(10, Associativity.LEFT),
+    "%": PrecedenceRule(10, Associativity.LEFT),
+    "**": PrecedenceRule(11, Associativity.RIGHT),
 }
 
 
@@ -2,13 +2,29 @@
 
 from __future__ import annotations
 
-from dataclasses import dataclass
+import math
+import re
+from dataclasses import dataclass, replace
+from enum import Enum
 from itertools import zip_longest
-from typing import Dict, List, Optional, Tuple
+from typing import Dict, FrozenSet, List, Mapping, Optional, Sequence, Set, Tuple
 
 from ..ast import nodes
+from ..ast.visitors import is_optional_chain, walk
 from ..text import Span
-from . import symbols, types
+from . import prelude, symbols, types
+from .liveness import find_dead_stores
+
+
+# Largest integer magnitude below which every integer is exactly representable as numerus (IEEE-754 double).
+MAX_SAFE_INTEGER = 2**53
+
+_TYPE_NAME_RE = re.compile(r"[A-Za-z_$][A-Za-z0-9_$]*")
+
+
+class Severity(Enum):
+    ERROR = "error"
+    WARNING = "warning"
 
 
 @dataclass(slots=True)
@@ -16,47 +32,371 @@ class SemanticDiagnostic:
     code: str
     message: str
     span: Optional[Span]
+    severity: Severity = Severity.ERROR
+    # Secondary locations, such as the first definition of a function defined twice.
+    notes: Tuple[Tuple[str, Span], ...] = ()
 
+    @property
+    def is_error(self) -> bool:
+        return self.severity is Severity.ERROR
+
+
+@dataclass(slots=True)
+class AnalyzerOptions:
+    """Opt-in checks layered on top of the default rules."""
+
+    # Require `ut` for every implicit conversion to or from quodlibet (T210).
+    strict_any: bool = False
+    # Require a type annotation on every parameter of a top-level function (T220).
+    strict_params: bool = False
+
+
+_SCALAR_KINDS = {types.TypeKind.NUMERUS, types.TypeKind.TEXTUS, types.TypeKind.BOOLEANUM, types.TypeKind.NULLUM}
+
+# Call, member and index expressions chain: in `a?.b.c()` a nullum `a` skips `.c()` too.
+_CHAIN_LINKS = (nodes.CallExpression, nodes.MemberExpression, nodes.IndexExpression)
+
+_SHORT_CIRCUIT_OPERATORS = {nodes.BinaryOperator.AND, nodes.BinaryOperator.OR, nodes.BinaryOperator.NULLISH}
+
+# Already admit nullum (or no value), so an optional chain leaves them as they are.
+_NEVER_OPTIONAL_KINDS = {types.TypeKind.QUODLIBET, types.TypeKind.NULLUM, types.TypeKind.VACUUM}
+
+# Values of these types carry no fields; Scriptum has no built-in members such as a length.
+_MEMBERLESS_KINDS = {
+    types.TypeKind.NUMERUS,
+    types.TypeKind.TEXTUS,
+    types.TypeKind.BOOLEANUM,
+    types.TypeKind.NULLUM,
+    types.TypeKind.VACUUM,
+    types.TypeKind.ARRAY,
+    types.TypeKind.RANGE,
+    types.TypeKind.FUNCTION,
+}
+
+
+def _never_equal(left: types.Type, right: types.Type) -> bool:
+    # Equality never converts between types, so distinct scalar types cannot compare equal.
+    if left.kind not in _SCALAR_KINDS or right.kind not in _SCALAR_KINDS:
+        return False
+    return not (left.is_assignable_from(right) or right.is_assignable_from(left))
+
+
+def _constant_number(expr: nodes.Expression) -> Optional[float]:
+    """Value of a numeric literal, possibly signed; None for anything else."""
+
+    if isinstance(expr, nodes.Literal) and isinstance(expr.value, (int, float)) and not isinstance(expr.value, bool):
+        return expr.value
+    if isinstance(expr, nodes.UnaryExpression) and expr.operator in {
+        nodes.UnaryOperator.NEGATE,
+        nodes.UnaryOperator.POSITIVE,
+    }:
+        value = _constant_number(expr.operand)
+        if value is not None and expr.operator is nodes.UnaryOperator.NEGATE:
+            return -value
+        return value
+    return None
+
+
+def _is_finite(value: float) -> bool:
+    """`math.isfinite` that also takes ints too large for a double, which evaluate to infinitum."""
+    try:
+        return math.isfinite(value)
+    except OverflowError:
+        return False
+
+
+def _is_whole(value: float) -> bool:
+    """True for an integral value; safe on ints beyond the range of a double, on infinitum and on nan."""
+
+    return isinstance(value, int) or (math.isfinite(value) and value.is_integer())
+
+
+def _number_text(value: float) -> str:
+    """*value* as Scriptum spells it: `3` for a whole number, `infinitum`, `-infinitum` or `nan` otherwise."""
+
+    if isinstance(value, int) or (_is_whole(value) and abs(value) < 1e21):
+        return str(int(value))
+    # `1e+300` rather than all 301 digits of the double.
+    return {"inf": "infinitum", "-inf": "-infinitum"}.get(str(value), str(value))
+
+
+def _int32(value: float) -> int:
+    """*value* truncated to a signed 32-bit integer, as `<<` and `>>` see their operands."""
+
+    wrapped = int(value) & 0xFFFFFFFF
+    return wrapped - (1 << 32) if wrapped & 0x80000000 else wrapped
+
+
+def _mismatch_reason(expected: types.Type, actual: types.Type) -> str:
+    """Suffix naming the first difference when both sides are functions, objects or tuples."""
+
+    reason = (
+        types.signature_mismatch(expected, actual)
+        or types.object_mismatch(expected, actual)
+        or types.tuple_mismatch(expected, actual)
+    )
+    return f" ({reason})" if reason else ""
+
+
+def _vacuum_array(type_obj: Optional[types.Type]) -> Optional[types.Type]:
+    """Return the first array type nested in *type_obj* whose elements are vacuum."""
+
+    if type_obj is None:
+        return None
+    if type_obj.kind is types.TypeKind.ARRAY and type_obj.element and type_obj.element.kind is types.TypeKind.VACUUM:
+        return type_obj
+    nested = [
+        type_obj.element,
+        type_obj.ret,
+        *(type_obj.params or []),
+        *(type_obj.fields or {}).values(),
+        *(type_obj.items or []),
+    ]
+    for inner in nested:
+        found = _vacuum_array(inner)
+        if found is not None:
+            return found
+    return None
+
+
+def _expected_shape(expected: Optional[types.Type], kind: types.TypeKind) -> Optional[types.Type]:
+    """*expected*, or the type it makes optional, when that is a *kind* type; otherwise None."""
+
+    if expected is not None and expected.kind is types.TypeKind.OPTIONAL:
+        expected = expected.element
+    return expected if expected is not None and expected.kind is kind else None
+
+
+def _copy_flow(unassigned: Optional[Set[int]]) -> Optional[Set[int]]:
+    return None if unassigned is None else set(unassigned)
+
+
+def _merge_flow(*states: Optional[Set[int]]) -> Optional[Set[int]]:
+    """Join paths: a variable is unassigned if any reachable path leaves it so; None if none is reachable."""
+
+    reachable = [state for state in states if state is not None]
+    return set().union(*reachable) if reachable else None
+
+
+def _constant_boolean(expr: nodes.Expression) -> Optional[bool]:
+    """Value of `verum`/`falsum`, possibly behind `!`; None for anything else."""
+
+    if isinstance(expr, nodes.Literal) and isinstance(expr.value, bool):
+        return expr.value
+    if isinstance(expr, nodes.UnaryExpression) and expr.operator is nodes.UnaryOperator.NOT:
+        value = _constant_boolean(expr.operand)
+        return None if value is None else not value
+    return None
+
+
+def _can_leave_loop(
+    stmt: nodes.Statement,
+    nested: bool = False,
+    inner_labels: FrozenSet[str] = frozenset(),
+) -> bool:
+    """
+    Whether *stmt* holds a `redde`, or a `frange` that leaves the enclosing loop.
+
+    A labelled `frange` leaves it unless the label names a loop nested inside it.
+    """
+
+    if isinstance(stmt, nodes.ReturnStatement):
+        return True
+    if isinstance(stmt, nodes.BreakStatement):
+        return stmt.label not in inner_labels if stmt.label else not nested
+    if isinstance(stmt, nodes.BlockStatement):
+        return any(_can_leave_loop(child, nested, inner_labels) for child in stmt.statements)
+    if isinstance(stmt, nodes.IfStatement):
+        return _can_leave_loop(stmt.then_branch, nested, inner_labels) or (
+            stmt.else_branch is not None and _can_leave_loop(stmt.else_branch, nested, inner_labels)
+        )
+    if isinstance(stmt, (nodes.WhileStatement, nodes.ForStatement, nodes.CStyleForStatement)):
+        labels = inner_labels | {stmt.label} if stmt.label else inner_labels
+        return _can_leave_loop(stmt.body, True, labels)
+    return False
+
+
+class _TypeNameCollector:
+    """Gathers every name mentioned in the type annotations below a node."""
 
-class SemanticAnalyzer:
     def __init__(self) -> None:
+        self.names: Set[str] = set()
+
+    def visit(self, node: nodes.Node) -> None:
+        if isinstance(node, nodes.TypeAnnotation):
+            self.names.update(_TYPE_NAME_RE.findall(node.name))
+
+
+class SemanticAnalyzer:
+    def __init__(self, options: Optional[AnalyzerOptions] = None) -> None:
+        self.options = options or AnalyzerOptions()
         self.symbols = symbols.SymbolTable()
         self.diagnostics: List[SemanticDiagnostic] = []
         self.current_return_type: Optional[types.Type] = None
+        # Types returned so far by the function being analyzed when it has no return annotation.
+        self.returned_types: Optional[List[types.Type]] = None
         self.loop_depth: int = 0
+        # Labels of the enclosing loops, innermost last.
+        self.loop_labels: List[str] = []
+        # ids of the locals declared without a value that some path to this point leaves unassigned;
+        # None while analyzing code no path reaches (after `redde`, `frange` or `perge`).
+        self.unassigned: Optional[Set[int]] = set()
+        # Per enclosing loop, innermost last: its label and the `unassigned` state at each `frange` leaving it
+        # and at each `perge` continuing it.
+        self.loop_exits: List[Tuple[Optional[str], List[Optional[Set[int]]], List[Optional[Set[int]]]]] = []
         self.function_signatures: Dict[str, Tuple[List[types.Type], Optional[types.Type]]] = {}
+        self.exports: List[symbols.Symbol] = []
+        # Every declaration of the analyzed module, in source order of analysis.
+        self.symbol_index: List[symbols.SymbolEntry] = []
+        # Type of every expression analyzed so far, keyed by node id; editors read it for hover.
+        self.expression_types: Dict[int, types.Type] = {}
+        self.type_parameters: Sequence[str] = ()
+        # Resolved annotations keyed by their text and the type parameters in scope.
+        # `Type` is immutable, so cached instances are shared rather than copied.
+        self._annotation_cache: Dict[Tuple[str, Tuple[str, ...]], Optional[types.Type]] = {}
+        # ids of the annotation nodes already checked for `[vacuum]`, so T230 is reported once per annotation.
+        self._checked_annotations: Set[int] = set()
+        # ids of the symbols bound by `importa "m.stm" ut m`, whose members cannot be assigned.
+        self.namespaces: Set[int] = set()
+
+    def analyze(
+        self,
+        module: nodes.Module,
+        imports: Optional[Mapping[str, Sequence[symbols.Symbol]]] = None,
+    ) -> List[SemanticDiagnostic]:
+        """
+        Check *module* and return its diagnostics.
+
+        *imports* maps each `importa` path to the symbols exported by that module;
+        they are declared in the global scope before the module's own items.
+        """
 
-    def analyze(self, module: nodes.Module) -> List[SemanticDiagnostic]:
         self.diagnostics.clear()
         self.symbols = symbols.SymbolTable()
         self.function_signatures = {}
         self.current_return_type = None
+        self.returned_types = None
         self.loop_depth = 0
+        self.loop_labels = []
+        self.unassigned = set()
+        self.loop_exits = []
+        self.exports = []
+        self.symbol_index = []
+        self.expression_types = {}
+        self.type_parameters = ()
+        self._checked_annotations = set()
+        self.namespaces = set()
+
+        for declaration in module.declarations:
+            if isinstance(declaration, nodes.ImportDeclaration):
+                self._declare_imports(declaration, (imports or {}).get(declaration.path, ()))
 
         for declaration in module.declarations:
             if isinstance(declaration, nodes.FunctionDeclaration):
                 self._register_function(declaration)
+        self._declare_prelude(module)
 
+        self.symbols.expect(decl.name for decl in module.declarations if isinstance(decl, nodes.VariableDeclaration))
         for declaration in module.declarations:
             if isinstance(declaration, nodes.FunctionDeclaration):
                 self._analyze_function(declaration)
             elif isinstance(declaration, nodes.VariableDeclaration):
                 self._analyze_variable(declaration)
+
+        for declaration in module.declarations:
+            if isinstance(declaration, (nodes.FunctionDeclaration, nodes.VariableDeclaration)):
+                symbol = self.symbols.lookup(declaration.name)
+                if symbol is not None and declaration.visibility is not nodes.Visibility.PRIVATUS:
+                    self.exports.append(symbol)
         return list(self.diagnostics)
 
+    def _declare(self, symbol: symbols.Symbol, kind: symbols.SymbolKind) -> bool:
+        if not self.symbols.declare(symbol):
+            return False
+        self.symbol_index.append(symbols.SymbolEntry(symbol.name, kind, symbol.type, symbol.span))
+        return True
+
+    def _declare_prelude(self, module: nodes.Module) -> None:
+        # Builtins give way to the module's own names, so a program may define its own `imprime`.
+        taken = {decl.name for decl in module.declarations if isinstance(decl, nodes.VariableDeclaration)}
+        for name, builtin_type in prelude.PRELUDE.items():
+            if name not in taken and self.symbols.lookup(name) is None:
+                self.symbols.declare(symbols.Symbol(name, builtin_type, mutable=False))
+
+    def _declare_imports(self, declaration: nodes.ImportDeclaration, exported: Sequence[symbols.Symbol]) -> None:
+        if declaration.alias is not None:
+            # Members read the module's current values, but neither the namespace nor its members can be assigned.
+            fields = {symbol.name: symbol.type for symbol in exported}
+            namespace_type = types.Type(types.TypeKind.OBJECT, fields=fields)
+            namespace = symbols.Symbol(declaration.alias, namespace_type, mutable=False, span=declaration.span)
+            self.namespaces.add(id(namespace))
+            exported = [namespace]
+        elif declaration.names:
+            exported = self._select_imports(declaration, exported)
+        for symbol in exported:
+            if not self.symbols.declare(symbol):
+                self._error(
+                    "S110",
+                    f"Symbol '{symbol.name}' imported from '{declaration.path}' is already declared",
+                    declaration.span,
+                )
+
+    def _select_imports(
+        self,
+        declaration: nodes.ImportDeclaration,
+        exported: Sequence[symbols.Symbol],
+    ) -> List[symbols.Symbol]:
+        available = {symbol.name: symbol for symbol in exported}
+        selected: List[symbols.Symbol] = []
+        for item in declaration.names:
+            symbol = available.get(item.name)
+            if symbol is None:
+                self._error("S130", f"Module '{declaration.path}' has no export named '{item.name}'", item.span)
+            elif item.alias is None:
+                selected.append(symbol)
+            else:
+                # Like an import binding in JavaScript, a renamed import reads the export but cannot be assigned.
+                selected.append(symbols.Symbol(item.alias, symbol.type, mutable=False, span=item.span))
+        return selected
+
     def _register_function(self, func: nodes.FunctionDeclaration) -> None:
+        previous_type_parameters = self.type_parameters
+        self.type_parameters = [param.name for param in func.type_parameters]
         param_types = [
             self._annotation_to_type(param.type_annotation) or types.PRIMITIVE_TYPES["quodlibet"]
             for param in func.parameters
         ]
         return_annotation = self._annotation_to_type(func.return_type)
-        function_type = types.function_type(param_types, return_annotation or types.PRIMITIVE_TYPES["quodlibet"])
-        if not self.symbols.declare(symbols.Symbol(func.name, function_type, mutable=False, span=func.span)):
+        self.type_parameters = previous_type_parameters
+        # A default before a parameter without one can only be reached by passing that argument anyway.
+        required = max(
+            (index + 1 for index, param in enumerate(func.parameters) if param.default_value is None), default=0
+        )
+        function_type = types.function_type(
+            param_types,
+            return_annotation or types.PRIMITIVE_TYPES["quodlibet"],
+            [param.name for param in func.type_parameters],
+            required=required,
+        )
+        function_symbol = symbols.Symbol(func.name, function_type, mutable=False, span=func.span)
+        if not self._declare(function_symbol, symbols.SymbolKind.FUNCTION):
+            first = self.symbols.lookup(func.name)
+            if func.name in self.function_signatures and first is not None and first.span is not None:
+                # There is no overloading: calls keep resolving to the first definition.
+                self._error(
+                    "S112",
+                    f"Function '{func.name}' is already defined",
+                    func.span,
+                    notes=((f"'{func.name}' is first defined here", first.span),),
+                )
+                return
             self._error("S110", f"Symbol '{func.name}' already declared in this scope", func.span)
         self.function_signatures[func.name] = (param_types, return_annotation)
 
     def _analyze_function(self, func: nodes.FunctionDeclaration) -> None:
-        signature = self.function_signatures.get(func.name)
+        registered = self.symbols.lookup(func.name)
+        # A second definition of the same name is checked against its own annotations.
+        signature = self.function_signatures.get(func.name) if registered and registered.span == func.span else None
         param_types = signature[0] if signature else [
             self._annotation_to_type(param.type_annotation) or types.PRIMITIVE_TYPES["quodlibet"]
             for param in func.parameters
@@ -64,145 +404,547 @@ class SemanticAnalyzer:
         return_annotation = signature[1] if signature else self._annotation_to_type(func.return_type)
 
         previous_return = self.current_return_type
+        previous_returned_types = self.returned_types
         previous_loop_depth = self.loop_depth
+        previous_loop_labels = self.loop_labels
+        previous_flow = (self.unassigned, self.loop_exits)
+        previous_type_parameters = self.type_parameters
         self.current_return_type = return_annotation
+        self.returned_types = [] if func.return_type is None else None
         self.loop_depth = 0
+        self.loop_labels = []
+        self.unassigned, self.loop_exits = set(), []
+        self.type_parameters = [param.name for param in func.type_parameters]
+        self._check_type_parameter_usage(func)
+        if self.options.strict_params:
+            self._check_parameter_annotations(func)
 
         self.symbols.push_scope()
         for index, param in enumerate(func.parameters):
             param_type = param_types[index] if index < len(param_types) else (
                 self._annotation_to_type(param.type_annotation) or types.PRIMITIVE_TYPES["quodlibet"]
             )
-            if not self.symbols.declare(symbols.Symbol(param.name, param_type, mutable=False, span=param.span)):
+            parameter = symbols.Symbol(param.name, param_type, mutable=False, span=param.span)
+            if not self._declare(parameter, symbols.SymbolKind.PARAMETER):
                 self._error("S110", f"Parameter '{param.name}' already declared in this scope", param.span)
-        for stmt in func.body.statements:
-            self._analyze_statement(stmt)
+        self._analyze_statements(func.body.statements)
         self.symbols.pop_scope()
+        self._check_dead_stores(func.parameters, func.body)
+        if self.returned_types is not None:
+            self._record_inferred_return(func, self.returned_types)
 
         self.current_return_type = previous_return
+        self.returned_types = previous_returned_types
         self.loop_depth = previous_loop_depth
+        self.loop_labels = previous_loop_labels
+        self.unassigned, self.loop_exits = previous_flow
+        self.type_parameters = previous_type_parameters
+
+    def _record_inferred_return(self, func: nodes.FunctionDeclaration, returned: List[types.Type]) -> None:
+        # Display only: calls keep using the declared signature, whose return type is quodlibet.
+        inferred = types.unify(returned) if returned else types.PRIMITIVE_TYPES["vacuum"]
+        for index, entry in enumerate(self.symbol_index):
+            if entry.kind is symbols.SymbolKind.FUNCTION and entry.name == func.name and entry.span == func.span:
+                signature = replace(entry.type, ret=inferred)
+                self.symbol_index[index] = replace(entry, type=signature, inferred=True)
+                return
+
+    def _check_parameter_annotations(self, func: nodes.FunctionDeclaration) -> None:
+        for param in func.parameters:
+            if param.type_annotation is None:
+                self._error("T220", f"Parameter '{param.name}' of '{func.name}' needs a type annotation", param.span)
+
+    def _check_type_parameter_usage(self, func: nodes.FunctionDeclaration) -> None:
+        collector = _TypeNameCollector()
+        walk(collector, func)
+        for param in func.type_parameters:
+            if param.name not in collector.names:
+                self._warning("W110", f"Type parameter '{param.name}' of '{func.name}' is never used", param.span)
+
+    def _check_dead_stores(self, parameters: List[nodes.Parameter], body: nodes.BlockStatement) -> None:
+        for store in find_dead_stores(parameters, body):
+            name = store.target.name if isinstance(store.target, nodes.Identifier) else ""
+            self._warning("W104", f"Value assigned to '{name}' is overwritten before it is read", store.span)
 
     def _analyze_variable(self, decl: nodes.VariableDeclaration) -> None:
-        init_type = self._analyze_expression(decl.initializer) if decl.initializer else None
         annotated_type = self._annotation_to_type(decl.type_annotation)
+        if annotated_type == types.PRIMITIVE_TYPES["quodlibet"]:
+            # An explicit `quodlibet` opts this variable out of checking.
+            annotated_type = types.DYNAMIC
+        init_type = self._analyze_expression(decl.initializer, annotated_type) if decl.initializer else None
         var_type = annotated_type or init_type or types.PRIMITIVE_TYPES["quodlibet"]
 
-        if annotated_type and init_type and not annotated_type.is_assignable_from(init_type):
+        if annotated_type and init_type and not self._is_assignable(annotated_type, init_type, decl.span):
             self._error(
                 "T200",
-                f"Type mismatch: cannot initialise '{decl.name}' of type {annotated_type} with {init_type}",
+                f"Type mismatch: cannot initialise '{decl.name}' of type {annotated_type.pretty()} "
+                f"with {init_type.pretty()}{_mismatch_reason(annotated_type, init_type)}",
                 decl.span,
             )
-        if not self.symbols.declare(symbols.Symbol(decl.name, var_type, mutable=decl.mutable, span=decl.span)):
-            self._error("S110", f"Symbol '{decl.name}' already declared in this scope", decl.span)
+        kind = symbols.SymbolKind.GLOBAL if decl.is_global else symbols.SymbolKind.LOCAL
+        symbol = symbols.Symbol(decl.name, var_type, mutable=decl.mutable, span=decl.span)
+        if not self._declare(symbol, kind):
+            if decl.is_global and decl.name in self.function_signatures:
+                # Functions are registered before globals, so the function keeps the name.
+                self._error(
+                    "S111",
+                    f"Global '{decl.name}' has the same name as function '{decl.name}'; "
+                    f"references to '{decl.name}' resolve to the function",
+                    decl.span,
+                )
+            else:
+                self._error("S110", f"Symbol '{decl.name}' already declared in this scope", decl.span)
+        elif decl.initializer is None and not decl.is_global and self.unassigned is not None:
+            self.unassigned.add(id(symbol))
+
+    def _analyze_destructuring(self, decl: nodes.DestructuringDeclaration) -> None:
+        init_type = self._analyze_expression(decl.initializer) or types.PRIMITIVE_TYPES["quodlibet"]
+        quodlibet = types.PRIMITIVE_TYPES["quodlibet"]
+        if init_type.kind is types.TypeKind.TUPLE:
+            name_types = list(init_type.items or [])
+            if len(name_types) != len(decl.names):
+                self._error(
+                    "T240",
+                    f"Cannot destructure {init_type} into {len(decl.names)} names; "
+                    f"it holds {len(name_types)} values",
+                    decl.span,
+                )
+                name_types = (name_types + [quodlibet] * len(decl.names))[: len(decl.names)]
+        elif init_type.kind is types.TypeKind.ARRAY and init_type.element:
+            # The length of an array is only checked when the declaration runs.
+            name_types = [init_type.element] * len(decl.names)
+        else:
+            if init_type.kind is not types.TypeKind.QUODLIBET:
+                self._error("T240", f"Only tuples and arrays can be destructured, found {init_type}", decl.span)
+            name_types = [quodlibet] * len(decl.names)
+        for name, name_type in zip(decl.names, name_types):
+            symbol = symbols.Symbol(name, name_type, mutable=decl.mutable, span=decl.span)
+            if not self._declare(symbol, symbols.SymbolKind.LOCAL):
+                self._error("S110", f"Symbol '{name}' already declared in this scope", decl.span)
+
+    def _analyze_statements(self, statements: Sequence[nodes.Statement]) -> None:
+        # Locals are not hoisted; knowing what comes later only sharpens the S100 message.
+        self.symbols.expect(stmt.name for stmt in statements if isinstance(stmt, nodes.VariableDeclaration))
+        self.symbols.expect(
+            name for stmt in statements if isinstance(stmt, nodes.DestructuringDeclaration) for name in stmt.names
+        )
+        for stmt in statements:
+            self._analyze_statement(stmt)
 
     def _analyze_statement(self, stmt: nodes.Statement) -> None:
         if isinstance(stmt, nodes.VariableDeclaration):
             self._analyze_variable(stmt)
+        elif isinstance(stmt, nodes.DestructuringDeclaration):
+            self._analyze_destructuring(stmt)
         elif isinstance(stmt, nodes.ExpressionStatement):
             self._analyze_expression(stmt.expression)
         elif isinstance(stmt, nodes.ReturnStatement):
-            value_type = self._analyze_expression(stmt.value) if stmt.value else types.PRIMITIVE_TYPES["vacuum"]
-            if self.current_return_type and value_type and not self.current_return_type.is_assignable_from(value_type):
+            value_type = (
+                self._analyze_expression(stmt.value, self.current_return_type)
+                if stmt.value
+                else types.PRIMITIVE_TYPES["vacuum"]
+            )
+            if self.returned_types is not None and value_type:
+                self.returned_types.append(value_type)
+            if (
+                self.current_return_type
+                and value_type
+                and not self._is_assignable(self.current_return_type, value_type, stmt.span)
+            ):
                 self._error(
                     "T010",
-                    f"Return type mismatch: expected {self.current_return_type}, got {value_type}",
+                    f"Return type mismatch: expected {self.current_return_type.pretty()}, got {value_type.pretty()}"
+                    f"{_mismatch_reason(self.current_return_type, value_type)}",
                     stmt.span,
                 )
+            self.unassigned = None
         elif isinstance(stmt, nodes.BlockStatement):
             self.symbols.push_scope()
-            for inner in stmt.statements:
-                self._analyze_statement(inner)
+            self._analyze_statements(stmt.statements)
             self.symbols.pop_scope()
         elif isinstance(stmt, nodes.IfStatement):
             condition_type = self._analyze_expression(stmt.condition)
             self._expect_boolean(condition_type, stmt.condition.span, "T020", "Condition for 'si' must be booleanum")
+            before = _copy_flow(self.unassigned)
             self._analyze_statement(stmt.then_branch)
+            after_then, self.unassigned = self.unassigned, before
             if stmt.else_branch:
                 self._analyze_statement(stmt.else_branch)
+            self.unassigned = _merge_flow(after_then, self.unassigned)
         elif isinstance(stmt, nodes.WhileStatement):
             condition_type = self._analyze_expression(stmt.condition)
             self._expect_boolean(condition_type, stmt.condition.span, "T021", "Condition for 'dum' must be booleanum")
-            self.loop_depth += 1
-            self._analyze_statement(stmt.body)
-            self.loop_depth -= 1
+            if _constant_boolean(stmt.condition) is True and not _can_leave_loop(stmt.body):
+                self._warning("W103", "Loop 'dum' never ends: no 'frange' or 'redde' in its body", stmt.span)
+            self._analyze_loop_body(stmt)
         elif isinstance(stmt, nodes.ForStatement):
             iterable_type = self._analyze_expression(stmt.iterable)
             element_type = self._iterable_element_type(iterable_type, stmt.iterable.span)
             target_annotation = self._annotation_to_type(stmt.target.type_annotation)
             target_type = target_annotation or element_type
-            if target_annotation and not target_annotation.is_assignable_from(element_type):
+            if target_annotation and not self._is_assignable(target_annotation, element_type, stmt.target.span):
                 self._error(
                     "T031",
                     f"Loop variable '{stmt.target.name}' expects {target_annotation}, got {element_type}",
                     stmt.target.span,
                 )
             self.symbols.push_scope()
-            if not self.symbols.declare(
-                symbols.Symbol(stmt.target.name, target_type, mutable=stmt.target.mutable, span=stmt.target.span)
+            if not self._declare(
+                symbols.Symbol(stmt.target.name, target_type, mutable=stmt.target.mutable, span=stmt.target.span),
+                symbols.SymbolKind.LOCAL,
             ):
                 self._error("S110", f"Symbol '{stmt.target.name}' already declared in this scope", stmt.target.span)
-            self.loop_depth += 1
-            self._analyze_statement(stmt.body)
-            self.loop_depth -= 1
+            self._analyze_loop_body(stmt)
+            self.symbols.pop_scope()
+        elif isinstance(stmt, nodes.CStyleForStatement):
+            self.symbols.push_scope()
+            if stmt.init is not None:
+                self._analyze_statement(stmt.init)
+            if stmt.condition is not None:
+                condition_type = self._analyze_expression(stmt.condition)
+                self._expect_boolean(
+                    condition_type, stmt.condition.span, "T022", "Condition for a C-style 'pro' must be booleanum"
+                )
+            endless = stmt.condition is None or _constant_boolean(stmt.condition) is True
+            if endless and not _can_leave_loop(stmt.body):
+                self._warning("W103", "Loop 'pro' never ends: no 'frange' or 'redde' in its body", stmt.span)
+            self._analyze_loop_body(stmt)
             self.symbols.pop_scope()
         elif isinstance(stmt, nodes.BreakStatement):
             if self.loop_depth == 0:
                 self._error("T040", "'frange' can only be used inside loops", stmt.span)
+            else:
+                self._check_jump_label(stmt.label, stmt.span)
+                self._record_loop_exit(stmt.label)
+            self.unassigned = None
         elif isinstance(stmt, nodes.ContinueStatement):
             if self.loop_depth == 0:
                 self._error("T041", "'perge' can only be used inside loops", stmt.span)
+            else:
+                self._check_jump_label(stmt.label, stmt.span)
+                self._record_loop_exit(stmt.label, continuing=True)
+            self.unassigned = None
+
+    def _analyze_loop_body(
+        self, loop: nodes.WhileStatement | nodes.ForStatement | nodes.CStyleForStatement
+    ) -> None:
+        if loop.label is not None and loop.label in self.loop_labels:
+            self._error("S141", f"Label '{loop.label}' is already used by an enclosing loop", loop.span)
+        if loop.label is not None:
+            self.loop_labels.append(loop.label)
+        self.loop_depth += 1
+        before = _copy_flow(self.unassigned)
+        self.loop_exits.append((loop.label, [], []))
+        self._analyze_statement(loop.body)
+        _, exits, continues = self.loop_exits.pop()
+        if isinstance(loop, nodes.CStyleForStatement) and loop.update is not None:
+            # The update runs after the body falls through or meets `perge`; what it assigns is not
+            # known to be assigned after the loop, which may end before running it once.
+            self.unassigned = _merge_flow(self.unassigned, *continues)
+            self._analyze_expression(loop.update)
+        # The body may run zero times, so only its `frange` exits can add assignments, and only when
+        # the loop cannot end through its condition (`dum (verum)`).
+        endless = isinstance(loop, (nodes.WhileStatement, nodes.CStyleForStatement)) and (
+            loop.condition is None or _constant_boolean(loop.condition) is True
+        )
+        self.unassigned = _merge_flow(None if endless else before, *exits)
+        self.loop_depth -= 1
+        if loop.label is not None:
+            self.loop_labels.pop()
+
+    def _record_loop_exit(self, label: Optional[str], continuing: bool = False) -> None:
+        for loop_label, exits, continues in reversed(self.loop_exits):
+            if label is None or loop_label == label:
+                (continues if continuing else exits).append(_copy_flow(self.unassigned))
+                return
+
+    def _check_jump_label(self, label: Optional[str], span: Span) -> None:
+        if label is not None and label not in self.loop_labels:
+            self._error("S140", f"No enclosing loop is labelled '{label}'", span)
+
+    def _analyze_expression(
+        self,
+        expr: Optional[nodes.Expression],
+        expected: Optional[types.Type] = None,
+    ) -> Optional[types.Type]:
+        """
+        Check *expr* and return its type.
+
+        *expected* is the type the context requires, when known. It only guides
+        literals that cannot be typed from their elements, such as `[]`; the
+        caller still checks the result against it.
+        """
 
-    def _analyze_expression(self, expr: Optional[nodes.Expression]) -> Optional[types.Type]:
         if expr is None:
             return None
+        expr_type = self._infer_expression(expr, expected)
+        if expr_type is not None:
+            self.expression_types[expr.node_id] = expr_type
+        return expr_type
+
+    def _infer_expression(self, expr: nodes.Expression, expected: Optional[types.Type]) -> Optional[types.Type]:
         if isinstance(expr, nodes.Literal):
+            self._check_numeric_precision(expr)
             return types.type_from_literal(expr.value, expr.raw)
         if isinstance(expr, nodes.Identifier):
             symbol = self.symbols.lookup(expr.name)
+            if symbol is None and expr.name == "ipse":
+                self._error(
+                    "S160",
+                    "'ipse' is only available inside a method, a 'functio' written as a property of a 'structura'",
+                    expr.span,
+                )
+                return types.UNKNOWN
             if symbol is None:
-                self._error("S100", f"Undeclared identifier '{expr.name}'", expr.span)
-                return types.PRIMITIVE_TYPES["quodlibet"]
+                self._error("S100", self.symbols.undeclared_message(expr.name), expr.span)
+                return types.UNKNOWN
+            self._check_assigned(symbol, expr.span)
             return symbol.type
         if isinstance(expr, nodes.UnaryExpression):
             return self._analyze_unary(expr)
         if isinstance(expr, nodes.AssignmentExpression):
             return self._analyze_assignment(expr)
+        if isinstance(expr, nodes.UpdateExpression):
+            return self._analyze_update(expr)
+        if isinstance(expr, nodes.CastExpression):
+            return self._analyze_cast(expr)
+        if isinstance(expr, nodes.RangeExpression):
+            for bound in (expr.start, expr.end):
+                bound_type = self._analyze_expression(bound)
+                if bound_type and bound_type.kind not in {types.TypeKind.NUMERUS, types.TypeKind.QUODLIBET}:
+                    self._error("T103", "Range bounds must be numerus", bound.span)
+            return types.Type(types.TypeKind.RANGE)
         if isinstance(expr, nodes.BinaryExpression):
             return self._analyze_binary(expr)
-        if isinstance(expr, nodes.CallExpression):
-            return self._analyze_call(expr)
-        if isinstance(expr, nodes.MemberExpression):
-            self._analyze_expression(expr.object)
-            return types.PRIMITIVE_TYPES["quodlibet"]
-        if isinstance(expr, nodes.IndexExpression):
-            collection_type = self._analyze_expression(expr.collection)
-            self._analyze_expression(expr.index)
-            if collection_type and collection_type.kind is types.TypeKind.ARRAY and collection_type.element:
-                return collection_type.element
-            return types.PRIMITIVE_TYPES["quodlibet"]
+        if isinstance(expr, _CHAIN_LINKS):
+            link_type = self._analyze_link(expr)
+            # Past a `?.` or `?[` that met nullum the rest of the chain is skipped, so the chain may be nullum.
+            if is_optional_chain(expr) and link_type.kind not in _NEVER_OPTIONAL_KINDS:
+                return link_type.with_optional()
+            return link_type
         if isinstance(expr, nodes.ConditionalExpression):
             condition_type = self._analyze_expression(expr.condition)
             self._expect_boolean(condition_type, expr.condition.span, "T130", "Condition for '?:' must be booleanum")
-            consequent = self._analyze_expression(expr.consequent)
-            alternate = self._analyze_expression(expr.alternate)
+            before = _copy_flow(self.unassigned)
+            consequent = self._analyze_expression(expr.consequent, expected)
+            after_consequent, self.unassigned = self.unassigned, before
+            alternate = self._analyze_expression(expr.alternate, expected)
+            self.unassigned = _merge_flow(after_consequent, self.unassigned)
             filtered = [t for t in (consequent, alternate) if t]
             return types.least_restrictive(filtered) if filtered else types.PRIMITIVE_TYPES["quodlibet"]
         if isinstance(expr, nodes.ArrayLiteral):
-            element_types = [self._analyze_expression(element) for element in expr.elements]
+            expected_array = _expected_shape(expected, types.TypeKind.ARRAY)
+            expected_element = expected_array.element if expected_array else None
+            element_types = [self._analyze_expression(element, expected_element) for element in expr.elements]
+            for element, element_type in zip(expr.elements, element_types):
+                if element_type and element_type.kind is types.TypeKind.VACUUM:
+                    self._error("T230", "Array element has type vacuum, so it holds no value", element.span)
             filtered = [t for t in element_types if t]
+            if not filtered and expected_array is not None:
+                # `[]` has no element to infer from, so it takes the element type the context expects.
+                return expected_array
             element_type = types.least_restrictive(filtered) if filtered else types.PRIMITIVE_TYPES["quodlibet"]
+            if self.options.strict_any and expected_element is None:
+                # A nested `[]` has no elements to disagree with its neighbours.
+                typed = [
+                    item
+                    for element, item in zip(expr.elements, element_types)
+                    if item and not (isinstance(element, nodes.ArrayLiteral) and not element.elements)
+                ]
+                self._check_mixed_elements(expr, typed, types.least_restrictive(typed) if typed else element_type)
             return types.Type(types.TypeKind.ARRAY, element=element_type)
+        if isinstance(expr, nodes.TupleLiteral):
+            quodlibet = types.PRIMITIVE_TYPES["quodlibet"]
+            expected_tuple = _expected_shape(expected, types.TypeKind.TUPLE)
+            expected_items: List[Optional[types.Type]] = list(expected_tuple.items or []) if expected_tuple else []
+            expected_items += [None] * (len(expr.elements) - len(expected_items))
+            items = [
+                self._analyze_expression(element, item) or quodlibet
+                for element, item in zip(expr.elements, expected_items)
+            ]
+            for element, item in zip(expr.elements, items):
+                if item.kind is types.TypeKind.VACUUM:
+                    self._error("T230", "Tuple element has type vacuum, so it holds no value", element.span)
+            return types.tuple_type(items)
         if isinstance(expr, nodes.ObjectLiteral):
-            value_types = {
-                prop.key: self._analyze_expression(prop.value) or types.PRIMITIVE_TYPES["quodlibet"]
-                for prop in expr.properties
-            }
-            return types.Type(types.TypeKind.OBJECT, fields=value_types)
+            return self._analyze_object_literal(expr, expected)
         if isinstance(expr, nodes.LambdaExpression):
+            self._analyze_lambda(expr)
+            return types.PRIMITIVE_TYPES["quodlibet"]
+        return types.PRIMITIVE_TYPES["quodlibet"]
+
+    def _check_mixed_elements(
+        self,
+        expr: nodes.ArrayLiteral,
+        element_types: List[types.Type],
+        element_type: types.Type,
+    ) -> None:
+        """Under `strict_any`, an array whose elements only share quodlibet is an error, not a silent widening."""
+
+        if element_type.kind is not types.TypeKind.QUODLIBET:
+            return
+        if any(item.kind is types.TypeKind.QUODLIBET for item in element_types):
+            return
+        distinct: List[str] = []
+        for item in element_types:
+            if str(item) not in distinct:
+                distinct.append(str(item))
+        if len(distinct) < 2:
+            # `[]`, or elements that print alike, leave nothing to report as a conflict.
+            return
+        listed = ", ".join(distinct[:-1]) + f" and {distinct[-1]}"
+        self._error(
+            "T212",
+            f"Array elements have incompatible types {listed}; annotate the array or cast elements with 'ut'",
+            expr.span,
+        )
+
+    def _analyze_object_literal(self, expr: nodes.ObjectLiteral, expected: Optional[types.Type]) -> types.Type:
+        quodlibet = types.PRIMITIVE_TYPES["quodlibet"]
+        expected_object = _expected_shape(expected, types.TypeKind.OBJECT)
+        expected_fields = (expected_object.fields or {}) if expected_object else {}
+        value_types: Dict[str, types.Type] = {}
+        for prop in expr.properties:
+            if isinstance(prop.value, nodes.LambdaExpression):
+                # Lambdas are typed quodlibet; their bodies wait until the type `ipse` takes is known.
+                value_types[prop.key] = quodlibet
+            else:
+                value_types[prop.key] = self._analyze_expression(prop.value, expected_fields.get(prop.key)) or quodlibet
+        object_type = types.Type(types.TypeKind.OBJECT, fields=value_types)
+        for prop in expr.properties:
+            if isinstance(prop.value, nodes.LambdaExpression):
+                self.symbols.push_scope()
+                self.symbols.declare(symbols.Symbol("ipse", object_type, mutable=False, span=prop.value.span))
+                self._analyze_expression(prop.value, expected_fields.get(prop.key))
+                self.symbols.pop_scope()
+        return object_type
+
+    def _analyze_link(self, expr: nodes.Expression) -> types.Type:
+        """Type of one call, member or index link, before a `?.` or `?[` below it makes the chain optional."""
+
+        if isinstance(expr, nodes.CallExpression):
+            return self._analyze_call(expr)
+        if isinstance(expr, nodes.MemberExpression):
+            object_type = self._analyze_chain_base(expr.object)
+            if object_type in (types.UNKNOWN, types.DYNAMIC):
+                return object_type
+            if expr.optional and object_type is not None:
+                object_type = object_type.unwrap_optional()
+            if object_type and object_type.kind is types.TypeKind.OBJECT and object_type.fields:
+                return object_type.fields.get(expr.property, types.PRIMITIVE_TYPES["quodlibet"])
+            self._check_has_members(object_type, expr)
             return types.PRIMITIVE_TYPES["quodlibet"]
+        collection_type = self._analyze_chain_base(expr.collection)
+        index_type = self._analyze_expression(expr.index)
+        if collection_type in (types.UNKNOWN, types.DYNAMIC):
+            return collection_type
+        if expr.optional and collection_type is not None:
+            collection_type = collection_type.unwrap_optional()
+        if collection_type and collection_type.kind is types.TypeKind.TEXTUS:
+            self._check_text_index(index_type, expr.index)
+            return types.PRIMITIVE_TYPES["textus"]
+        sequence = collection_type and collection_type.kind in (types.TypeKind.ARRAY, types.TypeKind.TUPLE)
+        if sequence and index_type and index_type.kind is types.TypeKind.RANGE:
+            self._error("T242", f"Only a textus can be indexed by a range, not {collection_type}", expr.index.span)
+            return types.UNKNOWN
+        if collection_type and collection_type.kind is types.TypeKind.ARRAY and collection_type.element:
+            return collection_type.element
+        if collection_type and collection_type.kind is types.TypeKind.TUPLE and collection_type.items:
+            return self._tuple_item_type(collection_type, expr.index)
         return types.PRIMITIVE_TYPES["quodlibet"]
 
+    def _check_text_index(self, index_type: Optional[types.Type], index: nodes.Expression) -> None:
+        """`texto[i]` reads one character and `texto[a..b]` a substring, both as textus."""
+
+        allowed = {types.TypeKind.NUMERUS, types.TypeKind.RANGE, types.TypeKind.QUODLIBET}
+        if index_type is None or index_type == types.UNKNOWN or index_type.kind in allowed:
+            return
+        self._error("T241", f"A textus is indexed by numerus or a range, not {index_type}", index.span)
+
+    def _tuple_item_type(self, tuple_type: types.Type, index: nodes.Expression) -> types.Type:
+        """The item a constant index selects; any item's type, joined, when the index is not constant."""
+
+        items = tuple_type.items or []
+        position = _constant_number(index)
+        if position is None or not (_is_whole(position) or not math.isfinite(position)):
+            return types.least_restrictive(items)
+        if not (_is_whole(position) and 0 <= position < len(items)):
+            self._error("T240", f"Index {_number_text(position)} is out of range for tuple {tuple_type}", index.span)
+            return types.PRIMITIVE_TYPES["quodlibet"]
+        return items[int(position)]
+
+    def _analyze_chain_base(self, expr: nodes.Expression) -> Optional[types.Type]:
+        # Inside a chain the optionality added by `?.` is only applied once, at its end.
+        if isinstance(expr, _CHAIN_LINKS):
+            link_type = self._analyze_link(expr)
+            self.expression_types[expr.node_id] = link_type
+            return link_type
+        return self._analyze_expression(expr)
+
+    def _analyze_lambda(self, expr: nodes.LambdaExpression) -> None:
+        # `redde` inside the body belongs to the lambda, not to the enclosing function.
+        previous_return = self.current_return_type
+        previous_returned_types = self.returned_types
+        previous_loop_depth = self.loop_depth
+        previous_loop_labels = self.loop_labels
+        # Reads in the body are checked against what is assigned where the lambda is created;
+        # assignments in the body do not count outside it, since it may never be called.
+        previous_flow = (self.unassigned, self.loop_exits)
+        self.current_return_type = self._annotation_to_type(expr.return_type)
+        self.returned_types = None
+        self.loop_depth = 0
+        self.loop_labels = []
+        self.unassigned, self.loop_exits = _copy_flow(self.unassigned), []
+
+        self.symbols.push_scope()
+        for param in expr.parameters:
+            param_type = self._annotation_to_type(param.type_annotation) or types.PRIMITIVE_TYPES["quodlibet"]
+            parameter = symbols.Symbol(param.name, param_type, mutable=False, span=param.span)
+            if not self._declare(parameter, symbols.SymbolKind.PARAMETER):
+                self._error("S110", f"Parameter '{param.name}' already declared in this scope", param.span)
+        if isinstance(expr.body, nodes.BlockStatement):
+            self._analyze_statements(expr.body.statements)
+            self._check_dead_stores(expr.parameters, expr.body)
+        else:
+            body_type = self._analyze_expression(expr.body)
+            if (
+                self.current_return_type
+                and body_type
+                and not self._is_assignable(self.current_return_type, body_type, expr.body.span)
+            ):
+                self._error(
+                    "T010",
+                    f"Return type mismatch: expected {self.current_return_type.pretty()}, got {body_type.pretty()}"
+                    f"{_mismatch_reason(self.current_return_type, body_type)}",
+                    expr.body.span,
+                )
+        self.symbols.pop_scope()
+
+        self.current_return_type = previous_return
+        self.returned_types = previous_returned_types
+        self.loop_depth = previous_loop_depth
+        self.loop_labels = previous_loop_labels
+        self.unassigned, self.loop_exits = previous_flow
+
+    def _check_numeric_precision(self, literal: nodes.Literal) -> None:
+        value = literal.value
+        if isinstance(value, bool) or not isinstance(value, int):
+            return
+        if abs(value) <= MAX_SAFE_INTEGER:
+            return
+        try:
+            nearest = str(int(float(value)))
+        except OverflowError:
+            # Beyond the largest double: the literal reads as infinitum.
+            nearest = "infinitum"
+        if nearest == str(value):
+            return
+        self._warning(
+            "W100",
+            f"Integer literal {literal.raw} cannot be represented exactly as numerus (nearest value is {nearest})",
+            literal.span,
+        )
+
     def _analyze_unary(self, expr: nodes.UnaryExpression) -> types.Type:
         operand_type = self._analyze_expression(expr.operand)
         if expr.operator is nodes.UnaryOperator.NOT:
@@ -212,29 +954,159 @@ class SemanticAnalyzer:
             if operand_type and operand_type.kind not in {types.TypeKind.NUMERUS, types.TypeKind.QUODLIBET}:
                 self._error("T100", "Unary arithmetic operands must be numerus", expr.span)
             return types.PRIMITIVE_TYPES["numerus"]
+        if expr.operator is nodes.UnaryOperator.BIT_NOT:
+            if operand_type and operand_type.kind not in {types.TypeKind.NUMERUS, types.TypeKind.QUODLIBET}:
+                self._error("T100", "Bitwise negation requires numerus", expr.span)
+            return types.PRIMITIVE_TYPES["numerus"]
         return operand_type or types.PRIMITIVE_TYPES["quodlibet"]
 
     def _analyze_assignment(self, expr: nodes.AssignmentExpression) -> types.Type:
+        symbol: Optional[symbols.Symbol] = None
         if isinstance(expr.target, nodes.Identifier):
             symbol = self.symbols.lookup(expr.target.name)
             if symbol is None:
-                self._error("S100", f"Undeclared identifier '{expr.target.name}'", expr.target.span)
+                self._error("S100", self.symbols.undeclared_message(expr.target.name), expr.target.span)
                 target_type: Optional[types.Type] = types.PRIMITIVE_TYPES["quodlibet"]
             else:
                 target_type = symbol.type
                 if not symbol.mutable:
                     self._error("S120", f"Cannot assign to immutable symbol '{expr.target.name}'", expr.span)
+        elif isinstance(expr.target, nodes.MemberExpression):
+            target_type = self._member_assignment_type(expr.target)
         else:
             target_type = self._analyze_expression(expr.target)
-        value_type = self._analyze_expression(expr.value)
-        if target_type and value_type and not target_type.is_assignable_from(value_type):
-            self._error("T200", f"Type mismatch: cannot assign {value_type} to {target_type}", expr.span)
+        value_type = self._analyze_expression(expr.value, target_type)
+        if symbol is not None and self.unassigned is not None:
+            self.unassigned.discard(id(symbol))
+        if target_type and value_type and not self._is_assignable(target_type, value_type, expr.span):
+            reason = _mismatch_reason(target_type, value_type)
+            message = f"Type mismatch: cannot assign {value_type.pretty()} to {target_type.pretty()}{reason}"
+            self._error("T200", message, expr.span)
         return target_type or value_type or types.PRIMITIVE_TYPES["quodlibet"]
 
+    def _member_assignment_type(self, target: nodes.MemberExpression) -> types.Type:
+        object_type = self._analyze_expression(target.object)
+        if isinstance(target.object, nodes.Identifier):
+            symbol = self.symbols.lookup(target.object.name)
+            if symbol is not None and id(symbol) in self.namespaces:
+                message = f"Cannot assign to '{target.object.name}.{target.property}' of an imported module"
+                self._error("S120", message, target.span)
+        if not (object_type and object_type.kind is types.TypeKind.OBJECT and object_type.fields):
+            self._check_has_members(object_type, target)
+            return types.PRIMITIVE_TYPES["quodlibet"]
+        field_type = object_type.fields.get(target.property)
+        if field_type is None:
+            self._error("T201", f"Object type {object_type} has no field '{target.property}'", target.span)
+            return types.PRIMITIVE_TYPES["quodlibet"]
+        return field_type
+
+    def _check_assigned(self, symbol: symbols.Symbol, span: Optional[object]) -> None:
+        if self.unassigned is None or id(symbol) not in self.unassigned:
+            return
+        self._error("S150", f"Variable '{symbol.name}' may be read before it is assigned", span)
+        # Report each variable once; later reads would repeat the same diagnostic.
+        self.unassigned.discard(id(symbol))
+
+    def _check_has_members(self, object_type: Optional[types.Type], expr: nodes.MemberExpression) -> None:
+        if object_type is None or object_type.kind not in _MEMBERLESS_KINDS:
+            return
+        message = f"Type {object_type} has no members, so '.{expr.property}' does not exist"
+        if object_type.kind is types.TypeKind.TEXTUS:
+            message += "; read a character with [indice]"
+        elif object_type.kind is types.TypeKind.ARRAY:
+            message += "; read an element with [indice] or visit them with 'pro'"
+        self._error("T202", message, expr.span)
+
+    def _analyze_cast(self, expr: nodes.CastExpression) -> types.Type:
+        value_type = self._analyze_expression(expr.expression)
+        target_type = self._annotation_to_type(expr.target_type)
+        if target_type is None:
+            return types.PRIMITIVE_TYPES["quodlibet"]
+        if value_type and types.TypeKind.QUODLIBET not in {value_type.kind, target_type.kind}:
+            if not (target_type.is_assignable_from(value_type) or value_type.is_assignable_from(target_type)):
+                self._error("T211", f"Cannot cast {value_type} to {target_type}", expr.span)
+        return target_type
+
+    def _analyze_update(self, expr: nodes.UpdateExpression) -> types.Type:
+        symbol = self.symbols.lookup(expr.operand.name)
+        if symbol is None:
+            self._error("S100", self.symbols.undeclared_message(expr.operand.name), expr.operand.span)
+            return types.PRIMITIVE_TYPES["numerus"]
+        self._check_assigned(symbol, expr.operand.span)
+        if not symbol.mutable:
+            self._error("S120", f"Cannot assign to immutable symbol '{expr.operand.name}'", expr.span)
+        if symbol.type.kind not in {types.TypeKind.NUMERUS, types.TypeKind.QUODLIBET}:
+            self._error("T100", f"Operand of '{expr.operator.value}' must be numerus", expr.span)
+        return types.PRIMITIVE_TYPES["numerus"]
+
+    def _check_shift_amount(self, amount: nodes.Expression) -> None:
+        value = _constant_number(amount)
+        if value is None or (value >= 0 and _is_whole(value)):
+            return
+        self._warning("W102", f"Shift amount {_number_text(value)} is not a non-negative integer", amount.span)
+
+    def _check_constant_overflow(self, expr: nodes.BinaryExpression) -> None:
+        """W105 when `**`, `<<` or `>>` on two literals gives a value other than the exact result."""
+
+        left, right = _constant_number(expr.left), _constant_number(expr.right)
+        if left is None or right is None or not (_is_finite(left) and _is_finite(right)):
+            return
+        symbol = {nodes.BinaryOperator.POW: "**", nodes.BinaryOperator.SHL: "<<"}.get(expr.operator, ">>")
+        base = _number_text(left)
+        if expr.operator is nodes.BinaryOperator.POW and left < 0:
+            base = f"({base})"
+        text = f"{base} {symbol} {_number_text(right)}"
+        if expr.operator is nodes.BinaryOperator.POW:
+            try:
+                result = math.pow(left, right)
+            except OverflowError:
+                result = math.inf
+            except ValueError:
+                # `0 ** -1` and fractional powers of negative numbers: not an overflow.
+                return
+            if math.isinf(result):
+                message = f"Constant {text} overflows numerus, whose largest finite value is about 1.8e308"
+                self._warning("W105", message, expr.span)
+            elif isinstance(left, int) and isinstance(right, int) and right >= 0 and abs(result) > MAX_SAFE_INTEGER:
+                exact = left**right
+                if int(result) != exact:
+                    self._warning(
+                        "W105",
+                        f"Constant {text} = {exact} cannot be represented exactly as numerus "
+                        f"(nearest value is {int(result)})",
+                        expr.span,
+                    )
+            return
+        if right < 0 or right != int(right) or left != int(left):
+            # W102 covers the amount; a fractional operand is truncated on purpose.
+            return
+        amount, value = int(right), int(left)
+        if expr.operator is nodes.BinaryOperator.SHL:
+            actual = _int32(_int32(value) << (amount & 31))
+            exact = value << amount if amount < 64 else None
+        else:
+            actual = _int32(value) >> (amount & 31)
+            exact = value >> amount
+        if actual == exact or (exact is None and value == 0):
+            return
+        if amount >= 32:
+            message = f"Shift amount of constant {text} wraps to {amount & 31}; the result is {actual}"
+        else:
+            message = f"Constant {text} does not fit in 32 bits; the result is {actual}, not {exact}"
+        self._warning("W105", message, expr.span)
+
     def _analyze_binary(self, expr: nodes.BinaryExpression) -> types.Type:
         left = self._analyze_expression(expr.left)
-        right = self._analyze_expression(expr.right)
         op = expr.operator
+        if op in _SHORT_CIRCUIT_OPERATORS:
+            # The right side may not run, so its assignments do not count afterwards.
+            before = _copy_flow(self.unassigned)
+            # `talvez ?? []` falls back to a value of the type `talvez` holds.
+            fallback = left.element if op is nodes.BinaryOperator.NULLISH and left and left.is_optional() else None
+            right = self._analyze_expression(expr.right, fallback)
+            self.unassigned = before
+        else:
+            right = self._analyze_expression(expr.right)
 
         arithmetic_ops = {
             nodes.BinaryOperator.ADD,
@@ -243,7 +1115,13 @@ class SemanticAnalyzer:
             nodes.BinaryOperator.DIV,
             nodes.BinaryOperator.MOD,
             nodes.BinaryOperator.POW,
+            nodes.BinaryOperator.SHL,
+            nodes.BinaryOperator.SHR,
         }
+        if op in {nodes.BinaryOperator.SHL, nodes.BinaryOperator.SHR}:
+            self._check_shift_amount(expr.right)
+        if op in {nodes.BinaryOperator.POW, nodes.BinaryOperator.SHL, nodes.BinaryOperator.SHR}:
+            self._check_constant_overflow(expr)
         if op in arithmetic_ops:
             if (left and left.kind not in {types.TypeKind.NUMERUS, types.TypeKind.QUODLIBET}) or (
                 right and right.kind not in {types.TypeKind.NUMERUS, types.TypeKind.QUODLIBET}
@@ -270,6 +1148,12 @@ class SemanticAnalyzer:
             nodes.BinaryOperator.STRICT_EQ,
             nodes.BinaryOperator.STRICT_NE,
         }:
+            if left and right and _never_equal(left, right):
+                outcome = "verum" if op in {nodes.BinaryOperator.NE, nodes.BinaryOperator.STRICT_NE} else "falsum"
+                if op in {nodes.BinaryOperator.STRICT_EQ, nodes.BinaryOperator.STRICT_NE}:
+                    self._error("T104", f"Strict comparison of {left} and {right} is always {outcome}", expr.span)
+                else:
+                    self._warning("W101", f"Comparison of {left} and {right} is always {outcome}", expr.span)
             return types.PRIMITIVE_TYPES["booleanum"]
 
         if op in {nodes.BinaryOperator.AND, nodes.BinaryOperator.OR}:
@@ -316,29 +1200,61 @@ class SemanticAnalyzer:
         return types.least_restrictive(result_candidates) if result_candidates else types.PRIMITIVE_TYPES["quodlibet"]
 
     def _analyze_call(self, expr: nodes.CallExpression) -> types.Type:
-        callee_type = self._analyze_expression(expr.callee)
-        argument_types = [self._analyze_expression(argument) for argument in expr.arguments]
+        callee_type = self._analyze_chain_base(expr.callee)
+        # Generic parameters are only known after unification, so they guide no argument.
+        expected_arguments: List[Optional[types.Type]] = []
+        if callee_type and callee_type.kind is types.TypeKind.FUNCTION and not callee_type.type_params:
+            expected_arguments = list(callee_type.params or [])
+        rest = callee_type.rest if callee_type and callee_type.kind is types.TypeKind.FUNCTION else None
+        expected_arguments += [rest] * (len(expr.arguments) - len(expected_arguments))
+        argument_types = [
+            self._analyze_expression(argument, expected_argument)
+            for argument, expected_argument in zip(expr.arguments, expected_arguments)
+        ]
         if callee_type and callee_type.kind is types.TypeKind.FUNCTION:
-            param_types = callee_type.params or []
-            if len(param_types) != len(argument_types):
-                self._error(
-                    "T300",
-                    f"Expected {len(param_types)} arguments, got {len(argument_types)}",
-                    expr.span,
-                )
+            callee_type = self._instantiate(callee_type, expr, argument_types)
+            param_types = list(callee_type.params or [])
+            if callee_type.rest is not None:
+                # Variadic: any number of further arguments, each checked against the rest type.
+                param_types += [callee_type.rest] * (len(argument_types) - len(param_types))
+                if len(argument_types) < callee_type.min_arity:
+                    self._error(
+                        "T300",
+                        f"Expected at least {callee_type.min_arity} arguments, got {len(argument_types)}",
+                        expr.span,
+                    )
+            elif not callee_type.min_arity <= len(argument_types) <= len(param_types):
+                expected = str(len(param_types))
+                if callee_type.min_arity != len(param_types):
+                    expected = f"{callee_type.min_arity} to {expected}"
+                self._error("T300", f"Expected {expected} arguments, got {len(argument_types)}", expr.span)
             for index, (param_type, arg_type, arg_expr) in enumerate(
                 zip_longest(param_types, argument_types, expr.arguments, fillvalue=None),
                 start=1,
             ):
                 if param_type is None or arg_type is None:
                     continue
-                if not param_type.is_assignable_from(arg_type):
+                if expr.trailing_lambda and index == len(expr.arguments) and param_type.kind not in {
+                    types.TypeKind.FUNCTION,
+                    types.TypeKind.QUODLIBET,
+                }:
+                    self._error(
+                        "T304",
+                        f"Trailing lambda passed to parameter {index}, which expects {param_type}",
+                        arg_expr.span,
+                    )
+                    continue
+                if not self._is_assignable(param_type, arg_type, arg_expr.span):
                     self._error(
                         "T301",
-                        f"Argument {index} type mismatch: expected {param_type}, got {arg_type}",
+                        f"Argument {index} type mismatch: expected {param_type.pretty()}, got {arg_type.pretty()}"
+                        f"{_mismatch_reason(param_type, arg_type)}",
                         arg_expr.span,
                     )
             return callee_type.ret or types.PRIMITIVE_TYPES["quodlibet"]
+        if callee_type and callee_type.kind is types.TypeKind.QUODLIBET:
+            # A dynamic value may hold a closure; the interpreter rejects it at call time if not.
+            return callee_type
 
         if callee_type is not None:
             self._error("T302", "Expression is not callable", expr.callee.span)
@@ -346,10 +1262,50 @@ class SemanticAnalyzer:
             self._error("T302", "Expression is not callable", expr.span)
         return types.PRIMITIVE_TYPES["quodlibet"]
 
+    def _instantiate(
+        self,
+        callee_type: types.Type,
+        expr: nodes.CallExpression,
+        argument_types: List[Optional[types.Type]],
+    ) -> types.Type:
+        type_params = callee_type.type_params or []
+        if expr.type_arguments and len(expr.type_arguments) != len(type_params):
+            self._error(
+                "T303",
+                f"Expected {len(type_params)} type arguments, got {len(expr.type_arguments)}",
+                expr.span,
+            )
+        if not type_params:
+            return callee_type
+        if expr.type_arguments:
+            explicit = [
+                self._annotation_to_type(argument) or types.PRIMITIVE_TYPES["quodlibet"]
+                for argument in expr.type_arguments
+            ]
+            bindings = dict(zip(type_params, explicit))
+        else:
+            bindings = types.infer_type_arguments(callee_type.params or [], argument_types)
+        for name in type_params:
+            bindings.setdefault(name, types.PRIMITIVE_TYPES["quodlibet"])
+        return types.substitute(callee_type, bindings)
+
     def _annotation_to_type(self, annotation: Optional[nodes.TypeAnnotation]) -> Optional[types.Type]:
         if annotation is None:
             return None
-        return types.type_from_annotation(annotation.name)
+        key = (annotation.name.strip(), tuple(self.type_parameters))
+        if key not in self._annotation_cache:
+            self._annotation_cache[key] = types.type_from_annotation(*key)
+        resolved = self._annotation_cache[key]
+        if id(annotation) not in self._checked_annotations:
+            self._checked_annotations.add(id(annotation))
+            vacuum_array = _vacuum_array(resolved)
+            if vacuum_array is not None:
+                self._error(
+                    "T230",
+                    f"Array type {vacuum_array} cannot hold values; vacuum is only valid as a return type",
+                    annotation.span,
+                )
+        return resolved
 
     def _expect_boolean(self, type_obj: Optional[types.Type], span: Optional[object], code: str, message: str) -> None:
         if type_obj is None:
@@ -362,10 +1318,47 @@ class SemanticAnalyzer:
             return types.PRIMITIVE_TYPES["quodlibet"]
         if iterable_type.kind is types.TypeKind.ARRAY and iterable_type.element:
             return iterable_type.element
+        if iterable_type.kind is types.TypeKind.RANGE:
+            return types.PRIMITIVE_TYPES["numerus"]
         if iterable_type.kind is types.TypeKind.QUODLIBET:
-            return types.PRIMITIVE_TYPES["quodlibet"]
+            # Keeps `types.UNKNOWN` when the iterable's own error was already reported.
+            return iterable_type
+        if iterable_type.is_optional() and iterable_type.element and iterable_type.element.kind is types.TypeKind.ARRAY:
+            self._error(
+                "T032",
+                f"Expression in 'pro' may be nullum (type {iterable_type}); "
+                "coalesce it with '??' or cast it with 'ut' before iterating",
+                span,
+            )
+            # Check the body against the elements it would see once unwrapped.
+            return self._iterable_element_type(iterable_type.element, span)
         self._error("T030", "Expression in 'pro' must be iterable", span)
-        return types.PRIMITIVE_TYPES["quodlibet"]
+        return types.UNKNOWN
+
+    def _is_assignable(self, target: types.Type, value: types.Type, span: Optional[object]) -> bool:
+        """
+        `Type.is_assignable_from`, plus the `strict_any` rule.
+
+        A strict-mode violation is reported here as T210, and True is returned so
+        the caller does not add its own mismatch on top. `types.UNKNOWN` fits
+        anywhere: its error has been reported already. So does `types.DYNAMIC`,
+        which the user asked for by annotating a variable `quodlibet`.
+        """
+
+        if types.UNKNOWN in (target, value) or types.DYNAMIC in (target, value):
+            return True
+
+        if self.options.strict_any and (target.kind is types.TypeKind.QUODLIBET) != (
+            value.kind is types.TypeKind.QUODLIBET
+        ):
+            self._error("T210", f"Implicit conversion from {value} to {target} requires an explicit 'ut' cast", span)
+            return True
+        return target.is_assignable_from(value)
+
+    def _error(
+        self, code: str, message: str, span: Optional[object], notes: Tuple[Tuple[str, Span], ...] = ()
+    ) -> None:
+        self.diagnostics.append(SemanticDiagnostic(code=code, message=message, span=span, notes=notes))
 
-    def _error(self, code: str, message: str, span: Optional[object]) -> None:
-        self.diagnostics.append(SemanticDiagnostic(code=code, message=message, span=span))
+    def _warning(self, code: str, message: str, span: Optional[object]) -> None:
+        self.diagnostics.append(SemanticDiagnostic(code=code, message=message, span=span, severity=Severity.WARNING))
@@ -0,0 +1,59 @@
+"""Catalogue of the diagnostic codes reported by `SemanticAnalyzer`."""
+
+from __future__ import annotations
+
+from typing import Dict
+
+# Code -> one-line summary. S = symbols, T = types, W = warnings.
+DIAGNOSTIC_CODES: Dict[str, str] = {
+    "S100": "Undeclared identifier, or one used before its declaration",
+    "S110": "Name already declared in the same scope",
+    "S111": "Global shares its name with a function",
+    "S112": "Function defined twice in the same module",
+    "S120": "Assignment to an immutable binding",
+    "S130": "Imported name is not exported by the module",
+    "S140": "'frange'/'perge' names a label no enclosing loop has",
+    "S141": "Loop label already used by an enclosing loop",
+    "S150": "Local declared without a value may be read before it is assigned",
+    "S160": "'ipse' used outside of a method",
+    "T010": "Returned value does not match the declared return type",
+    "T020": "Condition of 'si' is not booleanum",
+    "T021": "Condition of 'dum' is not booleanum",
+    "T022": "Condition of a C-style 'pro' is not booleanum",
+    "T030": "Expression in 'pro' is not iterable",
+    "T031": "Loop variable annotation does not match the element type",
+    "T032": "Expression in 'pro' is an optional array that may be nullum",
+    "T040": "'frange' outside of a loop",
+    "T041": "'perge' outside of a loop",
+    "T100": "Arithmetic, bitwise or update operand is not numerus",
+    "T102": "Comparison operand is not numerus",
+    "T103": "Range bound is not numerus",
+    "T104": "Strict comparison of unrelated types is constant",
+    "T110": "Logical operand is not booleanum",
+    "T120": "Left operand of '??' cannot be null",
+    "T121": "Right operand of '??' does not fit the optional's element type",
+    "T130": "Condition of '?:' is not booleanum",
+    "T200": "Value is not assignable to the target type",
+    "T201": "Assignment to a field the object type does not have",
+    "T202": "Member access on a type that has no members",
+    "T210": "Implicit conversion to or from quodlibet under strict_any",
+    "T211": "Cast between unrelated types",
+    "T212": "Array literal mixes incompatible element types under strict_any",
+    "T220": "Parameter without annotation under strict_params",
+    "T230": "Array whose elements are vacuum",
+    "T240": "Tuple size does not match its destructuring, index or target",
+    "T241": "Index into textus is neither numerus nor a range",
+    "T242": "Range index on something other than textus",
+    "T300": "Wrong number of arguments",
+    "T301": "Argument is not assignable to the parameter type",
+    "T302": "Callee is not a function",
+    "T303": "Wrong number of type arguments",
+    "T304": "Trailing lambda passed to a non-function parameter",
+    "W100": "Integer literal cannot be represented exactly",
+    "W101": "Loose comparison of unrelated types is constant",
+    "W102": "Shift amount is negative or fractional",
+    "W103": "Constant-true 'dum' or 'pro' loop that never exits",
+    "W104": "Assigned value is overwritten before it is read",
+    "W105": "Constant power or shift overflows or loses precision",
+    "W110": "Type parameter is never used",
+}
@@ -0,0 +1,156 @@
+"""
+Dead store detection: `x = 1; x = 2;`, where a value is overwritten before anything reads it.
+
+A backward dataflow pass over the structured statements of one function body computes, at each
+point, the locals that every path overwrites before reading them. An assignment to such a local is
+dead. `si` branches meet by intersection, loops iterate to a fixpoint, and `frange`/`perge` take the
+set of the point they jump to. The pass stays conservative: only plain `x = valor` statements are
+reported, never initialisers, and an assignment whose value calls a function, assigns or uses
+`++`/`--` is kept, as are locals captured by a lambda, which may read them at any time.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import Dict, FrozenSet, List, Optional, Sequence, Set
+
+from ..ast import nodes
+from ..ast.visitors import free_identifiers, iter_children
+
+_Set = FrozenSet[str]
+
+
+@dataclass(slots=True)
+class _Loop:
+    label: Optional[str]
+    exit: _Set
+    resume: _Set
+
+
+def find_dead_stores(
+    parameters: Sequence[nodes.Parameter], body: nodes.Statement
+) -> List[nodes.AssignmentExpression]:
+    """The assignments in *body* whose value is overwritten on every path before it is read, in source order."""
+
+    candidates = {param.name for param in parameters}
+    captured: Set[str] = set()
+    _collect_locals(body, candidates, captured)
+    finder = _DeadStoreFinder(frozenset(candidates - captured))
+    finder.statement(body, frozenset())
+    return sorted(finder.dead.values(), key=lambda node: node.span.start)
+
+
+def _collect_locals(node: nodes.Node, names: Set[str], captured: Set[str]) -> None:
+    if isinstance(node, nodes.VariableDeclaration):
+        names.add(node.name)
+    elif isinstance(node, nodes.DestructuringDeclaration):
+        names.update(node.names)
+    elif isinstance(node, nodes.ForStatement):
+        names.add(node.target.name)
+    elif isinstance(node, nodes.LambdaExpression):
+        captured.update(free_identifiers(node))
+        return
+    for child in iter_children(node):
+        _collect_locals(child, names, captured)
+
+
+def _uses(node: Optional[nodes.Node]) -> _Set:
+    """Every name *node* mentions, assignment targets and lambda bodies included."""
+
+    names: Set[str] = set()
+    pending = [node] if node is not None else []
+    while pending:
+        current = pending.pop()
+        if isinstance(current, nodes.Identifier):
+            names.add(current.name)
+        pending.extend(iter_children(current))
+    return frozenset(names)
+
+
+def _has_side_effects(node: nodes.Node) -> bool:
+    if isinstance(node, (nodes.CallExpression, nodes.AssignmentExpression, nodes.UpdateExpression)):
+        return True
+    if isinstance(node, nodes.LambdaExpression):
+        return False
+    return any(_has_side_effects(child) for child in iter_children(node))
+
+
+class _DeadStoreFinder:
+    def __init__(self, candidates: _Set) -> None:
+        self.candidates = candidates
+        self.dead: Dict[int, nodes.AssignmentExpression] = {}
+        self._loops: List[_Loop] = []
+
+    def statement(self, stmt: nodes.Statement, after: _Set) -> _Set:
+        """The locals overwritten before being read on every path from the start of *stmt*."""
+
+        if isinstance(stmt, nodes.BlockStatement):
+            for child in reversed(stmt.statements):
+                after = self.statement(child, after)
+            return after
+        if isinstance(stmt, nodes.ExpressionStatement):
+            expr = stmt.expression
+            if isinstance(expr, nodes.AssignmentExpression) and isinstance(expr.target, nodes.Identifier):
+                name = expr.target.name
+                if name not in self.candidates:
+                    return after - _uses(expr.value)
+                # Sets only grow while loops iterate, so a store found dead stays dead.
+                if name in after and not _has_side_effects(expr.value):
+                    self.dead[expr.node_id] = expr
+                return (after | {name}) - _uses(expr.value)
+            return after - _uses(expr)
+        if isinstance(stmt, nodes.VariableDeclaration):
+            return after - {stmt.name} - _uses(stmt.initializer)
+        if isinstance(stmt, nodes.DestructuringDeclaration):
+            return after - set(stmt.names) - _uses(stmt.initializer)
+        if isinstance(stmt, nodes.ReturnStatement):
+            return frozenset()
+        if isinstance(stmt, nodes.IfStatement):
+            then_set = self.statement(stmt.then_branch, after)
+            else_set = self.statement(stmt.else_branch, after) if stmt.else_branch is not None else after
+            return (then_set & else_set) - _uses(stmt.condition)
+        if isinstance(stmt, nodes.WhileStatement):
+            return self._loop(stmt, after, stmt.condition)
+        if isinstance(stmt, nodes.ForStatement):
+            return self._loop(stmt, after, binding=stmt.target.name) - _uses(stmt.iterable)
+        if isinstance(stmt, nodes.CStyleForStatement):
+            head = self._loop(stmt, after, stmt.condition, stmt.update)
+            return self.statement(stmt.init, head) if stmt.init is not None else head
+        if isinstance(stmt, nodes.BreakStatement):
+            loop = self._target(stmt.label)
+            return loop.exit if loop is not None else frozenset()
+        if isinstance(stmt, nodes.ContinueStatement):
+            loop = self._target(stmt.label)
+            return loop.resume if loop is not None else frozenset()
+        return frozenset()
+
+    def _loop(
+        self,
+        loop: nodes.WhileStatement | nodes.ForStatement | nodes.CStyleForStatement,
+        after: _Set,
+        condition: Optional[nodes.Expression] = None,
+        update: Optional[nodes.Expression] = None,
+        binding: Optional[str] = None,
+    ) -> _Set:
+        """The set at the loop head, where the condition is tested or the next element taken."""
+
+        # Iterate from the empty set: every step only adds names, so this stops, and a smaller
+        # set can only hide dead stores, never invent one.
+        head: _Set = frozenset()
+        while True:
+            resume = head - _uses(update)
+            self._loops.append(_Loop(loop.label, after, resume))
+            body = self.statement(loop.body, resume)
+            self._loops.pop()
+            if binding is not None:
+                body -= {binding}
+            next_head = (after & body) - _uses(condition)
+            if next_head == head:
+                return head
+            head = next_head
+
+    def _target(self, label: Optional[str]) -> Optional[_Loop]:
+        for loop in reversed(self._loops):
+            if label is None or loop.label == label:
+                return loop
+        return None
@@ -0,0 +1,18 @@
+"""
+Builtin functions every module can call without importing them.
+
+The analyzer declares these after the module's own items, so a function,
+variable or import with the same name takes their place. The interpreter
+implements them in `ir.interpreter.NATIVES`.
+"""
+
+from __future__ import annotations
+
+from typing import Dict
+
+from . import types
+
+PRELUDE: Dict[str, types.Type] = {
+    # `imprime(a, b, ...)` writes its arguments separated by spaces, then a newline.
+    "imprime": types.function_type([], types.PRIMITIVE_TYPES["vacuum"], rest=types.PRIMITIVE_TYPES["quodlibet"]),
+}
@@ -3,7 +3,8 @@
 from __future__ import annotations
 
 from dataclasses import dataclass, field
-from typing import Dict, List, Optional
+from enum import Enum
+from typing import Any, Dict, Iterable, List, Optional, Set
 
 from .types import Type
 
@@ -16,14 +17,46 @@ class Symbol:
     span: Optional[object] = None
 
 
+class SymbolKind(Enum):
+    FUNCTION = "function"
+    GLOBAL = "global"
+    LOCAL = "local"
+    PARAMETER = "parameter"
+
+
+@dataclass(slots=True)
+class SymbolEntry:
+    """One declaration seen by the analyzer, as listed in an editor outline."""
+
+    name: str
+    kind: SymbolKind
+    type: Type
+    span: Optional[object] = None
+    # True for functions whose return type was inferred from their `redde` statements.
+    inferred: bool = False
+
+    def as_json(self) -> Dict[str, Any]:
+        span = self.span
+        return {
+            "name": self.name,
+            "kind": self.kind.value,
+            "type": str(self.type),
+            "span": [span.start, span.end] if span is not None else None,
+            "inferred": self.inferred,
+        }
+
+
 @dataclass(slots=True)
 class Scope:
     symbols: Dict[str, Symbol] = field(default_factory=dict)
+    # Names declared further down the block that opened this scope.
+    pending: Set[str] = field(default_factory=set)
 
     def declare(self, symbol: Symbol) -> bool:
         if symbol.name in self.symbols:
             return False
         self.symbols[symbol.name] = symbol
+        self.pending.discard(symbol.name)
         return True
 
     def lookup(self, name: str) -> Optional[Symbol]:
@@ -55,10 +88,23 @@ class SymbolTable:
     def is_declared_in_current_scope(self, name: str) -> bool:
         return name in self._scopes[-1].symbols
 
+    def expect(self, names: Iterable[str]) -> None:
+        """Record that *names* are declared later in the current scope."""
+
+        self._scopes[-1].pending.update(names)
+
+    def is_declared_later(self, name: str) -> bool:
+        return any(name in scope.pending for scope in self._scopes)
+
+    def undeclared_message(self, name: str) -> str:
+        if self.is_declared_later(name):
+            return f"Identifier '{name}' is used before its declaration"
+        return f"Undeclared identifier '{name}'"
+
     def assign(self, name: str, value_type: Type) -> Optional[tuple[str, str]]:
         symbol = self.lookup(name)
         if symbol is None:
-            return ("S100", f"Undeclared identifier '{name}'")
+            return ("S100", self.undeclared_message(name))
         if not symbol.mutable:
             return ("S120", f"Cannot assign to immutable symbol '{name}'")
         if not symbol.type.is_assignable_from(value_type):
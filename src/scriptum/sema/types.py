@@ -4,7 +4,9 @@ from __future__ import annotations
 
 from dataclasses import dataclass
 from enum import Enum, auto
-from typing import Dict, Iterable, List, Optional
+from typing import Dict, Iterable, List, Optional, Sequence
+
+from ..tokens import property_key
 
 
 class TypeKind(Enum):
@@ -19,6 +21,9 @@ class TypeKind(Enum):
     OBJECT = auto()
     FUNCTION = auto()
     OPTIONAL = auto()
+    TUPLE = auto()
+    RANGE = auto()
+    PARAM = auto()
 
 
 @dataclass(frozen=True)
@@ -28,6 +33,18 @@ class Type:
     fields: Optional[Dict[str, "Type"]] = None
     params: Optional[List["Type"]] = None
     ret: Optional["Type"] = None
+    name: Optional[str] = None
+    type_params: Optional[List[str]] = None
+    # Functions only: how many leading params lack a default (None = all of them).
+    required: Optional[int] = None
+    # Tuples only: the element types, by position.
+    items: Optional[List["Type"]] = None
+    # Functions only: the type of every argument past `params`, for variadic builtins such as `imprime`.
+    rest: Optional["Type"] = None
+
+    @property
+    def min_arity(self) -> int:
+        return self.required if self.required is not None else len(self.params or [])
 
     def is_assignable_from(self, other: "Type") -> bool:
         if self.kind is TypeKind.QUODLIBET:
@@ -46,18 +63,29 @@ class Type:
             return self.element.is_assignable_from(other.element) if self.element and other.element else True
         if self.kind is TypeKind.NUMERUS and other.kind is TypeKind.NUMERUS:
             return True
+        if self.kind is TypeKind.RANGE and other.kind is TypeKind.RANGE:
+            return True
         if self.kind is TypeKind.BOOLEANUM and other.kind is TypeKind.BOOLEANUM:
             return True
         if self.kind is TypeKind.TEXTUS and other.kind is TypeKind.TEXTUS:
             return True
+        if self.kind is TypeKind.TUPLE and other.kind is TypeKind.TUPLE:
+            return tuple_mismatch(self, other) is None
+        if self.kind is TypeKind.OBJECT and other.kind is TypeKind.OBJECT:
+            # Structural: every expected field must be present and fit; extra fields are allowed.
+            return object_mismatch(self, other) is None
         if self.kind is TypeKind.FUNCTION and other.kind is TypeKind.FUNCTION:
             if (self.params is None) or (other.params is None):
                 return True
-            if len(self.params) != len(other.params):
+            # Every call the target allows must be one the value accepts.
+            if other.min_arity > self.min_arity or (len(other.params) < len(self.params) and other.rest is None):
+                return False
+            if self.rest is not None and other.rest is None:
                 return False
+            accepted = other.params + [other.rest] * (len(self.params) - len(other.params))
             return all(
                 param_self.is_assignable_from(param_other)
-                for param_self, param_other in zip(self.params, other.params)
+                for param_self, param_other in zip(self.params, accepted)
             ) and (self.ret is None or other.ret is None or self.ret.is_assignable_from(other.ret))
         return False
 
@@ -74,19 +102,63 @@ class Type:
     def is_optional(self) -> bool:
         return self.kind is TypeKind.OPTIONAL
 
+    def pretty(self, indent: int = 0) -> str:
+        """
+        Like `str`, but objects with more than `PRETTY_FIELD_LIMIT` fields put one field per line.
+
+        Nested lines are indented by four spaces per level, starting at *indent*.
+        """
+
+        if self.kind is TypeKind.OBJECT and len(self.fields or {}) > PRETTY_FIELD_LIMIT:
+            inner = "    " * (indent + 1)
+            lines = [f"{inner}{property_key(k)}: {v.pretty(indent + 1)}" for k, v in (self.fields or {}).items()]
+            return "{\n" + ",\n".join(lines) + "\n" + "    " * indent + "}"
+        if self.kind is TypeKind.OBJECT:
+            fields = (f"{property_key(k)}: {v.pretty(indent)}" for k, v in (self.fields or {}).items())
+            return "{" + ", ".join(fields) + "}"
+        if self.kind is TypeKind.ARRAY and self.element:
+            return f"[{self.element.pretty(indent)}]"
+        if self.kind is TypeKind.TUPLE:
+            return "(" + ", ".join(item.pretty(indent) for item in self.items or []) + ")"
+        if self.kind is TypeKind.OPTIONAL and self.element and self.element.kind is not TypeKind.FUNCTION:
+            return f"{self.element.pretty(indent)}?"
+        return str(self)
+
     def __str__(self) -> str:
         if self.kind is TypeKind.ARRAY:
             return f"[{self.element}]"
         if self.kind is TypeKind.OPTIONAL:
-            return f"{self.element}?"
+            return f"{_grouped(self.element)}?"
         if self.kind is TypeKind.OBJECT:
-            return "{" + ", ".join(f"{k}: {v}" for k, v in (self.fields or {}).items()) + "}"
+            return "{" + ", ".join(f"{property_key(k)}: {v}" for k, v in (self.fields or {}).items()) + "}"
+        if self.kind is TypeKind.TUPLE:
+            return "(" + ", ".join(_grouped(item) for item in self.items or []) + ")"
         if self.kind is TypeKind.FUNCTION:
-            params = ", ".join(str(p) for p in (self.params or []))
-            return f"functio({params}) -> {self.ret}"
+            params = ", ".join(
+                [str(p) if index < self.min_arity else f"{p} = ..." for index, p in enumerate(self.params or [])]
+                + ([f"...{_grouped(self.rest)}"] if self.rest is not None else [])
+            )
+            generics = f"<{', '.join(self.type_params)}>" if self.type_params else ""
+            return f"functio{generics}({params}) -> {_grouped(self.ret)}"
+        if self.kind is TypeKind.PARAM:
+            return self.name or "?"
         return self.kind.name.lower()
 
 
+# Objects with more fields than this are split over several lines by `Type.pretty`.
+PRETTY_FIELD_LIMIT = 4
+
+
+def _grouped(type_obj: Optional[Type]) -> str:
+    """Render a nested type, parenthesising function types so `->` and `?` stay unambiguous."""
+
+    if type_obj is None:
+        return "quodlibet"
+    if type_obj.kind is TypeKind.FUNCTION:
+        return f"({type_obj})"
+    return str(type_obj)
+
+
 PRIMITIVE_TYPES: Dict[str, Type] = {
     "numerus": Type(TypeKind.NUMERUS),
     "textus": Type(TypeKind.TEXTUS),
@@ -97,17 +169,94 @@ PRIMITIVE_TYPES: Dict[str, Type] = {
     "quodlibet": Type(TypeKind.QUODLIBET),
 }
 
+# The type of an expression whose error has already been reported. It is quodlibet to every rule,
+# and the analyzer also accepts it silently where a concrete type is required, so that one mistake
+# yields one diagnostic instead of one per use of the broken value.
+UNKNOWN = Type(TypeKind.QUODLIBET, name="unknown")
+
+# The type of a variable the user annotated `quodlibet` on purpose (`constans quodlibet x = ...;`), as
+# opposed to one that ends up quodlibet because nothing better was inferred. Like `UNKNOWN`, it fits
+# anywhere without a diagnostic, even under `strict_any`; member, index and call results keep it.
+DYNAMIC = Type(TypeKind.QUODLIBET, name="dynamic")
+
 
 def normalize_type_name(name: str) -> str:
     return name.strip().lower()
 
 
-def type_from_annotation(name: str) -> Optional[Type]:
-    name = normalize_type_name(name)
+def type_from_annotation(name: str, type_params: Sequence[str] = ()) -> Optional[Type]:
+    name = name.strip()
+    if _is_grouped(name):
+        entries = _split_top_level(name[1:-1])
+        if len(entries) > 1:
+            items = [type_from_annotation(entry, type_params) for entry in entries]
+            return tuple_type(items) if all(items) else None
+        # Grouping parens only steer what `?` and `[]` apply to; they add no type of their own.
+        return type_from_annotation(name[1:-1], type_params)
     if name.endswith("?"):
-        inner = type_from_annotation(name[:-1])
+        inner = type_from_annotation(name[:-1], type_params)
         return inner.with_optional() if inner else None
-    return PRIMITIVE_TYPES.get(name)
+    if name.endswith("[]"):
+        element = type_from_annotation(name[:-2], type_params)
+        return Type(TypeKind.ARRAY, element=element) if element else None
+    if name.startswith("[") and name.endswith("]"):
+        element = type_from_annotation(name[1:-1], type_params)
+        return Type(TypeKind.ARRAY, element=element) if element else None
+    if name.startswith("{") and name.endswith("}"):
+        return _object_type_from_annotation(name[1:-1], type_params)
+    if name in type_params:
+        return type_parameter(name)
+    return PRIMITIVE_TYPES.get(normalize_type_name(name))
+
+
+def _object_type_from_annotation(body: str, type_params: Sequence[str]) -> Optional[Type]:
+    fields: Dict[str, Type] = {}
+    for entry in _split_top_level(body) if body.strip() else []:
+        field_name, separator, annotation = entry.partition(":")
+        field_type = type_from_annotation(annotation, type_params) if separator else None
+        if field_type is None:
+            return None
+        fields[field_name.strip()] = field_type
+    return Type(TypeKind.OBJECT, fields=fields)
+
+
+def _split_top_level(text: str) -> List[str]:
+    """Split *text* on the commas that are not nested in brackets, parens or braces."""
+
+    entries: List[str] = []
+    depth = 0
+    start = 0
+    for index, char in enumerate(text):
+        if char in "([{":
+            depth += 1
+        elif char in ")]}":
+            depth -= 1
+        elif char == "," and depth == 0:
+            entries.append(text[start:index])
+            start = index + 1
+    entries.append(text[start:])
+    return entries
+
+
+def _is_grouped(name: str) -> bool:
+    """Whether *name* is wrapped in one pair of parens that match each other, as in `(numerus[])`."""
+
+    if not (name.startswith("(") and name.endswith(")")):
+        return False
+    depth = 0
+    for index, char in enumerate(name):
+        depth += {"(": 1, ")": -1}.get(char, 0)
+        if depth == 0:
+            return index == len(name) - 1
+    return False
+
+
+def tuple_type(items: Sequence[Type]) -> Type:
+    return Type(TypeKind.TUPLE, items=list(items))
+
+
+def type_parameter(name: str) -> Type:
+    return Type(TypeKind.PARAM, name=name)
 
 
 def type_from_literal(value: object, raw: str) -> Type:
@@ -140,5 +289,146 @@ def least_restrictive(types: Iterable[Type]) -> Type:
     return result or PRIMITIVE_TYPES["quodlibet"]
 
 
-def function_type(param_types: List[Type], return_type: Type) -> Type:
-    return Type(TypeKind.FUNCTION, params=param_types, ret=return_type)
+def unify(types: Iterable[Type]) -> Type:
+    """
+    Like `least_restrictive`, but nullum joins the other types as an optional.
+
+    `numerus` and `nullum` unify to `numerus?` rather than quodlibet; without a
+    union type, types that share no supertype still give quodlibet.
+    """
+
+    items = list(types)
+    others = [t for t in items if t.kind is not TypeKind.NULLUM]
+    if not others:
+        return items[0] if items else PRIMITIVE_TYPES["quodlibet"]
+    joined = least_restrictive(others)
+    if len(others) == len(items) or joined.kind in {TypeKind.QUODLIBET, TypeKind.VACUUM}:
+        return joined
+    return joined.with_optional()
+
+
+def function_type(
+    param_types: List[Type],
+    return_type: Type,
+    type_params: Optional[List[str]] = None,
+    required: Optional[int] = None,
+    rest: Optional[Type] = None,
+) -> Type:
+    if required is not None and required >= len(param_types):
+        required = None
+    return Type(
+        TypeKind.FUNCTION,
+        params=param_types,
+        ret=return_type,
+        type_params=type_params or None,
+        required=required,
+        rest=rest,
+    )
+
+
+def signature_mismatch(expected: Type, actual: Type) -> Optional[str]:
+    """Explain why function *actual* cannot stand in for *expected*; None if it can or either is not a function."""
+
+    if expected.kind is not TypeKind.FUNCTION or actual.kind is not TypeKind.FUNCTION:
+        return None
+    if expected.params is None or actual.params is None:
+        return None
+    if actual.min_arity > expected.min_arity or (len(actual.params) < len(expected.params) and actual.rest is None):
+        return f"takes {_arity(actual)}, but is called with {_arity(expected)}"
+    accepted = actual.params + [actual.rest] * (len(expected.params) - len(actual.params))
+    for index, (param_expected, param_actual) in enumerate(zip(expected.params, accepted), start=1):
+        if not param_expected.is_assignable_from(param_actual):
+            return f"parameter {index} is {param_actual}, expected {param_expected}"
+    if expected.ret and actual.ret and not expected.ret.is_assignable_from(actual.ret):
+        return f"returns {actual.ret}, expected {expected.ret}"
+    return None
+
+
+def object_mismatch(expected: Type, actual: Type, path: str = "") -> Optional[str]:
+    """
+    Explain why object *actual* lacks what *expected* requires; None if it fits or either is not an object.
+
+    Nested objects are compared field by field, so the reason names the innermost offending field
+    with a dotted path such as `'a.b.c'`. Field order never matters.
+    """
+
+    if expected.kind is not TypeKind.OBJECT or actual.kind is not TypeKind.OBJECT:
+        return None
+    found = actual.fields or {}
+    missing = [path + name for name in (expected.fields or {}) if name not in found]
+    if missing:
+        listed = ", ".join(f"'{name}'" for name in missing)
+        return f"missing field{'s' if len(missing) > 1 else ''} {listed}"
+    for name, field_type in (expected.fields or {}).items():
+        if field_type.is_assignable_from(found[name]):
+            continue
+        nested = object_mismatch(field_type, found[name], f"{path}{name}.")
+        return nested or f"field '{path}{name}' is {found[name]}, expected {field_type}"
+    return None
+
+
+def tuple_mismatch(expected: Type, actual: Type) -> Optional[str]:
+    """Explain why tuple *actual* does not fit *expected*, by size then by position; None if it fits."""
+
+    if expected.kind is not TypeKind.TUPLE or actual.kind is not TypeKind.TUPLE:
+        return None
+    wanted, found = expected.items or [], actual.items or []
+    if len(wanted) != len(found):
+        return f"expected {len(wanted)} values, got {len(found)}"
+    for position, (item, value) in enumerate(zip(wanted, found), start=1):
+        if not item.is_assignable_from(value):
+            return f"value {position} is {value}, expected {item}"
+    return None
+
+
+def _arity(function: Type) -> str:
+    low, high = function.min_arity, len(function.params or [])
+    count = str(high) if low == high else f"{low} to {high}"
+    return f"{count} argument" + ("" if count == "1" else "s")
+
+
+def substitute(type_obj: Type, bindings: Dict[str, Type]) -> Type:
+    """Replace type parameters in *type_obj* according to *bindings*."""
+
+    if type_obj.kind is TypeKind.PARAM:
+        return bindings.get(type_obj.name or "", type_obj)
+    if type_obj.kind in {TypeKind.ARRAY, TypeKind.OPTIONAL} and type_obj.element:
+        return Type(type_obj.kind, element=substitute(type_obj.element, bindings))
+    if type_obj.kind is TypeKind.OBJECT and type_obj.fields is not None:
+        fields = {key: substitute(value, bindings) for key, value in type_obj.fields.items()}
+        return Type(TypeKind.OBJECT, fields=fields)
+    if type_obj.kind is TypeKind.TUPLE:
+        return tuple_type([substitute(item, bindings) for item in type_obj.items or []])
+    if type_obj.kind is TypeKind.FUNCTION:
+        params = [substitute(param, bindings) for param in type_obj.params] if type_obj.params is not None else None
+        ret = substitute(type_obj.ret, bindings) if type_obj.ret else None
+        return Type(TypeKind.FUNCTION, params=params, ret=ret, required=type_obj.required, rest=type_obj.rest)
+    return type_obj
+
+
+def infer_type_arguments(params: Sequence[Type], arguments: Sequence[Optional[Type]]) -> Dict[str, Type]:
+    """Bind type parameters by matching parameter types against argument types (first match wins)."""
+
+    bindings: Dict[str, Type] = {}
+    for param, argument in zip(params, arguments):
+        if argument is not None:
+            _unify(param, argument, bindings)
+    return bindings
+
+
+def _unify(param: Type, argument: Type, bindings: Dict[str, Type]) -> None:
+    if param.kind is TypeKind.PARAM and param.name:
+        bindings.setdefault(param.name, argument)
+    elif param.kind is argument.kind and param.kind in {TypeKind.ARRAY, TypeKind.OPTIONAL}:
+        if param.element and argument.element:
+            _unify(param.element, argument.element, bindings)
+    elif param.kind is TypeKind.OPTIONAL and param.element:
+        _unify(param.element, argument, bindings)
+    elif param.kind is TypeKind.TUPLE and argument.kind is TypeKind.TUPLE:
+        for inner_param, inner_argument in zip(param.items or [], argument.items or []):
+            _unify(inner_param, inner_argument, bindings)
+    elif param.kind is TypeKind.FUNCTION and argument.kind is TypeKind.FUNCTION:
+        for inner_param, inner_argument in zip(param.params or [], argument.params or []):
+            _unify(inner_param, inner_argument, bindings)
+        if param.ret and argument.ret:
+            _unify(param.ret, argument.ret, bindings)
@@ -7,6 +7,8 @@ and token kinds required by the lexer and parser.
 
 from __future__ import annotations
 
+import json
+import re
 from dataclasses import dataclass
 from enum import Enum, auto
 from typing import Any, Dict, Iterable, Optional, Set, Tuple
@@ -34,6 +36,7 @@ KEYWORDS: Tuple[str, ...] = (
     "constans",
     "functio",
     "structura",
+    "ipse",
     "si",
     "aliter",
     "dum",
@@ -47,11 +50,17 @@ KEYWORDS: Tuple[str, ...] = (
     "falsum",
     "nullum",
     "indefinitum",
+    "infinitum",
+    "nan",
     "numerus",
     "textus",
     "booleanum",
     "vacuum",
     "quodlibet",
+    "publicus",
+    "privatus",
+    "importa",
+    "ut",
 )
 
 
@@ -69,6 +78,8 @@ OPERATORS: Tuple[str, ...] = (
     ">=",
     "<",
     "<=",
+    "<<",
+    ">>",
     "+",
     "-",
     "*",
@@ -76,7 +87,14 @@ OPERATORS: Tuple[str, ...] = (
     "%",
     "**",
     "!",
+    "~",
     ".",
+    "?.",
+    "?[",
+    "..",
+    "..=",
+    "++",
+    "--",
 )
 
 
@@ -86,6 +104,10 @@ PUNCTUATION: Tuple[str, ...] = (",", ";", ":", "::", "->", "=>", "?")
 DELIMITERS: Tuple[str, ...] = ("{", "}", "[", "]", "(", ")")
 
 
+# Optional last letter of a number literal: `5i` must be a whole number and `5f` is kept as a float.
+NUMBER_SUFFIXES: Tuple[str, ...] = ("i", "f")
+
+
 @dataclass(slots=True)
 class Token:
     """Concrete token emitted by the lexer."""
@@ -106,6 +128,23 @@ def is_keyword(lexeme: str) -> bool:
     return lexeme in KEYWORDS
 
 
+# Same as the IDENTIFIER pattern in `lexer.spec`.
+_IDENTIFIER_RE = re.compile(r"[A-Za-z_][A-Za-z0-9_$]*")
+
+
+def is_identifier(name: str) -> bool:
+    """Check if *name* lexes as a single identifier, which excludes keywords."""
+
+    return _IDENTIFIER_RE.fullmatch(name) is not None and not is_keyword(name)
+
+
+def property_key(name: str) -> str:
+    """*name* written as an object key: bare when it is an identifier, else as a string literal."""
+
+    # String literal escapes are JSON's, so `json.dumps` writes one Scriptum and JavaScript both read.
+    return name if is_identifier(name) else json.dumps(name, ensure_ascii=False)
+
+
 def all_literals() -> Iterable[str]:
     """Return the set of fixed literal lexemes (operators + punctuation + delimiters)."""
 
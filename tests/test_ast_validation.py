@@ -0,0 +1,77 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+
+from scriptum.ast import nodes
+from scriptum.ast.validate import validate_ast
+from scriptum.parser.parser import ParseError, ScriptumParser
+from scriptum.text import SourceFile, Span
+
+EXAMPLES = sorted((Path(__file__).resolve().parents[1] / "examples").rglob("*.stm"))
+
+
+def _if(else_branch: object) -> nodes.Module:
+    condition = nodes.Literal(node_id=2, span=Span(4, 9), value=True, raw="verum")
+    then_branch = nodes.BlockStatement(node_id=3, span=Span(11, 13), statements=[])
+    statement = nodes.IfStatement(
+        node_id=1, span=Span(0, 40), condition=condition, then_branch=then_branch, else_branch=else_branch
+    )
+    body = nodes.BlockStatement(node_id=5, span=Span(0, 40), statements=[statement])
+    function = nodes.FunctionDeclaration(
+        node_id=6, span=Span(0, 40), name="main", parameters=[], return_type=None, body=body
+    )
+    return nodes.Module(node_id=7, span=Span(0, 40), declarations=[function])
+
+
+@pytest.mark.parametrize("path", EXAMPLES, ids=lambda path: path.name)
+def test_parsed_examples_satisfy_the_ast_invariants(path: Path) -> None:
+    try:
+        module = ScriptumParser().parse(SourceFile(str(path), path.read_text(encoding="utf8")))
+    except ParseError:
+        pytest.skip("example of a syntax error")
+    assert validate_ast(module) == []
+
+
+def test_aliter_holding_an_expression_is_rejected() -> None:
+    stray = nodes.Identifier(node_id=4, span=Span(20, 25), name="valor")
+    problems = validate_ast(_if(stray))
+    assert [problem.message for problem in problems] == [
+        "'aliter' branch must be a block or 'si', got Identifier",
+        "IfStatement.else_branch should be Statement or missing, got Identifier",
+    ]
+
+
+def test_aliter_holding_a_bare_statement_is_rejected() -> None:
+    stray = nodes.BreakStatement(node_id=4, span=Span(20, 27))
+    (problem,) = validate_ast(_if(stray))
+    assert problem.message == "'aliter' branch must be a block or 'si', got BreakStatement"
+    assert problem.span == Span(20, 27)
+
+
+def test_aliter_si_and_blocks_are_accepted() -> None:
+    assert validate_ast(_if(None)) == []
+    assert validate_ast(_if(nodes.BlockStatement(node_id=4, span=Span(20, 22), statements=[]))) == []
+    nested = nodes.IfStatement(
+        node_id=8,
+        span=Span(20, 35),
+        condition=nodes.Literal(node_id=9, span=Span(24, 30), value=False, raw="falsum"),
+        then_branch=nodes.BlockStatement(node_id=10, span=Span(32, 34), statements=[]),
+        else_branch=None,
+    )
+    assert validate_ast(_if(nested)) == []
+
+
+def test_missing_child_reused_ids_and_escaping_spans_are_reported() -> None:
+    module = _if(nodes.BlockStatement(node_id=2, span=Span(30, 60), statements=[]))
+    statement = module.declarations[0].body.statements[0]
+    statement.condition = None
+    messages = [problem.message for problem in validate_ast(module)]
+    assert messages == [
+        "IfStatement.condition should be Expression, got nothing",
+        "BlockStatement at Span(start=30, end=60) lies outside its parent IfStatement",
+    ]
+
+    module = _if(nodes.BlockStatement(node_id=2, span=Span(30, 35), statements=[]))
+    assert [problem.message for problem in validate_ast(module)] == ["BlockStatement reuses node id 2"]
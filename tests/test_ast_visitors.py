@@ -0,0 +1,154 @@
+from __future__ import annotations
+
+import textwrap
+
+from scriptum.ast import nodes
+from scriptum.ast.visitors import (
+    call_graph,
+    count_nodes,
+    free_identifiers,
+    node_at,
+    nodes_at,
+    recursive_functions,
+    strongly_connected_components,
+)
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile
+
+
+def _parse(source: str) -> nodes.Module:
+    return ScriptumParser().parse(SourceFile("<test>", textwrap.dedent(source)))
+
+
+def test_lambda_reports_captured_outer_variables() -> None:
+    module = _parse(
+        """
+        constans numerus passo = 2;
+
+        functio contador() {
+            mutabilis numerus total = 0;
+            constans quodlibet incrementa = functio (extra) {
+                constans numerus local = extra + passo;
+                total = total + local;
+                redde total;
+            };
+            redde incrementa;
+        }
+        """
+    )
+    function = module.declarations[1]
+    lam = function.body.statements[1].initializer
+    assert isinstance(lam, nodes.LambdaExpression)
+    assert free_identifiers(lam) == {"passo", "total"}
+    assert free_identifiers(function) == {"passo"}
+
+
+def test_nested_lambda_captures_bubble_up_and_loop_targets_are_bound() -> None:
+    module = _parse(
+        """
+        functio externa(lista) {
+            redde functio () {
+                pro item in lista {
+                    constans quodlibet f = functio () => item + fator;
+                    f();
+                }
+            };
+        }
+        """
+    )
+    outer_lambda = module.declarations[0].body.statements[0].value
+    assert free_identifiers(outer_lambda) == {"lista", "fator"}
+    assert free_identifiers(module.declarations[0]) == {"fator"}
+
+
+def test_call_graph_links_mutually_recursive_functions_in_a_cycle() -> None:
+    module = _parse(
+        """
+        functio par(n) {
+            si n == 0 { redde verum; }
+            redde impar(n - 1);
+        }
+
+        functio impar(n) {
+            si n == 0 { redde falsum; }
+            redde par(n - 1);
+        }
+
+        functio main(imprime) {
+            constans par = 3;
+            constans quodlibet f = functio () => impar(par);
+            imprime(f());
+            redde f();
+        }
+        """
+    )
+    assert call_graph(module) == {"par": ["impar"], "impar": ["par"], "main": ["impar"]}
+
+
+def test_tarjan_finds_direct_and_indirect_recursion() -> None:
+    module = _parse(
+        """
+        functio fatorial(n) {
+            si n == 0 { redde 1; }
+            redde n * fatorial(n - 1);
+        }
+
+        functio a(n) { redde b(n); }
+        functio b(n) { redde c(n); }
+        functio c(n) { redde n > 0 ? a(n - 1) : folha(n); }
+        functio folha(n) { redde n; }
+
+        functio main() { redde a(fatorial(3)); }
+        """
+    )
+    graph = call_graph(module)
+    assert strongly_connected_components(graph) == [["fatorial"], ["folha"], ["a", "b", "c"], ["main"]]
+    assert recursive_functions(graph) == [["fatorial"], ["a", "b", "c"]]
+
+
+def test_acyclic_call_graph_has_no_recursive_functions() -> None:
+    graph = {"main": ["ajuda", "fora_do_modulo"], "ajuda": []}
+    assert strongly_connected_components(graph) == [["ajuda"], ["main"]]
+    assert recursive_functions(graph) == []
+
+
+def test_long_call_chains_do_not_exhaust_the_recursion_limit() -> None:
+    names = [f"f{position}" for position in range(3000)]
+    chain = {name: [callee] for name, callee in zip(names, names[1:])}
+    assert strongly_connected_components({**chain, names[-1]: []}) == [[name] for name in reversed(names)]
+    assert recursive_functions({**chain, names[-1]: [names[0]]}) == [names]
+
+
+def test_node_at_finds_the_innermost_node_under_an_offset() -> None:
+    source = "functio f(n) {\n    redde n * 2;\n}\n"
+    module = ScriptumParser().parse(SourceFile("<test>", source))
+    offset = source.index("n * 2")
+    path = nodes_at(module, offset)
+    assert [type(node).__name__ for node in path] == [
+        "Module",
+        "FunctionDeclaration",
+        "BlockStatement",
+        "ReturnStatement",
+        "BinaryExpression",
+        "Identifier",
+    ]
+    assert node_at(module, offset) is path[-1]
+    assert isinstance(node_at(module, source.index("2;")), nodes.Literal)
+    assert node_at(module, len(source) + 5) is None
+
+
+def test_count_nodes_covers_declarations_statements_expressions_and_types() -> None:
+    module = _parse(
+        """
+        constans valores: numerus[] = [1, 2];
+
+        functio soma(numerus a, numerus b) -> numerus {
+            redde a + b;
+        }
+        """
+    )
+    # Variable: declaration, annotation, array, two literals. Function: declaration, two parameters
+    # with their annotations, return annotation, block, return, binary expression, two identifiers.
+    assert count_nodes(module.declarations[0]) == 5
+    assert count_nodes(module.declarations[1]) == 11
+    assert count_nodes(module) == 17
@@ -0,0 +1,107 @@
+from __future__ import annotations
+
+import pathlib
+
+from click.testing import CliRunner
+
+from scriptum.cli import cli
+from scriptum.driver import CompilerDriver, DriverConfig, Stage
+from scriptum.parser.parser import ScriptumParser
+from scriptum.sema.analyzer import AnalyzerOptions
+
+MAIN = """importa { dobra } de "util.stm";
+
+functio main() -> numerus {
+    redde dobra(21);
+}
+"""
+
+UTIL = """functio dobra(numerus x) -> numerus {
+    redde x * 2;
+}
+"""
+
+
+def _project(directory: pathlib.Path) -> pathlib.Path:
+    (directory / "util.stm").write_text(UTIL, encoding="utf8")
+    main = directory / "main.stm"
+    main.write_text(MAIN, encoding="utf8")
+    return main
+
+
+def _build(main: pathlib.Path, cache_dir: pathlib.Path, **options: bool) -> CompilerDriver.Result:
+    config = DriverConfig(analyzer=AnalyzerOptions(**options), cache_dir=cache_dir)
+    return CompilerDriver(config).run(main, until=Stage.CODEGEN)
+
+
+def test_second_build_reuses_the_cached_ir(tmp_path: pathlib.Path, monkeypatch) -> None:
+    main = _project(tmp_path)
+    first = _build(main, tmp_path / "cache")
+    assert not first.cached
+    assert len(list((tmp_path / "cache").glob("*.scir"))) == 1
+
+    def fail(*_args, **_kwargs):
+        raise AssertionError("the front end should not run on a cache hit")
+
+    monkeypatch.setattr(ScriptumParser, "parse", fail)
+    second = _build(main, tmp_path / "cache")
+    assert second.cached
+    assert second.ast is None
+    assert second.ir == first.ir
+    assert second.formatted == first.formatted
+
+
+def test_changed_inputs_miss_the_cache(tmp_path: pathlib.Path) -> None:
+    main = _project(tmp_path)
+    cache_dir = tmp_path / "cache"
+    _build(main, cache_dir)
+
+    assert not _build(main, cache_dir, strict_params=True).cached
+
+    (tmp_path / "util.stm").write_text(UTIL.replace("x * 2", "x + x"), encoding="utf8")
+    assert not _build(main, cache_dir).cached
+    assert _build(main, cache_dir).cached
+
+    main.write_text(MAIN.replace("21", "20"), encoding="utf8")
+    rebuilt = _build(main, cache_dir)
+    assert not rebuilt.cached
+    assert "dobra(20)" in rebuilt.formatted
+
+
+def test_same_text_in_another_directory_does_not_reuse_the_entry(tmp_path: pathlib.Path) -> None:
+    cache_dir = tmp_path / "cache"
+    first, second = tmp_path / "d1", tmp_path / "d2"
+    first.mkdir()
+    second.mkdir()
+    _build(_project(first), cache_dir)
+    main = _project(second)
+    (second / "util.stm").write_text(UTIL.replace("dobra", "triplica"), encoding="utf8")
+
+    result = CompilerDriver(DriverConfig(cache_dir=cache_dir)).run(main, until=Stage.SEMANTIC)
+    assert "S130" in [diagnostic.code for diagnostic in result.diagnostics]
+    runner = CliRunner()
+    built = runner.invoke(cli, ["build", str(main), "--cache-dir", str(cache_dir)])
+    assert built.exit_code != 0
+    assert "S130" in built.output
+
+
+def test_unreadable_entry_is_rebuilt(tmp_path: pathlib.Path) -> None:
+    main = _project(tmp_path)
+    cache_dir = tmp_path / "cache"
+    _build(main, cache_dir)
+    for entry in cache_dir.glob("*.scir"):
+        entry.write_bytes(b"SCIR\xff\xff")
+
+    assert not _build(main, cache_dir).cached
+    assert _build(main, cache_dir).cached
+
+
+def test_build_command_accepts_a_cache_directory(tmp_path: pathlib.Path) -> None:
+    main = _project(tmp_path)
+    runner = CliRunner()
+    argv = ["build", str(main), "--cache-dir", str(tmp_path / "cache")]
+    first = runner.invoke(cli, argv)
+    second = runner.invoke(cli, argv)
+    assert first.exit_code == 0, first.output
+    assert second.output == first.output
+    assert list((tmp_path / "cache").glob("*.scir"))
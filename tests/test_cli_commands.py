@@ -20,6 +20,29 @@ def test_dev_lex_outputs_tokens_json() -> None:
     assert tokens[0]["lexeme"] in {"constans", "mutabilis"}
 
 
+def test_dev_lex_text_format_shows_line_and_column_ranges(tmp_path: Path) -> None:
+    program = tmp_path / "posicoes.stm"
+    program.write_text('constans x = 1;\n\n  redde "a b";\n', encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["dev", "lex", "--format", "text", str(program)])
+    assert result.exit_code == 0, result.output
+    assert result.output.splitlines() == [
+        '1:1-1:9 KEYWORD "constans"',
+        '1:10-1:11 IDENTIFIER "x"',
+        '1:12-1:13 OPERATOR "="',
+        '1:14-1:15 NUMBER_LITERAL "1"',
+        '1:15-1:16 PUNCTUATION ";"',
+        '3:3-3:8 KEYWORD "redde"',
+        '3:9-3:14 STRING_LITERAL "\\"a b\\""',
+        '3:14-3:15 PUNCTUATION ";"',
+    ]
+
+    as_json = json.loads(runner.invoke(cli, ["dev", "lex", str(program)]).output)
+    assert as_json[5]["span"] == [19, 24]
+    assert as_json[5]["start"] == {"line": 3, "column": 3}
+    assert as_json[5]["end"] == {"line": 3, "column": 8}
+
+
 def test_dev_ast_outputs_ast_json() -> None:
     runner = CliRunner()
     result = runner.invoke(cli, ["dev", "ast", str(FIXTURES / "basic_valid.stm")])
@@ -46,6 +69,164 @@ def test_check_reports_diagnostics_in_json() -> None:
     assert diagnostics[0]["position"]["line"] >= 1
 
 
+def test_check_succeeds_with_only_warnings(tmp_path: Path) -> None:
+    program = tmp_path / "aviso.stm"
+    program.write_text("constans numerus grande = 9007199254740993;\n", encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(program)])
+    assert result.exit_code == 0, result.output
+    assert "W100 (warning):" in result.output
+    assert "Semantic analysis completed successfully." in result.output
+
+
+def test_check_deny_warnings_fails_on_warnings(tmp_path: Path) -> None:
+    program = tmp_path / "avisos.stm"
+    program.write_text(
+        "constans numerus grande = 9007199254740993;\nconstans booleanum nunca = 1 == \"1\";\n", encoding="utf8"
+    )
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(program), "--deny-warnings"])
+    assert result.exit_code != 0
+    assert "W100 (warning):" in result.output and "W101 (warning):" in result.output
+    assert "2 warnings treated as errors (--deny-warnings)." in result.output
+    assert "completed successfully" not in result.output
+
+    as_json = runner.invoke(cli, ["check", str(program), "--deny-warnings", "--json"])
+    assert as_json.exit_code != 0
+    assert [diag["severity"] for diag in json.loads(as_json.output.split("Error:", 1)[0])] == ["warning", "warning"]
+
+    program.write_text("constans numerus pequeno = 1;\n", encoding="utf8")
+    clean = runner.invoke(cli, ["check", str(program), "--deny-warnings"])
+    assert clean.exit_code == 0, clean.output
+
+
+def test_check_deny_warnings_counts_them_next_to_errors(tmp_path: Path) -> None:
+    program = tmp_path / "misto.stm"
+    program.write_text("constans numerus grande = 9007199254740993;\nconstans textus t = 1;\n", encoding="utf8")
+    result = CliRunner().invoke(cli, ["check", str(program), "--deny-warnings"])
+    assert result.exit_code != 0
+    assert "Semantic analysis reported issues. 1 warning treated as an error (--deny-warnings)." in result.output
+
+
+def test_check_shows_inferred_return_types(tmp_path: Path) -> None:
+    program = tmp_path / "inferido.stm"
+    program.write_text("functio f() {\n    redde 1 + 2;\n}\n", encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(program), "--show-inferred"])
+    assert result.exit_code == 0, result.output
+    assert result.output.splitlines() == ["functio f() -> numerus", "Semantic analysis completed successfully."]
+
+    combined = runner.invoke(cli, ["check", str(program), "--show-inferred", "--json"])
+    assert combined.exit_code != 0
+    assert "cannot be combined with --json" in combined.output
+
+
+def test_check_reports_several_files_together(tmp_path: Path) -> None:
+    valid = tmp_path / "valido.stm"
+    valid.write_text("constans numerus n = 1;\n", encoding="utf8")
+    broken = tmp_path / "quebrado.stm"
+    broken.write_text("constans textus t = 1;\n", encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(valid), str(broken)])
+    assert result.exit_code != 0
+    assert result.output.splitlines() == [
+        f"{broken}:",
+        "  T200: Type mismatch: cannot initialise 't' of type textus with numerus",
+        "Checked 2 files: 1 error, 0 warnings.",
+        "Error: Semantic analysis reported issues in 1 of 2 files.",
+    ]
+
+    as_json = runner.invoke(cli, ["check", "--json", str(valid), str(broken)])
+    assert as_json.exit_code != 0
+    report = json.loads(as_json.output.split("Error:", 1)[0])
+    assert [entry["file"] for entry in report] == [str(valid), str(broken)]
+    assert report[0]["diagnostics"] == []
+    assert [diag["code"] for diag in report[1]["diagnostics"]] == ["T200"]
+
+    clean = runner.invoke(cli, ["check", str(valid), str(valid)])
+    assert clean.exit_code == 0, clean.output
+    assert clean.output.splitlines() == [
+        "Checked 2 files: 0 errors, 0 warnings.",
+        "Semantic analysis completed successfully.",
+    ]
+
+
+def test_graph_calls_prints_dot_with_recursive_cycle(tmp_path: Path) -> None:
+    program = tmp_path / "grafo.stm"
+    program.write_text(
+        "functio a(n) {\n    redde b(n);\n}\n\nfunctio b(n) {\n    redde a(n);\n}\n\n"
+        "functio c() {\n    redde c();\n}\n",
+        encoding="utf8",
+    )
+    runner = CliRunner()
+    result = runner.invoke(cli, ["graph", "--calls", str(program)])
+    assert result.exit_code == 0, result.output
+    assert result.output.splitlines() == [
+        "digraph calls {",
+        '    "a";',
+        '    "b";',
+        '    "c";',
+        '    "a" -> "b";',
+        '    "b" -> "a";',
+        '    "c" -> "c";',
+        "}",
+    ]
+
+    recursive = runner.invoke(cli, ["graph", "--recursive", str(program)])
+    assert recursive.exit_code == 0, recursive.output
+    assert recursive.output.splitlines() == ["a, b", "c"]
+
+    missing = runner.invoke(cli, ["graph", str(program)])
+    assert missing.exit_code != 0
+    assert "--calls" in missing.output
+
+
+def test_check_points_at_unterminated_string(tmp_path: Path) -> None:
+    program = tmp_path / "aberta.stm"
+    program.write_text('functio main() {\n    constans textus b = "aberta;\n    redde b;\n}\n', encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(program)])
+    assert result.exit_code != 0
+    assert "Unterminated string literal starting at line 2, column 25" in result.output
+    assert '    constans textus b = "aberta;\n                        ^\n' in result.output
+
+
+def test_check_points_at_the_opening_of_an_unclosed_parenthesis(tmp_path: Path) -> None:
+    program = tmp_path / "aberto.stm"
+    program.write_text("functio main() {\n    constans x = (1 + 2;\n    redde x;\n}\n", encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(program)])
+    assert result.exit_code != 0
+    assert "Expected ')' after expression. Found ';'" in result.output
+    assert "The '(' opened at line 2, column 18 is still open." in result.output
+    assert "note: '(' opened here\n    constans x = (1 + 2;\n                 ^\n" in result.output
+
+
+def test_check_json_reports_syntax_errors_as_diagnostics(tmp_path: Path) -> None:
+    program = tmp_path / "aberto.stm"
+    program.write_text("functio main() {\n    redde (1;\n}\n", encoding="utf8")
+    result = CliRunner().invoke(cli, ["check", str(program), "--json"])
+    assert result.exit_code != 0
+    payload, _, tail = result.output.rpartition("]")
+    (diagnostic,) = json.loads(payload + "]")
+    assert diagnostic["code"] is None
+    assert diagnostic["position"] == {"line": 2, "column": 13}
+    assert diagnostic["notes"] == [{"message": "'(' opened here", "span": [27, 28]}]
+    assert "Compilation stopped before semantic analysis." in tail
+
+
+def test_check_reports_ternary_without_colon_once(tmp_path: Path) -> None:
+    program = tmp_path / "ternario.stm"
+    program.write_text("functio main() {\n    constans x = verum ? 1;\n    redde x;\n}\n", encoding="utf8")
+    runner = CliRunner()
+    result = runner.invoke(cli, ["check", str(program)])
+    assert result.exit_code != 0
+    assert result.output == (
+        "Error: Expected ':' to complete the conditional expression started by '?' at Span(start=40, end=41). "
+        "Found ';' at Span(start=43, end=44).\n"
+    )
+
+
 def test_dev_ir_and_run_commands() -> None:
     runner = CliRunner()
     ir_result = runner.invoke(cli, ["dev", "ir", str(FIXTURES / "basic_valid.stm")])
@@ -78,6 +259,12 @@ def test_inline_execution_without_subcommand() -> None:
     assert json.loads(result.output) == 42
 
 
+def test_run_prints_ranges_as_imprime_shows_them() -> None:
+    result = CliRunner().invoke(cli, ["run", "-c", "functio main() { redde [0..3, 1..=2]; }"])
+    assert result.exit_code == 0, result.output
+    assert json.loads(result.output) == ["0..3", "1..=2"]
+
+
 @pytest.mark.parametrize(
     "argv",
     [
@@ -87,6 +274,7 @@ def test_inline_execution_without_subcommand() -> None:
         ["check"],
         ["fmt"],
         ["build"],
+        ["compile"],
     ],
 )
 def test_cli_rejects_non_stm_files(argv: list[str]) -> None:
@@ -96,3 +284,41 @@ def test_cli_rejects_non_stm_files(argv: list[str]) -> None:
         result = runner.invoke(cli, argv + ["program.txt"])
     assert result.exit_code != 0
     assert "must use the .stm extension" in result.output
+
+
+@pytest.mark.parametrize(
+    ("emit", "check"),
+    [
+        ("tokens", lambda output: json.loads(output)[0]["lexeme"] == "constans"),
+        ("ast", lambda output: json.loads(output)["__type__"] == "Module"),
+        ("ir", lambda output: output.startswith("ModuleIr 0..")),
+        ("ir-json", lambda output: json.loads(output)["kind"] == "ModuleIr"),
+        ("code", lambda output: output.startswith("constans numerus base = 1;\n\nfunctio main() -> numerus {\n")),
+        ("js", lambda output: output.startswith("const base = 1;\n")),
+    ],
+)
+def test_compile_prints_the_requested_representation(emit: str, check) -> None:
+    runner = CliRunner()
+    result = runner.invoke(cli, ["compile", str(FIXTURES / "basic_valid.stm"), f"--emit={emit}"])
+    assert result.exit_code == 0, result.output
+    assert check(result.output)
+
+
+def test_compile_defaults_to_code_and_keeps_legacy_stage_option() -> None:
+    runner = CliRunner()
+    source = str(FIXTURES / "basic_valid.stm")
+    assert runner.invoke(cli, ["compile", source]).output == runner.invoke(cli, ["build", source]).output
+    legacy = runner.invoke(cli, ["compile", source, "--stage", "semantic"])
+    assert legacy.exit_code == 0, legacy.output
+    assert "[warning]" in legacy.output
+
+
+def test_compile_to_js_rejects_modules_with_imports(tmp_path: Path) -> None:
+    (tmp_path / "util.stm").write_text("functio dobra(numerus x) -> numerus { redde x * 2; }\n", encoding="utf8")
+    program = tmp_path / "principal.stm"
+    program.write_text(
+        'importa { dobra } de "util.stm";\n\nfunctio main() -> numerus { redde dobra(2); }\n', encoding="utf8"
+    )
+    result = CliRunner().invoke(cli, ["compile", str(program), "--emit=js"])
+    assert result.exit_code == 1
+    assert result.output == "Error: The JavaScript backend does not support 'importa' yet (imports 'util.stm')\n"
@@ -2,14 +2,16 @@ from __future__ import annotations
 
 from pathlib import Path
 
+import pytest
 from click.testing import CliRunner
 
 from scriptum.cli import cli
-from scriptum.codegen import generate
+from scriptum.codegen import CodegenOptions, generate
 from scriptum.parser.parser import ScriptumParser
 from scriptum.text import SourceFile
 
 FIXTURES_DIR = Path(__file__).resolve().parents[0] / "fixtures" / "fmt"
+EXAMPLES_DIR = Path(__file__).resolve().parents[1] / "examples" / "ok"
 
 
 def _load_fixture(name: str) -> tuple[str, str]:
@@ -24,6 +26,11 @@ def _format_source(text: str) -> str:
     return generate(module).formatted
 
 
+def _format_source_minified(text: str) -> str:
+    module = ScriptumParser().parse(SourceFile("<test>", text))
+    return generate(module, CodegenOptions(minify=True)).formatted
+
+
 def test_formatter_matches_golden_loops() -> None:
     raw, expected = _load_fixture("loops")
     formatted = _format_source(raw)
@@ -38,6 +45,67 @@ def test_formatter_is_idempotent() -> None:
     assert twice == expected
 
 
+@pytest.mark.parametrize(
+    "program", sorted(EXAMPLES_DIR.rglob("*.stm")), ids=lambda path: str(path.relative_to(EXAMPLES_DIR))
+)
+def test_formatting_reaches_a_fixpoint_on_examples(program: Path) -> None:
+    once = _format_source(program.read_text(encoding="utf8"))
+    assert _format_source(once) == once
+
+
+def test_powers_of_prefix_operations_keep_their_parentheses() -> None:
+    source = "functio main() {\n    redde [(-x) ** 2, -(x ** 2), (!x) ** y ** 2, (++x) ** 2, 2 ** -x];\n}\n"
+    assert _format_source(source) == source
+
+
+def test_object_keys_that_are_not_identifiers_stay_quoted() -> None:
+    source = 'constans o = structura { "com-traço": 1, "123": 2, "nome": 3, "si": 4, "a\\"b": 5 };\n'
+    expected = 'constans o = structura { "com-traço": 1, "123": 2, nome: 3, "si": 4, "a\\"b": 5 };\n'
+    assert _format_source(source) == expected
+    assert _format_source(expected) == expected
+
+
+def test_line_comments_stay_next_to_their_object_properties() -> None:
+    raw, expected = _load_fixture("object_comments")
+    assert _format_source(raw) == expected
+    assert _format_source(expected) == expected
+    # Comments are trivia: the minified output drops them and still parses.
+    assert "//" not in _format_source_minified(raw)
+
+
+def test_long_object_literals_break_one_property_per_line() -> None:
+    source = 'functio main() {\n    redde structura { alfa: 1, beta: "dois", gama: [3, 4] };\n}\n'
+    module = ScriptumParser().parse(SourceFile("<test>", source))
+    assert generate(module, CodegenOptions(max_width=40)).formatted == (
+        "functio main() {\n"
+        "    redde structura {\n"
+        "        alfa: 1,\n"
+        '        beta: "dois",\n'
+        "        gama: [3, 4]\n"
+        "    };\n"
+        "}\n"
+    )
+
+
+def test_keyword_member_names_round_trip() -> None:
+    source = 'functio main() {\n    constans o = structura { "redde": 1, "si": 2 };\n    redde o.redde + o?.si;\n}\n'
+    assert _format_source(source) == source
+    minified = _format_source_minified(source)
+    assert minified == 'functio main(){constans o=structura{"redde":1,"si":2};redde o.redde+o?.si;}'
+
+
+def test_formatter_keeps_else_if_chains_flat() -> None:
+    raw, expected = _load_fixture("else_if")
+    assert _format_source(raw) == expected
+    assert _format_source(expected) == expected
+
+
+def test_formatter_preserves_visibility_modifiers() -> None:
+    source = "publicus functio f() -> numerus {\n    redde 1;\n}\n"
+    formatted = _format_source("privatus   constans numerus x = 1;\n" + source)
+    assert formatted == "privatus constans numerus x = 1;\n\n" + source
+
+
 def test_cli_fmt_formats_file_in_place(tmp_path: Path) -> None:
     raw, expected = _load_fixture("collections")
     target = tmp_path / "sample.stm"
@@ -60,3 +128,205 @@ def test_cli_fmt_from_stdin() -> None:
     result = runner.invoke(cli, ["fmt"], input=raw)
     assert result.exit_code == 0
     assert result.output == expected
+
+
+def test_formatter_keeps_trailing_lambdas() -> None:
+    source = (
+        "functio main() {\n"
+        "    repete(3) functio () {\n"
+        "        dic();\n"
+        "    };\n"
+        "    cura functio (x) => x;\n"
+        "}\n"
+    )
+    assert _format_source(source) == source
+
+
+def test_formatter_keeps_range_kind() -> None:
+    source = (
+        "functio main() {\n"
+        "    pro i in 0..=n {\n"
+        "        perge;\n"
+        "    }\n"
+        "    pro j in 0..n {\n"
+        "        perge;\n"
+        "    }\n"
+        "}\n"
+    )
+    assert _format_source(source) == source
+
+
+def test_expression_lambdas_round_trip() -> None:
+    source = (
+        "functio main() {\n"
+        "    constans quodlibet dobro = functio (numerus x) -> numerus => x * 2;\n"
+        "    constans quodlibet soma = functio (a, b) => functio (c) => a + b + c;\n"
+        "    constans quodlibet nada = functio () -> numerus {\n"
+        "        redde 0;\n"
+        "    };\n"
+        "}\n"
+    )
+    formatted = _format_source(source)
+    assert formatted == source
+    assert _format_source(formatted) == formatted
+
+
+def test_doc_comments_survive_fmt() -> None:
+    source = (
+        "/// Valor inicial.\n"
+        "constans numerus base = 1;\n"
+        "\n"
+        "/// Dobra o número recebido.\n"
+        "///\n"
+        "/// Usa `base` como fator.\n"
+        "publicus functio dobra(numerus x) -> numerus {\n"
+        "    redde x * base;\n"
+        "}\n"
+    )
+    assert _format_source(source) == source
+
+    result = CliRunner().invoke(cli, ["fmt"], input=source.replace("///", "  ///", 1))
+    assert result.exit_code == 0
+    assert result.output == source
+
+
+def test_formatter_prints_bitwise_negation() -> None:
+    source = "functio main() {\n    redde [~x, ~~x, ~(a + b), -~x, ~x * 2];\n}\n"
+    assert _format_source(source) == source
+
+
+def test_formatter_parenthesizes_shifts() -> None:
+    source = "functio main() {\n    redde [(a << b) + c, a << b + c, a < b >> c, -x << 2];\n}\n"
+    assert _format_source(source) == source
+
+
+def test_formatter_prints_optional_chains() -> None:
+    source = "functio main() {\n    redde [a?.b.c, a.b?.c(), a?.b[0] ?? 1];\n}\n"
+    assert _format_source(source) == source
+
+
+def test_formatter_prints_optional_indexes() -> None:
+    source = "functio main() {\n    redde [a?[0], a?[0].b, c ? [1] : [2]];\n}\n"
+    assert _format_source(source) == source
+
+
+def test_string_literals_use_the_quote_needing_fewer_escapes() -> None:
+    source = """functio main() {
+    redde ['abc', "it's", 'diz "oi"', "a\\"b'c\\"", 'x\\'y\\n', "\\u00e9\\""];
+}
+"""
+    expected = """functio main() {
+    redde ["abc", "it's", 'diz "oi"', 'a"b\\'c"', "x'y\\n", '\\u00e9"'];
+}
+"""
+    assert _format_source(source) == expected
+    assert _format_source(expected) == expected
+
+
+def test_import_paths_are_escaped() -> None:
+    source = 'importa "a\\"b.stm";\nimporta { f } de "c\\\\d.stm";\nimporta "e.stm" ut e;\n'
+    formatted = _format_source(source)
+    assert formatted == source
+    assert _format_source(formatted) == formatted
+
+
+def test_formatter_keeps_number_suffixes() -> None:
+    source = "functio main() {\n    redde [5i, 5f, 5e3];\n}\n"
+    assert _format_source(source) == source
+
+
+def test_special_float_literals_round_trip_as_keywords() -> None:
+    source = "functio main() {\n    redde [infinitum, -infinitum, nan, -(infinitum ** 2)];\n}\n"
+    assert _format_source(source) == source
+    assert _format_source_minified(source) == "functio main(){redde[infinitum,-infinitum,nan,-(infinitum**2)];}"
+
+
+def test_minified_output_reparses_to_the_same_module() -> None:
+    source = """
+    /// Conta de 1 até n.
+    functio conta(numerus n) -> numerus {
+        mutabilis numerus total = 0;
+        pro i in 1..=n {
+            total = total - -i + ~(i >> 1);
+        }
+        constans quodlibet f = functio (x) => x * 2;
+        redde f(total) + 0.5;
+    }
+
+    constans textus saudacao = "ola, mundo";
+    """
+    module = ScriptumParser().parse(SourceFile("<test>", source))
+    minified = generate(module, CodegenOptions(minify=True)).formatted
+    assert "\n" not in minified
+    assert "functio conta(numerus n)->numerus{" in minified
+    assert "total=total- -i+~(i>>1);" in minified
+    assert 'saudacao="ola, mundo";' in minified
+    assert _format_source(minified) == generate(module).formatted.replace("/// Conta de 1 até n.\n", "")
+
+
+def test_multiple_declarations_are_formatted_one_per_line() -> None:
+    formatted = _format_source("functio f() { mutabilis numerus a = 1, b; }\n")
+    assert formatted == "functio f() {\n    mutabilis numerus a = 1;\n    mutabilis numerus b;\n}\n"
+
+
+def test_loop_labels_round_trip() -> None:
+    source = (
+        "functio main() {\n"
+        "    externo: pro i in 0..3 {\n"
+        "        interno: dum (verum) {\n"
+        "            si (i == 1) {\n"
+        "                perge externo;\n"
+        "            }\n"
+        "            frange interno;\n"
+        "        }\n"
+        "        frange;\n"
+        "    }\n"
+        "}\n"
+    )
+    assert _format_source(source) == source
+    assert "externo:pro i in 0..3{interno:dum(verum){" in _format_source_minified(source)
+
+
+def test_c_style_for_round_trips() -> None:
+    source = (
+        "functio main() {\n"
+        "    externo: pro (mutabilis numerus i = 0; i < 3; i++) {\n"
+        "        pro (;;) {\n"
+        "            frange externo;\n"
+        "        }\n"
+        "    }\n"
+        "}\n"
+    )
+    assert _format_source(source) == source
+    assert "externo:pro(mutabilis numerus i=0;i<3;i++){pro(;;){" in _format_source_minified(source)
+
+
+def test_grouped_and_compound_annotations_round_trip() -> None:
+    source = (
+        "functio demo(entrada: (numerus[])?, numerus n) -> numerus {\n"
+        "    constans valores: (numerus?)[] = [n];\n"
+        "    constans quodlibet q = entrada ut (numerus[])? ?? [];\n"
+        "    redde n;\n"
+        "}\n"
+    )
+    assert _format_source(source) == source
+
+
+def test_object_type_annotations_round_trip() -> None:
+    source = (
+        "functio norma(p: {x: numerus, y: {z: textus?}}) -> {x: numerus} {\n"
+        "    constans pontos: {x: numerus}[] = [p];\n"
+        "    redde p;\n"
+        "}\n"
+    )
+    assert _format_source(source.replace(", y: {", ",y:{")) == source
+
+
+def test_tuples_and_destructuring_round_trip() -> None:
+    source = (
+        "functio divmod(numerus a, numerus b) -> (numerus, {r: (numerus, textus)}) {\n"
+        "    constans (q, r) = (a / b, a % b);\n"
+        "    redde (q, structura { r: (r, \"resto\") });\n"
+        "}\n"
+    )
+    assert _format_source(source.replace("(q, r) =", "( q,r ) =")) == source
@@ -0,0 +1,215 @@
+from __future__ import annotations
+
+import json
+import textwrap
+
+from scriptum.codegen import generate_js_with_map
+from scriptum.codegen.sourcemap import decode_vlq, encode_vlq
+from scriptum.ir import lower_module
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile
+
+SOURCE = textwrap.dedent(
+    """
+    constans numerus base = 2;
+
+    functio dobra(numerus x) -> numerus {
+        redde x * base;
+    }
+
+    functio main() -> numerus {
+        mutabilis numerus total = 0;
+        pro item in [1, 2, 3] {
+            si (item == 2) {
+                perge;
+            }
+            total = total + dobra(item);
+        }
+        redde total;
+    }
+    """
+).lstrip()
+
+
+def _generate() -> tuple[str, dict]:
+    source = SourceFile("programa.stm", SOURCE)
+    module = lower_module(ScriptumParser().parse(source))
+    code, source_map = generate_js_with_map(module, source, file="programa.js")
+    return code, json.loads(source_map)
+
+
+def _decode(mappings: str) -> list[tuple[int, int, int, int]]:
+    decoded = []
+    source_line = source_column = 0
+    for generated_line, line in enumerate(mappings.split(";")):
+        column = 0
+        for segment in filter(None, line.split(",")):
+            delta_column, _, delta_line, delta_source_column = decode_vlq(segment)
+            column += delta_column
+            source_line += delta_line
+            source_column += delta_source_column
+            decoded.append((generated_line, column, source_line, source_column))
+    return decoded
+
+
+def _original_position(needle: str) -> tuple[int, int]:
+    offset = SOURCE.index(needle)
+    line = SOURCE.count("\n", 0, offset)
+    return line, offset - (SOURCE.rfind("\n", 0, offset) + 1)
+
+
+def _generated_position(code: str, needle: str) -> tuple[int, int]:
+    offset = code.index(needle)
+    line = code.count("\n", 0, offset)
+    return line, offset - (code.rfind("\n", 0, offset) + 1)
+
+
+def test_javascript_output() -> None:
+    code, _ = _generate()
+    assert code.startswith("const base = 2;\n\nfunction dobra(x) {\n  return x * base;\n}\n")
+    assert "  for (const item of [1, 2, 3]) {\n    if (item === 2) {\n      continue;\n    }\n" in code
+    assert "total = total + dobra(item);" in code
+
+
+def test_source_map_header() -> None:
+    _, source_map = _generate()
+    assert source_map["version"] == 3
+    assert source_map["file"] == "programa.js"
+    assert source_map["sources"] == ["programa.stm"]
+    assert source_map["sourcesContent"] == [SOURCE]
+
+
+def test_source_map_points_functions_back_to_scriptum() -> None:
+    code, source_map = _generate()
+    mappings = {
+        (line, column): (src_line, src_column)
+        for line, column, src_line, src_column in _decode(source_map["mappings"])
+    }
+    assert mappings[_generated_position(code, "function dobra")] == _original_position("functio dobra")
+    assert mappings[_generated_position(code, "function main")] == _original_position("functio main")
+    assert mappings[_generated_position(code, "return x * base")] == _original_position("redde x * base")
+    assert mappings[_generated_position(code, "dobra(item)")] == _original_position("dobra(item)")
+
+
+def test_vlq_round_trip() -> None:
+    values = [0, 1, -1, 15, -16, 16, 1024, -123456]
+    assert decode_vlq("".join(encode_vlq(value) for value in values)) == values
+    assert encode_vlq(16) == "gB"
+
+
+def test_doc_comments_become_jsdoc() -> None:
+    source = SourceFile("doc.stm", "/// Dobra x.\n///\n/// Sem efeitos.\nfunctio dobra(x) {\n    redde x * 2;\n}\n")
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert code.startswith("/**\n * Dobra x.\n *\n * Sem efeitos.\n */\nfunction dobra(x) {\n")
+
+
+def test_doc_comments_cannot_close_the_jsdoc_block() -> None:
+    source = SourceFile("doc.stm", "/// Casa a/*/b e */.\nfunctio f() {}\n")
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert code.startswith("/**\n * Casa a/*\\/b e *\\/.\n */\nfunction f() {\n")
+
+
+def test_loop_labels_are_kept() -> None:
+    text = "functio main() {\n    externo: dum (verum) {\n        frange externo;\n    }\n}\n"
+    source = SourceFile("rotulos.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "externo: while (true) {\n    break externo;\n  }" in code
+
+
+def test_c_style_for_becomes_a_for_loop() -> None:
+    text = (
+        "functio main() {\n    pro (mutabilis i = 0; i < 3; i++) {\n        perge;\n    }\n"
+        "    pro (;;) {\n        frange;\n    }\n}\n"
+    )
+    source = SourceFile("pro.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "for (let i = 0; i < 3; i++) {\n    continue;\n  }\n  for (;;) {\n    break;\n  }" in code
+
+
+def test_optional_chaining_is_kept() -> None:
+    text = "functio ler(p: {dados: {x: numerus}}?) {\n    redde p?.dados.x ?? 0;\n}\n"
+    source = SourceFile("opcional.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "return (p?.dados.x ?? null) ?? 0;" in code
+
+
+def test_optional_chain_compares_equal_to_nullum() -> None:
+    text = (
+        "functio main() {\n    constans q: {a: numerus, f: () -> numerus[]}? = nullum;\n"
+        "    redde [q?.a == nullum, q?.f()[0] == nullum, [q?.a]];\n}\n"
+    )
+    source = SourceFile("opcional.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    index = '(function (t, i) { return t == null ? null : typeof t === "string" ? Array.from(t)[i()] : t[i()]; })'
+    assert f"return [(q?.a ?? null) === null, {index}(q?.f(), () => 0) === null, [(q?.a ?? null)]];" in code
+
+
+def test_links_after_an_index_in_an_optional_chain_stay_optional() -> None:
+    text = "functio ler(q: quodlibet) {\n    redde q?.itens[0].nome;\n}\n"
+    source = SourceFile("opcional.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "return ((function (t, i) {" in code
+    assert "})(q?.itens, () => 0)?.nome ?? null);" in code
+
+
+def test_optional_index_is_null_for_a_null_collection() -> None:
+    text = "functio ler(a: numerus[]?, t: textus?) {\n    redde [a?[0], t?[0..2]];\n}\n"
+    source = SourceFile("indice_opcional.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert 'return t == null ? null : typeof t === "string" ? Array.from(t)[i()] : t[i()]; })(a, () => 0)' in code
+    assert "return t == null ? null : Array.from(t).slice(s(), e()).join(\"\"); })(t, () => 0, () => 2)" in code
+
+
+def test_number_suffixes_are_dropped() -> None:
+    source = SourceFile("sufixos.stm", "functio main() {\n    redde [5i, 2.5f, 5e3];\n}\n")
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "return [5, 2.5, 5e3];" in code
+
+
+def test_special_float_literals_become_javascript_globals() -> None:
+    source = SourceFile("especiais.stm", "functio main() {\n    redde [infinitum, -infinitum, nan];\n}\n")
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "return [Infinity, -Infinity, NaN];" in code
+
+
+def test_tuples_become_arrays() -> None:
+    text = "functio main() {\n    mutabilis (q, r) = (1, 2);\n    redde (r, q);\n}\n"
+    source = SourceFile("tuplas.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "let [q, r] = [1, 2];\n  return [r, q];" in code
+
+
+def test_textus_indexes_and_slices_count_code_points() -> None:
+    text = "functio main() {\n    constans textus s = \"😀ab\";\n    redde [s[1], s[1..=2]];\n}\n"
+    source = SourceFile("fatias.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    index = '(function (t, i) { return typeof t === "string" ? Array.from(t)[i] : t[i]; })'
+    helper = '(function (t, s, e) { return Array.from(t).slice(s, e + 1).join(""); })'
+    assert f"return [{index}(s, 1), {helper}(s, 1, 2)];" in code
+
+
+def test_object_keys_that_are_not_identifiers_are_quoted() -> None:
+    text = 'functio main() {\n    redde structura { "com-traço": 1, "123": 2, "nome": 3 };\n}\n'
+    source = SourceFile("chaves.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert 'return { "com-traço": 1, "123": 2, nome: 3 };' in code
+
+
+def test_methods_become_functions_bound_to_this() -> None:
+    text = "functio main() {\n    redde structura { n: 2, dobro: functio () => ipse.n * 2, f: functio () => 1 };\n}\n"
+    source = SourceFile("metodos.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert "dobro: function () {\n    return this.n * 2;\n  }, f: function () {\n    return 1;\n  }" in code
+
+
+def test_imprime_is_defined_on_top_of_console_log_when_called() -> None:
+    text = 'functio main() {\n    imprime("oi", 42);\n}\n'
+    source = SourceFile("imprime.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert code.startswith("function imprime(...valores) {\n  console.log(...valores);\n}\n\nfunction main() {")
+    assert code.count("function imprime") == 1
+
+    text = "functio imprime(x) {\n    redde x;\n}\n\nfunctio main() {\n    redde imprime(1);\n}\n"
+    source = SourceFile("proprio.stm", text)
+    code, _ = generate_js_with_map(lower_module(ScriptumParser().parse(source)), source)
+    assert code.count("function imprime") == 1 and "console.log" not in code
@@ -0,0 +1,118 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from scriptum.cli import cli
+from scriptum.codegen import CodegenOptions, generate
+from scriptum.config import ProjectConfig, find_config, load_config
+from scriptum.errors import ConfigError
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile
+
+EXAMPLE = Path(__file__).resolve().parent / "fixtures" / "config" / "scriptum.toml"
+
+
+def test_example_config_is_read() -> None:
+    config = load_config(EXAMPLE)
+    assert config == ProjectConfig(path=EXAMPLE, indent="\t", max_width=60, strict_any=True, target="js")
+    assert config.codegen_options() == CodegenOptions(indent="\t", max_width=60)
+    assert config.analyzer_options().strict_any
+    assert not config.analyzer_options().strict_params
+
+
+def test_config_is_found_in_ancestor_directories(tmp_path: Path) -> None:
+    nested = tmp_path / "a" / "b"
+    nested.mkdir(parents=True)
+    assert find_config(nested) is None
+    (tmp_path / "scriptum.toml").write_text("[format]\nindent = 2\n", encoding="utf8")
+    assert find_config(nested) == (tmp_path / "scriptum.toml").resolve()
+    assert load_config(tmp_path / "scriptum.toml").indent == "  "
+
+
+@pytest.mark.parametrize(
+    ("text", "message"),
+    [
+        ("[format]\nindnt = 2\n", "unknown setting 'format.indnt'"),
+        ("[estilo]\nindent = 2\n", "unknown section 'estilo'"),
+        ("[format]\nindent = 12\n", "Indentation must be a number of spaces from 1 to 8 or 'tab'"),
+        ("[format]\nmax_width = 0\n", "'format.max_width' must be a positive integer"),
+        ("[check]\nstrict_any = 1\n", "'check.strict_any' must be true or false"),
+        ("[build]\ntarget = \"wasm\"\n", "'build.target' must be one of scriptum, js"),
+        ("[format\n", "is not valid TOML"),
+    ],
+)
+def test_invalid_settings_name_the_file_and_key(tmp_path: Path, text: str, message: str) -> None:
+    path = tmp_path / "scriptum.toml"
+    path.write_text(text, encoding="utf8")
+    with pytest.raises(ConfigError, match=message) as info:
+        load_config(path)
+    assert str(path) in str(info.value)
+
+
+def test_max_width_breaks_the_outermost_list() -> None:
+    source = "functio main() {\n    redde calcula(primeiro, [1, 2, 3], segundo);\n}\n"
+    module = ScriptumParser().parse(SourceFile("<test>", source))
+    assert generate(module, CodegenOptions(max_width=50)).formatted == source
+    assert generate(module, CodegenOptions(indent="  ", max_width=30)).formatted == (
+        "functio main() {\n"
+        "  redde calcula(\n"
+        "    primeiro,\n"
+        "    [1, 2, 3],\n"
+        "    segundo\n"
+        "  );\n"
+        "}\n"
+    )
+
+
+def test_cli_uses_config_defaults_and_flags_take_precedence(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    (tmp_path / "scriptum.toml").write_text(EXAMPLE.read_text(encoding="utf8"), encoding="utf8")
+    nested = tmp_path / "src"
+    nested.mkdir()
+    program = nested / "main.stm"
+    program.write_text(
+        "functio eco(quodlibet x) {\n    redde x;\n}\n\nfunctio main() {\n    redde eco(1);\n}\n", encoding="utf8"
+    )
+    monkeypatch.chdir(nested)
+    runner = CliRunner()
+
+    strict = runner.invoke(cli, ["check", str(program)])
+    assert strict.exit_code != 0
+    assert "T210" in strict.output
+    relaxed = runner.invoke(cli, ["check", "--no-strict-any", str(program)])
+    assert relaxed.exit_code == 0, relaxed.output
+
+    program.write_text("functio main() {\n    redde 1;\n}\n", encoding="utf8")
+    compiled = runner.invoke(cli, ["compile", str(program)])
+    assert compiled.output.startswith("function main() {")
+    assert runner.invoke(cli, ["compile", "--emit", "code", str(program)]).output.startswith("functio main() {")
+
+    formatted = runner.invoke(cli, ["fmt", str(program)])
+    assert formatted.exit_code == 0, formatted.output
+    assert program.read_text(encoding="utf8") == "functio main() {\n\tredde 1;\n}\n"
+    runner.invoke(cli, ["fmt", "--indent", "4", str(program)])
+    assert program.read_text(encoding="utf8") == "functio main() {\n    redde 1;\n}\n"
+
+    explicit = tmp_path / "outro.toml"
+    explicit.write_text("[format]\nindnt = 4\n", encoding="utf8")
+    broken = runner.invoke(cli, ["--config", str(explicit), "fmt", str(program)])
+    assert broken.exit_code != 0
+    assert "unknown setting 'format.indnt'" in broken.output
+
+
+def test_broken_config_only_fails_commands_that_read_it(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    (tmp_path / "scriptum.toml").write_text("[format]\nindnt = 4\n", encoding="utf8")
+    nested = tmp_path / "src"
+    nested.mkdir()
+    monkeypatch.chdir(nested)
+    runner = CliRunner()
+
+    version = runner.invoke(cli, ["version"])
+    assert version.exit_code == 0, version.output
+    program = nested / "main.stm"
+    program.write_text("functio main() {\n    redde 1;\n}\n", encoding="utf8")
+    checked = runner.invoke(cli, ["check", str(program)])
+    assert checked.exit_code != 0
+    assert "unknown setting 'format.indnt'" in checked.output
@@ -0,0 +1,58 @@
+from __future__ import annotations
+
+import pytest
+
+from scriptum.diagnostics import collect_diagnostics
+from scriptum.errors import LexerError, ModuleImportError
+from scriptum.parser.parser import ParseError, ScriptumParser
+from scriptum.sema.analyzer import SemanticAnalyzer, Severity
+from scriptum.text import SourceFile, Span
+
+
+def _parse_error(text: str) -> ParseError:
+    with pytest.raises(ParseError) as info:
+        ScriptumParser().parse(SourceFile("<test>", text))
+    return info.value
+
+
+def _semantic(text: str):
+    return SemanticAnalyzer().analyze(ScriptumParser().parse(SourceFile("<test>", text)))
+
+
+def test_parse_and_semantic_diagnostics_are_merged_in_source_order() -> None:
+    # An editor keeps the checked version of a file around while the user breaks its syntax.
+    semantic = _semantic(
+        "functio f() -> numerus {\n"
+        "    constans textus t = 1;\n"
+        "    si (1 == \"a\") { redde 1; }\n"
+        "    redde verum;\n"
+        "}\n"
+    )
+    syntax = _parse_error("functio f() -> numerus {\n    redde (1;\n}\n")
+
+    merged = collect_diagnostics(syntax, semantic)
+
+    assert [(diag.code, diag.severity) for diag in merged] == [
+        ("T200", Severity.ERROR),
+        (None, Severity.ERROR),
+        ("W101", Severity.WARNING),
+        ("T010", Severity.ERROR),
+    ]
+    starts = [diag.span.start for diag in merged]
+    assert starts == sorted(starts)
+    assert merged[1].message.startswith("Expected ')' after expression.")
+    assert merged[1].notes == (("'(' opened here", Span(35, 36)),)
+
+
+def test_errors_precede_warnings_at_the_same_span_and_spanless_come_first() -> None:
+    semantic = _semantic('functio f() {\n    redde 1 == "a";\n}\n')
+    (warning,) = semantic
+    lexer_error = LexerError("Unexpected character", span=Span(0, 40), anchor=warning.span)
+
+    merged = collect_diagnostics(lexer_error, semantic)
+    assert [(diag.code, diag.span) for diag in merged] == [(None, warning.span), ("W101", warning.span)]
+
+    spanless = collect_diagnostics(ModuleImportError("Unable to read imported module 'x.stm'"), semantic)
+    assert [diag.code for diag in spanless] == [None, "W101"]
+    located = collect_diagnostics(ModuleImportError("Import cycle detected"), semantic, error_span=Span(99, 100))
+    assert [diag.code for diag in located] == ["W101", None]
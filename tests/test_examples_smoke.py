@@ -1,11 +1,14 @@
 from __future__ import annotations
 
 import ast
+import re
 from pathlib import Path
 
 import pytest
 
-from scriptum.driver import CompilerDriver, Stage
+from scriptum.driver import CompilerDriver, DriverConfig, Stage
+from scriptum.sema.analyzer import AnalyzerOptions
+from scriptum.sema.codes import DIAGNOSTIC_CODES
 
 OK_DIR = Path("examples/ok")
 ERR_DIR = Path("examples/err")
@@ -29,6 +32,19 @@ def _expect_errors(path: Path) -> list[str]:
     raise AssertionError(f"Missing // ERROR: marker in {path}")
 
 
+def _analyze_err(path: Path) -> set[str]:
+    """Codes reported for *path*, honouring a `// OPTIONS: strict_any, ...` marker."""
+
+    flags: dict[str, bool] = {}
+    for line in path.read_text(encoding="utf8").splitlines():
+        stripped = line.strip()
+        if stripped.startswith("// OPTIONS:"):
+            flags = {name.strip(): True for name in stripped.split(":", 1)[1].split(",") if name.strip()}
+    driver = CompilerDriver(DriverConfig(analyzer=AnalyzerOptions(**flags)))
+    result = driver.run(path, until=Stage.SEMANTIC)
+    return {diag.code for diag in result.diagnostics or []}
+
+
 @pytest.mark.parametrize("program", sorted(OK_DIR.glob("*.stm")))
 def test_examples_ok_run(program: Path) -> None:
     expected = _expect_value(program)
@@ -38,11 +54,21 @@ def test_examples_ok_run(program: Path) -> None:
     assert result.execution.value == expected
 
 
-@pytest.mark.parametrize("program", sorted(ERR_DIR.glob("*.stm")))
+@pytest.mark.parametrize("program", sorted(ERR_DIR.rglob("*.stm")))
 def test_examples_err_semantics(program: Path) -> None:
     expected_codes = set(_expect_errors(program))
-    driver = CompilerDriver()
-    result = driver.run(program, until=Stage.SEMANTIC)
-    diagnostics = result.diagnostics or []
-    diag_codes = {diag.code for diag in diagnostics}
-    assert expected_codes.issubset(diag_codes)
+    assert expected_codes.issubset(_analyze_err(program))
+
+
+def test_examples_err_cover_every_diagnostic_code() -> None:
+    emitted: set[str] = set()
+    for program in sorted(ERR_DIR.rglob("*.stm")):
+        emitted |= _analyze_err(program)
+    missing = sorted(set(DIAGNOSTIC_CODES) - emitted)
+    assert not missing, f"No example in {ERR_DIR} reports: {', '.join(missing)}"
+
+
+def test_diagnostic_catalogue_lists_every_reported_code() -> None:
+    source = Path("src/scriptum/sema/analyzer.py").read_text(encoding="utf8")
+    reported = set(re.findall(r'"([STW]\d{3})"', source))
+    assert sorted(reported - set(DIAGNOSTIC_CODES)) == []
@@ -0,0 +1,104 @@
+from __future__ import annotations
+
+import textwrap
+from typing import Dict
+
+import pytest
+
+from scriptum.errors import LexerError
+from scriptum.highlight import TokenClass, classify
+
+SOURCE = textwrap.dedent(
+    """\
+    // soma os quadrados
+    functio quadrado<T>(n: numerus, marca: T) -> numerus {
+        redde n * n;
+    }
+
+    functio total(valores: [numerus]) -> numerus {
+        mutabilis numerus soma = 0;
+        pro v in valores {
+            soma = soma + quadrado(v, "x");
+        }
+        constans ponto = structura {x: soma};
+        constans dobra = functio (k) { redde k * 2; };
+        redde dobra(ponto.x);
+    }
+    """
+)
+
+
+def _classes(source: str) -> Dict[str, set]:
+    found: Dict[str, set] = {}
+    for token in classify(source):
+        found.setdefault(token.lexeme, set()).add(token.kind)
+    return found
+
+
+def test_tokens_are_classified_by_what_they_name() -> None:
+    classes = _classes(SOURCE)
+    assert classes["functio"] == {TokenClass.KEYWORD}
+    assert classes["numerus"] == {TokenClass.TYPE}
+    assert classes["T"] == {TokenClass.TYPE}
+    assert classes["quadrado"] == {TokenClass.FUNCTION}
+    assert classes["n"] == {TokenClass.PARAMETER}
+    assert classes["valores"] == {TokenClass.PARAMETER}
+    assert classes["k"] == {TokenClass.PARAMETER}
+    assert classes["soma"] == {TokenClass.VARIABLE}
+    assert classes["v"] == {TokenClass.VARIABLE}
+    # A local holding a lambda is still a variable; only declared functions are functions.
+    assert classes["dobra"] == {TokenClass.VARIABLE}
+    assert classes["x"] == {TokenClass.PROPERTY}
+    assert classes["0"] == {TokenClass.NUMBER}
+    assert classes['"x"'] == {TokenClass.STRING}
+    assert classes["// soma os quadrados"] == {TokenClass.COMMENT}
+    assert classes["*"] == {TokenClass.OPERATOR}
+    assert "(" not in classes and ";" not in classes
+
+
+def test_tokens_come_in_source_order_with_their_spans() -> None:
+    classified = classify(SOURCE)
+    assert [token.span.start for token in classified] == sorted(token.span.start for token in classified)
+    assert all(SOURCE[token.span.start : token.span.end] == token.lexeme for token in classified)
+
+
+def test_local_names_shadow_functions() -> None:
+    classes = _classes("functio f(g) {\n    redde g;\n}\nfunctio g() {\n    redde f(1);\n}\n")
+    assert classes["g"] == {TokenClass.PARAMETER, TokenClass.FUNCTION}
+    assert classes["f"] == {TokenClass.FUNCTION}
+
+
+def test_parameters_with_a_prefix_type_are_parameters() -> None:
+    classes = _classes(
+        "functio f(numerus x, Ponto p) {\n    constans g = functio (textus t) => t;\n    redde x + p.x;\n}\n"
+    )
+    assert classes["x"] == {TokenClass.PARAMETER, TokenClass.PROPERTY}
+    assert classes["p"] == {TokenClass.PARAMETER}
+    assert classes["t"] == {TokenClass.PARAMETER}
+    assert classes["Ponto"] == {TokenClass.TYPE}
+
+
+def test_builtins_are_functions_unless_shadowed() -> None:
+    assert _classes('functio f() {\n    imprime("oi");\n}\n')["imprime"] == {TokenClass.FUNCTION}
+    assert _classes("functio f(imprime) {\n    redde imprime;\n}\n")["imprime"] == {TokenClass.PARAMETER}
+
+
+def test_keywords_after_a_dot_are_properties() -> None:
+    classes = _classes('functio f(obj) {\n    redde obj.redde ?? obj?.numerus;\n}\n')
+    assert classes["redde"] == {TokenClass.KEYWORD, TokenClass.PROPERTY}
+    assert classes["numerus"] == {TokenClass.PROPERTY}
+
+
+def test_quoted_object_keys_are_strings() -> None:
+    classes = _classes('constans x = 1;\nconstans o = structura { "a-b": x, c: x };\n')
+    assert classes['"a-b"'] == {TokenClass.STRING}
+    assert classes["c"] == {TokenClass.PROPERTY}
+    assert classes["x"] == {TokenClass.VARIABLE}
+
+
+def test_unparsable_source_falls_back_to_token_classes() -> None:
+    classes = _classes("functio f(n) {\n    redde n +;\n}\n")
+    assert classes["functio"] == {TokenClass.KEYWORD}
+    assert classes["n"] == {TokenClass.VARIABLE}
+    with pytest.raises(LexerError):
+        classify('constans x = "aberto;\n')
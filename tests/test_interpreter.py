@@ -0,0 +1,508 @@
+from __future__ import annotations
+
+import io
+import math
+import re
+import textwrap
+
+import pytest
+
+from scriptum.errors import ExecutionError
+from scriptum.ir import lower_module
+from scriptum.ir.interpreter import Interpreter, NumericRange
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile
+
+
+def _run(source: str):
+    module = ScriptumParser().parse(SourceFile("<test>", textwrap.dedent(source)))
+    return Interpreter(lower_module(module)).execute().value
+
+
+def test_comparisons_produce_booleans() -> None:
+    assert _run("functio main() { redde [1 < 2, 2 == 3, !(1 >= 2)]; }") == [True, False, True]
+
+
+def test_conditions_select_branches() -> None:
+    source = """
+        functio main() -> numerus {
+            mutabilis numerus total = 0;
+            mutabilis numerus i = 0;
+            dum (i < 5) {
+                si (i % 2 == 0 && i != 4) {
+                    total = total + i;
+                } aliter {
+                    total = total + 100;
+                }
+                i = i + 1;
+            }
+            redde total > 200 ? total : 0;
+        }
+    """
+    assert _run(source) == 302
+
+
+def test_booleans_are_not_equal_to_numbers() -> None:
+    source = """
+        functio main() {
+            constans quodlibet um = 1;
+            redde [verum == um, falsum != 0];
+        }
+    """
+    assert _run(source) == [False, True]
+
+
+@pytest.mark.parametrize(
+    "body, construct",
+    [
+        ("si (n) { redde 1; }", "si"),
+        ("dum (n) { frange; }", "dum"),
+        ("redde n ? 1 : 2;", "?:"),
+        ("redde verum && n;", "&&"),
+        ("redde !n;", "!"),
+    ],
+)
+def test_non_boolean_condition_is_a_runtime_error(body: str, construct: str) -> None:
+    source = f"functio main() {{ constans quodlibet n = 1; {body} }}"
+    message = f"Condition for '{construct}' must be booleanum, got numerus"
+    with pytest.raises(ExecutionError, match=re.escape(message)):
+        _run(source)
+
+
+def test_update_expressions_modify_locals_in_place() -> None:
+    source = """
+        functio main() {
+            mutabilis numerus i = 5;
+            constans numerus antes = i++;
+            constans numerus depois = ++i;
+            i--;
+            redde [antes, depois, i];
+        }
+    """
+    assert _run(source) == [5, 7, 6]
+
+
+def test_range_loop_sums_without_materialising(monkeypatch) -> None:
+    seen = []
+    original = Interpreter._ensure_iterable
+
+    def spy(self, value):
+        iterable = original(self, value)
+        seen.append(iterable)
+        return iterable
+
+    monkeypatch.setattr(Interpreter, "_ensure_iterable", spy)
+    source = """
+        functio main() -> numerus {
+            mutabilis numerus total = 0;
+            pro i in 0..1000000 {
+                total = total + i;
+            }
+            redde total;
+        }
+    """
+    assert _run(source) == 499999500000
+    assert seen == [NumericRange(0, 1000000)]
+
+
+@pytest.mark.parametrize(
+    ("operator", "expected"),
+    [("..", [3, 4]), ("..=", [4, 5])],
+)
+def test_range_end_is_included_only_by_inclusive_operator(operator: str, expected: list) -> None:
+    source = f"""
+        functio main() {{
+            mutabilis numerus passos = 0;
+            mutabilis numerus ultimo = 0;
+            pro i in 2{operator}5 {{
+                passos = passos + 1;
+                ultimo = i;
+            }}
+            redde [passos, ultimo];
+        }}
+    """
+    assert _run(source) == expected
+
+
+def test_empty_and_single_element_ranges() -> None:
+    source = """
+        functio contar(quodlibet intervalo) -> numerus {
+            mutabilis numerus total = 0;
+            pro i in intervalo {
+                total = total + 1;
+            }
+            redde total;
+        }
+
+        functio main() {
+            redde [contar(3..3), contar(3..=3), contar(4..=3), contar(0..=2.5)];
+        }
+    """
+    assert _run(source) == [0, 1, 0, 3]
+
+
+def test_cast_checks_primitive_values_at_runtime() -> None:
+    source = """
+        functio ler(quodlibet valor) -> numerus {
+            redde valor ut numerus;
+        }
+
+        functio main() {
+            redde ler("sete");
+        }
+    """
+    with pytest.raises(ExecutionError, match=re.escape("Cannot cast textus to numerus.")):
+        _run(source)
+    assert _run("functio main() { redde [1 ut numerus, nullum ut textus?]; }") == [1, None]
+
+
+def test_logical_operators_skip_the_right_side_when_the_left_decides() -> None:
+    source = """
+        mutabilis numerus chamadas = 0;
+
+        functio marca(booleanum valor) -> booleanum {
+            chamadas = chamadas + 1;
+            redde valor;
+        }
+
+        functio main() {
+            constans quodlibet texto = "nao e numero";
+            constans resultados = [
+                verum || (texto ut numerus) > 0,
+                falsum && (texto ut numerus) > 0,
+                1 ?? texto ut numerus,
+                marca(falsum) && marca(verum),
+                marca(verum) || marca(falsum),
+                marca(verum) && marca(falsum)
+            ];
+            redde [resultados, chamadas];
+        }
+    """
+    assert _run(source) == [[True, False, 1, False, True, False], 4]
+    with pytest.raises(ExecutionError, match="Cannot cast textus to numerus"):
+        _run('functio main() { constans quodlibet t = "a"; redde falsum || (t ut numerus) > 0; }')
+
+
+def test_closure_counter_keeps_its_own_captured_state() -> None:
+    source = """
+        functio fabrica() {
+            mutabilis numerus contagem = 0;
+            redde functio () {
+                contagem = contagem + 1;
+                redde contagem;
+            };
+        }
+
+        functio main() {
+            constans quodlibet a = fabrica();
+            constans quodlibet b = fabrica();
+            a();
+            a();
+            b();
+            redde [a(), b()];
+        }
+    """
+    assert _run(source) == [3, 2]
+
+
+def test_closures_created_in_loop_capture_each_element() -> None:
+    source = """
+        functio main() {
+            mutabilis quodlibet primeira = nullum;
+            mutabilis quodlibet ultima = nullum;
+            pro item in [1, 2, 3] {
+                constans quodlibet f = functio () => item * 10;
+                si (item == 1) {
+                    primeira = f;
+                }
+                ultima = f;
+            }
+            redde [primeira(), ultima()];
+        }
+    """
+    assert _run(source) == [10, 30]
+
+
+def test_closures_created_in_c_style_loop_capture_each_iteration() -> None:
+    source = """
+        functio main() {
+            mutabilis quodlibet primeira = nullum;
+            mutabilis quodlibet ultima = nullum;
+            pro (mutabilis numerus i = 0; i < 2; i++) {
+                constans quodlibet f = functio () => i;
+                si (i == 0) {
+                    primeira = f;
+                }
+                ultima = f;
+            }
+            redde [primeira(), ultima()];
+        }
+    """
+    assert _run(source) == [0, 1]
+
+
+def test_methods_read_fields_of_their_object_through_ipse() -> None:
+    source = """
+        functio main() {
+            constans contador = structura {
+                passo: 5,
+                proximo: functio (numerus n) -> numerus { redde n + ipse.passo; },
+                dobro: functio (numerus n) => ipse.proximo(n) * 2
+            };
+            constans outro = structura { passo: 1, proximo: contador.proximo };
+            redde [contador.proximo(1), contador.dobro(1), outro.proximo(1)];
+        }
+    """
+    # `ipse` is the object the method is called on, not the one it was written in.
+    assert _run(source) == [6, 12, 2]
+
+
+def test_bitwise_negation_uses_32_bit_integers() -> None:
+    assert _run("functio main() { redde [~5, ~-1, ~~7, ~2.7, ~4294967296, ~2147483647]; }") == [
+        -6,
+        0,
+        7,
+        -3,
+        -1,
+        -2147483648,
+    ]
+
+
+def test_bitwise_negation_rejects_non_numbers_at_runtime() -> None:
+    with pytest.raises(ExecutionError, match="Operator '~' requires numerus, got textus"):
+        _run('functio main() { constans quodlibet x = "a"; redde ~x; }')
+
+
+def test_shifts_follow_32_bit_semantics() -> None:
+    source = "functio main() { redde [1 << 4, -16 >> 2, 1 << 31, 1 << 33, 7.9 >> 1, 5 << 1.5]; }"
+    assert _run(source) == [16, -4, -2147483648, 2, 3, 10]
+
+
+def test_shifts_reject_non_numbers_at_runtime() -> None:
+    with pytest.raises(ExecutionError, match="Operator '<<' requires numerus, got booleanum"):
+        _run("functio main() { constans quodlibet x = verum; redde x << 1; }")
+    with pytest.raises(ExecutionError, match="Operator '>>' requires numerus, got textus"):
+        _run('functio main() { constans quodlibet x = "a"; redde 1 >> x; }')
+
+
+def test_special_float_literals_and_division_by_zero() -> None:
+    values = _run("functio main() { redde [infinitum, -infinitum, nan, 1 / 0, -1 / 0, 0 / 0, 5 % 0, 1 / infinitum]; }")
+    assert values[:2] == [math.inf, -math.inf]
+    assert math.isnan(values[2])
+    assert values[3:5] == [math.inf, -math.inf]
+    assert all(math.isnan(value) for value in values[5:7])
+    assert values[7] == 0
+    assert _run("functio main() { redde [nan == nan, nan != nan, infinitum > 1e308]; }") == [False, True, True]
+
+
+def test_remainder_takes_the_sign_of_the_dividend() -> None:
+    values = _run("functio main() { redde [-7 % 3, 7 % -3, -5 % infinitum, 5.5 % -2, -7.5 % 2, infinitum % 2]; }")
+    assert values[:5] == [-1, 1, -5, 1.5, -1.5]
+    assert math.isnan(values[5])
+
+
+def test_power_accepts_negative_and_fractional_exponents() -> None:
+    source = "functio main() { redde [2 ** -1, 9 ** 0.5, 2 ** 10, 4 ** -0.5, 0.25 ** 0.5, 2 ** 3 ** 2]; }"
+    assert _run(source) == [0.5, 3, 1024, 0.5, 0.5, 512]
+
+
+def test_power_follows_math_pow_where_python_would_raise() -> None:
+    source = "functio main() { redde [0 ** -1, (-8) ** (1 / 3), 10 ** 400.5]; }"
+    zero_inverse, negative_root, overflow = _run(source)
+    assert zero_inverse == math.inf
+    assert math.isnan(negative_root)
+    assert overflow == math.inf
+
+
+def test_power_is_exact_only_up_to_2_53() -> None:
+    source = "functio main() { redde [3 ** 33, 2 ** 53, 2 ** 60, 2 ** 100000, 2 ** 1000000000, (-2) ** 1025]; }"
+    assert _run(source) == [5559060566555523, 2**53, 2.0**60, math.inf, math.inf, -math.inf]
+    assert [type(value) for value in _run(source)[:3]] == [int, int, float]
+
+
+def test_labelled_jumps_target_the_named_loop() -> None:
+    source = """
+        functio main() {
+            mutabilis pares = [];
+            mutabilis numerus passos = 0;
+            externo: pro i in 0..4 {
+                mutabilis numerus j = 0;
+                dum (verum) {
+                    j = j + 1;
+                    passos = passos + 1;
+                    si (j > i) {
+                        perge externo;
+                    }
+                    si (i == 3) {
+                        frange externo;
+                    }
+                }
+            }
+            redde passos;
+        }
+    """
+    assert _run(source) == 1 + 2 + 3 + 1
+
+
+def test_perge_targets_the_innermost_loop_unless_labelled() -> None:
+    source = """
+        functio main() {
+            mutabilis pares = [];
+            externo: pro (mutabilis numerus i = 0; i < 3; i++) {
+                mutabilis numerus j = -1;
+                dum (j < 3) {
+                    j++;
+                    si (j == 1) {
+                        perge;
+                    }
+                    si (j > i) {
+                        perge externo;
+                    }
+                    pares = pares + [[i, j]];
+                }
+                pares = pares + [["fim", i]];
+            }
+            redde pares;
+        }
+    """
+    assert _run(source) == [[0, 0], [1, 0], [2, 0], [2, 2]]
+
+
+def test_c_style_for_runs_its_update_after_perge() -> None:
+    source = """
+        functio main() {
+            mutabilis vistos = [];
+            pro (mutabilis numerus i = 0; i < 5; i++) {
+                si (i % 2 == 0) {
+                    perge;
+                }
+                vistos = vistos + [i];
+            }
+            mutabilis numerus passos = 0;
+            pro (;;) {
+                passos++;
+                si (passos == 3) {
+                    frange;
+                }
+            }
+            redde [vistos, passos];
+        }
+    """
+    assert _run(source) == [[1, 3], 3]
+
+
+def test_optional_member_short_circuits_the_whole_chain() -> None:
+    source = """
+        mutabilis numerus chamadas = 0;
+
+        functio conta() -> numerus {
+            chamadas = chamadas + 1;
+            redde chamadas;
+        }
+
+        functio main() {
+            constans vazio: {dados: {itens: numerus[]}}? = nullum;
+            constans cheio: {dados: {itens: numerus[]}}? = structura { dados: structura { itens: [4, 5] } };
+            redde [
+                vazio?.dados.itens[conta()],
+                cheio?.dados.itens[conta()],
+                vazio?.dados?.itens ?? [],
+                chamadas
+            ];
+        }
+    """
+    assert _run(source) == [None, 5, [], 1]
+    with pytest.raises(ExecutionError, match="Member access requires an object literal"):
+        _run("functio main() { constans quodlibet vazio = nullum; redde vazio.dados?.x; }")
+
+
+def test_optional_index_is_nullum_for_a_nullum_collection() -> None:
+    source = """
+        mutabilis numerus chamadas = 0;
+
+        functio conta() -> numerus {
+            chamadas = chamadas + 1;
+            redde 0;
+        }
+
+        functio main() {
+            constans vazio: numerus[]? = nullum;
+            constans cheio: numerus[]? = [4, 5];
+            constans nome: textus? = nullum;
+            redde [vazio?[conta()], cheio?[conta()], nome?[0..2], vazio?[0] ?? -1, chamadas];
+        }
+    """
+    assert _run(source) == [None, 4, None, -1, 1]
+
+
+def test_tuples_pack_and_destructure_by_position() -> None:
+    source = """
+    functio divmod(numerus a, numerus b) -> (numerus, numerus) {
+        redde ((a - a % b) / b, a % b);
+    }
+
+    functio main() {
+        constans (q, r) = divmod(17, 5);
+        mutabilis (primeiro, segundo) = [q, r];
+        constans t = divmod(9, 2);
+        redde [q, r, segundo, t[0], t[1]];
+    }
+    """
+    assert _run(source) == [3, 2, 2, 4, 1]
+
+
+def test_textus_indexes_and_slices_count_characters() -> None:
+    source = """
+    functio main() {
+        constans textus s = "olá🙂mundo";
+        constans numerus n = 4;
+        redde [s[0], s[2], s[3], s[0..3], s[n..=8], s[n..n], s[4.0]];
+    }
+    """
+    assert _run(source) == ["o", "á", "🙂", "olá", "mundo", "", "m"]
+
+
+@pytest.mark.parametrize(
+    "index, message",
+    [
+        ("3", "Index 3 is out of range for a textus of length 3."),
+        ("-1", "Index -1 is out of range for a textus of length 3."),
+        ("1..4", "Slice 1..4 is out of range for a textus of length 3."),
+        ("2..=3", "Slice 2..=3 is out of range for a textus of length 3."),
+        ("2..1", "Slice 2..1 is out of range for a textus of length 3."),
+        ("0.5", "A textus is indexed by a whole numerus or a range, got 0.5."),
+    ],
+)
+def test_textus_index_out_of_range_is_a_runtime_error(index: str, message: str) -> None:
+    with pytest.raises(ExecutionError, match=re.escape(message)):
+        _run(f'functio main() {{ constans textus s = "abc"; redde s[{index}]; }}')
+
+
+def test_destructuring_the_wrong_number_of_values_is_a_runtime_error() -> None:
+    with pytest.raises(ExecutionError, match="Cannot destructure 3 values into 2 names"):
+        _run("functio main() { constans (a, b) = [1, 2, 3]; redde a; }")
+
+
+def test_imprime_writes_its_arguments_separated_by_spaces() -> None:
+    source = """
+    functio main() {
+        imprime("oi", 42);
+        imprime((1, "um"));
+        imprime();
+        imprime([1, "a", verum], structura { x: 1.5, "y-z": nullum }, 0..3, 2.5 * 2, nan, -infinitum);
+        redde imprime("fim");
+    }
+    """
+    module = ScriptumParser().parse(SourceFile("<test>", textwrap.dedent(source)))
+    output = io.StringIO()
+    assert Interpreter(lower_module(module), output=output).execute().value is None
+    assert output.getvalue() == 'oi 42\n[1, "um"]\n\n[1, "a", verum] {x: 1.5, "y-z": nullum} 0..3 5 nan -infinitum\nfim\n'
+
+
+def test_module_functions_shadow_builtins() -> None:
+    source = """
+    functio imprime(x) { redde x * 2; }
+
+    functio main() { redde imprime(21); }
+    """
+    assert _run(source) == 42
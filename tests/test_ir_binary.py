@@ -0,0 +1,86 @@
+from __future__ import annotations
+
+import struct
+import textwrap
+
+import pytest
+
+from scriptum.errors import IrFormatError
+from scriptum.ir import format_module_ir, from_bytes, lower_module, to_bytes
+from scriptum.ir.binary import FORMAT_VERSION, MAGIC
+from scriptum.ir.interpreter import Interpreter
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile
+
+SOURCE = textwrap.dedent(
+    """
+    importa { dobra ut duplica } de "util";
+
+    /// Limite superior.
+    publicus constans numerus limite = 3;
+    mutabilis nome: textus? = nullum;
+
+    functio soma<T>(numerus a, numerus b = -2) -> numerus {
+        redde a + b;
+    }
+
+    functio main() -> quodlibet {
+        mutabilis numerus total = 0;
+        constans fatores = [0.5, 2 ** -1, 1e21];
+        externo: pro i in 0..=limite {
+            dum (verum) {
+                si (i == 2) {
+                    perge externo;
+                }
+                total = total + soma(i) * fatores[0];
+                frange;
+            }
+        }
+        constans contador = functio (numerus x) -> numerus => x + total;
+        constans ponto = structura { x: 1, y: "dois" };
+        redde [total, contador(1), ponto.y, nome ?? "anonimo", total > 1 ? "muito" : "pouco", verum ut booleanum];
+    }
+    """
+)
+
+
+def _lower(source: str = SOURCE):
+    return lower_module(ScriptumParser().parse(SourceFile("<test>", source)))
+
+
+def test_round_trip_preserves_the_whole_module() -> None:
+    module = _lower()
+    data = to_bytes(module)
+    restored = from_bytes(data)
+
+    assert data.startswith(MAGIC)
+    assert restored == module
+    assert format_module_ir(restored) == format_module_ir(module)
+    assert to_bytes(restored) == data
+
+
+def test_restored_module_still_runs() -> None:
+    source = "functio main() { mutabilis numerus x = -3; x = x + 2 ** -1; redde [x, \"a\", nullum, falsum]; }"
+    assert Interpreter(from_bytes(to_bytes(_lower(source)))).execute().value == [-2.5, "a", None, False]
+
+
+def test_data_from_another_format_version_is_rejected() -> None:
+    data = bytearray(to_bytes(_lower()))
+    struct.pack_into(">H", data, len(MAGIC), FORMAT_VERSION + 1)
+    with pytest.raises(IrFormatError, match=f"format version {FORMAT_VERSION + 1}.*recompile"):
+        from_bytes(bytes(data))
+
+
+@pytest.mark.parametrize(
+    ("mangle", "message"),
+    [
+        (lambda data: b"JSON" + data[4:], "not serialized Scriptum IR"),
+        (lambda data: data[:2], "not serialized Scriptum IR"),
+        (lambda data: data[: len(data) // 2], "truncated or corrupt"),
+        (lambda data: data + b"N", "1 unexpected trailing bytes"),
+        (lambda data: data[:6] + b"X" + data[7:], "Unknown value tag"),
+    ],
+)
+def test_incompatible_data_fails_with_a_clear_error(mangle, message: str) -> None:
+    with pytest.raises(IrFormatError, match=message):
+        from_bytes(mangle(to_bytes(_lower())))
@@ -1,9 +1,26 @@
 from __future__ import annotations
 
 import textwrap
+from dataclasses import fields
 from pathlib import Path
 
-from scriptum.ir import format_module_ir, lower_module
+from scriptum.ast import nodes
+from scriptum.ast.visitors import iter_children
+from scriptum.ir import (
+    IrBinary,
+    IrContinue,
+    IrExpr,
+    IrIf,
+    IrStatement,
+    IrUpdate,
+    IrVariableDeclaration,
+    IrWhile,
+    format_ir,
+    format_module_ir,
+    lower_module,
+)
+from scriptum.ir.ir import IrNode
+from scriptum.ir.passes import flatten_conditional_statements
 from scriptum.parser.parser import ScriptumParser
 from scriptum.text import SourceFile
 
@@ -41,6 +58,13 @@ def test_lowering_with_loops_and_conditionals() -> None:
     assert result == expected
 
 
+def test_format_ir_prints_an_indented_tree_with_spans() -> None:
+    source = "functio dobro(numerus n) -> numerus {\n    si (n > 0) {\n        redde n * 2;\n    }\n    redde 0;\n}\n"
+    module = lower_module(ScriptumParser().parse(SourceFile('<test>', source)))
+    expected = (FIXTURES_DIR / 'simple_function.txt').read_text(encoding='utf8').strip()
+    assert format_ir(module) == expected
+
+
 def test_lowering_with_collections_and_lambda() -> None:
     result = _lower_source(
         """
@@ -60,3 +84,58 @@ def test_lowering_with_collections_and_lambda() -> None:
     )
     expected = (FIXTURES_DIR / 'collections.json').read_text(encoding='utf8').strip()
     assert result == expected
+
+
+def test_c_style_for_lowers_to_a_while_with_init_and_update() -> None:
+    module = ScriptumParser().parse(
+        SourceFile('<test>', 'functio f() { pro (mutabilis i = 0; i < 3; i++) { perge; } pro (;;) { frange; } }')
+    )
+    counted, endless = lower_module(module).functions[0].body
+    assert isinstance(counted, IrWhile)
+    assert isinstance(counted.init, IrVariableDeclaration) and counted.init.name == 'i'
+    assert isinstance(counted.condition, IrBinary) and isinstance(counted.update, IrUpdate)
+    assert isinstance(counted.body[0], IrContinue)
+    assert isinstance(endless, IrWhile)
+    assert (endless.init, endless.condition, endless.update) == (None, None, None)
+
+
+def _ast_nodes(node: nodes.Node) -> dict[int, nodes.Node]:
+    found = {node.node_id: node}
+    for child in iter_children(node):
+        found.update(_ast_nodes(child))
+    return found
+
+
+def _ir_nodes(node: IrNode) -> list[IrNode]:
+    found = [node]
+    for item in fields(node):
+        value = getattr(node, item.name)
+        for child in value if isinstance(value, list) else [value]:
+            if isinstance(child, IrNode):
+                found.extend(_ir_nodes(child))
+    return found
+
+
+def test_lowered_nodes_keep_the_ids_of_their_ast_nodes() -> None:
+    source = textwrap.dedent(
+        """
+        functio main(numerus n) -> numerus {
+            mutabilis numerus total = 0;
+            pro (mutabilis numerus i = 0; i < n; i++) {
+                total > 2 ? registrar(total) : 0;
+                total = total + [i, structura { x: i }.x][1];
+            }
+            redde functio (numerus x) => x * total;
+        }
+        """
+    )
+    module = ScriptumParser().parse(SourceFile('<test>', source))
+    ast_nodes = _ast_nodes(module)
+    lowered = [node for node in _ir_nodes(lower_module(module)) if isinstance(node, (IrStatement, IrExpr))]
+    assert lowered and all(node.node_id in ast_nodes for node in lowered)
+    assert all(ast_nodes[node.node_id].span == node.span for node in lowered)
+
+    flattened = flatten_conditional_statements(lower_module(module))
+    (branch,) = [node for node in _ir_nodes(flattened) if isinstance(node, IrIf)]
+    assert isinstance(ast_nodes[branch.node_id], nodes.ExpressionStatement)
+    assert isinstance(ast_nodes[branch.then_branch[0].node_id], nodes.CallExpression)
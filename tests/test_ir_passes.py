@@ -0,0 +1,134 @@
+from __future__ import annotations
+
+import textwrap
+
+import pytest
+
+from scriptum.codegen import generate
+from scriptum.ir import (
+    IrExpressionStatement,
+    IrIf,
+    flatten_conditional_statements,
+    lower_module,
+    simplify_expressions,
+)
+from scriptum.ir.interpreter import Interpreter
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile
+
+SOURCE = textwrap.dedent(
+    """
+    functio main() {
+        mutabilis numerus pares = 0;
+        mutabilis numerus impares = 0;
+        mutabilis numerus visto = 0;
+        pro n in [1, 2, 3, 4, 5] {
+            n % 2 == 0 ? pares = pares + n : impares = impares + 1;
+            n > 3 ? visto = n : n;
+        }
+        redde [pares, impares, visto];
+    }
+    """
+)
+
+
+def _lower():
+    return lower_module(ScriptumParser().parse(SourceFile("<test>", SOURCE)))
+
+
+def test_conditional_statement_becomes_if() -> None:
+    module = flatten_conditional_statements(_lower())
+    loop = module.functions[0].body[3]
+    first, second = loop.body
+    assert isinstance(first, IrIf)
+    assert [type(stmt) for stmt in first.then_branch] == [IrExpressionStatement]
+    assert [type(stmt) for stmt in first.else_branch] == [IrExpressionStatement]
+    assert isinstance(second, IrIf)
+    assert second.else_branch == []
+
+    formatted = generate(module).formatted
+    assert "si (n % 2 == 0) {\n            pares = pares + n;\n        } aliter {" in formatted
+    assert "si (n > 3) {\n            visto = n;\n        }\n" in formatted
+
+
+def test_flattening_preserves_behaviour() -> None:
+    expected = Interpreter(_lower()).execute().value
+    flattened = Interpreter(flatten_conditional_statements(_lower())).execute().value
+    assert expected == [6, 3, 5]
+    assert flattened == expected
+
+
+def test_pure_conditional_statement_is_left_alone() -> None:
+    source = "functio main() { mutabilis numerus x = 1; x > 0 ? x : 0; }"
+    module = flatten_conditional_statements(lower_module(ScriptumParser().parse(SourceFile("<test>", source))))
+    assert isinstance(module.functions[0].body[1], IrExpressionStatement)
+
+
+def _simplified(expression: str) -> str:
+    source = (
+        "functio f(numerus n, booleanum b, textus t, q) -> quodlibet {\n"
+        f"    redde {expression};\n"
+        "}\n"
+    )
+    module = simplify_expressions(lower_module(ScriptumParser().parse(SourceFile("<test>", source))))
+    return generate(module).formatted.splitlines()[1].strip()
+
+
+@pytest.mark.parametrize(
+    ("expression", "expected"),
+    [
+        ("n + 0", "n"),
+        ("0 + n", "n"),
+        ("n - 0", "n"),
+        ("n * 1", "n"),
+        ("1 * (n - 2)", "n - 2"),
+        ("(n << 2) * 0", "0"),
+        ("0 * ~n", "0"),
+        ("b && verum", "b"),
+        ("verum && n > 1", "n > 1"),
+        ("b || falsum", "b"),
+        ("falsum || !b", "!b"),
+        ("(n + 0) * 1 + 0", "n"),
+    ],
+)
+def test_simplification_rules(expression: str, expected: str) -> None:
+    assert _simplified(expression) == f"redde {expected};"
+
+
+@pytest.mark.parametrize(
+    "expression",
+    [
+        "t + 0",  # joins textus
+        "q * 1",  # "a" * 1 is a textus
+        "n * 0",  # nan * 0 is nan
+        "f(n, b, t, q) * 0",
+        "q && verum",  # fails when q is not booleanum
+        "0 - n",
+    ],
+)
+def test_simplification_leaves_unproven_operands_alone(expression: str) -> None:
+    assert _simplified(expression) == f"redde {expression};"
+
+
+def test_simplification_keeps_the_effects_of_the_kept_side() -> None:
+    source = """
+    functio main() {
+        mutabilis numerus contador = 0;
+        mutabilis booleanum visto = falsum;
+        constans a = contador++ + 0;
+        constans b = (visto = verum) && verum;
+        constans c = (contador = contador + 10) * 1;
+        redde [a, b, c, contador, visto];
+    }
+    """
+
+    def lower():
+        return lower_module(ScriptumParser().parse(SourceFile("<test>", textwrap.dedent(source))))
+
+    simplified = simplify_expressions(lower())
+    formatted = generate(simplified).formatted
+    assert "constans a = contador++;" in formatted
+    assert "constans b = visto = verum;" in formatted
+    assert "constans c = contador = contador + 10;" in formatted
+    assert Interpreter(simplified).execute().value == Interpreter(lower()).execute().value == [0, True, 11, 11, True]
+
@@ -0,0 +1,103 @@
+from __future__ import annotations
+
+import textwrap
+
+from scriptum.ir import (
+    IrBreak,
+    IrExpressionStatement,
+    IrFunction,
+    IrIdentifier,
+    IrLambda,
+    IrLiteral,
+    IrReturn,
+    IrVariable,
+    ModuleIr,
+    lower_module,
+    validate_module,
+)
+from scriptum.parser.parser import ScriptumParser
+from scriptum.text import SourceFile, Span
+
+SPAN = Span(0, 0)
+
+
+def _module(*functions: IrFunction, globals_: list[IrVariable] | None = None) -> ModuleIr:
+    return ModuleIr(span=SPAN, globals=globals_ or [], functions=list(functions))
+
+
+def _function(body: list) -> IrFunction:
+    return IrFunction(span=SPAN, name="main", parameters=[], return_annotation=None, body=body)
+
+
+def test_lowered_program_is_valid() -> None:
+    source = textwrap.dedent(
+        """
+        constans numerus base = 2;
+
+        functio main() -> numerus {
+            mutabilis numerus total = 0;
+            pro item in [1, 2, 3] {
+                si (item == 2) {
+                    perge;
+                }
+                total = total + item * base;
+            }
+            redde total;
+        }
+        """
+    )
+    module = ScriptumParser().parse(SourceFile("<test>", source))
+    assert validate_module(lower_module(module)) == []
+
+
+def test_break_outside_loop_is_rejected() -> None:
+    problems = validate_module(_module(_function([IrBreak(span=Span(3, 9))])))
+    assert [problem.message for problem in problems] == ["'frange' outside of a loop"]
+    assert problems[0].span == Span(3, 9)
+
+
+def test_unresolved_name_is_rejected() -> None:
+    body = [IrReturn(span=SPAN, value=IrIdentifier(span=Span(5, 10), name="ghost"))]
+    problems = validate_module(_module(_function(body)))
+    assert [problem.message for problem in problems] == ["Unresolved name 'ghost'"]
+
+
+def test_global_initializer_cannot_see_later_globals() -> None:
+    globals_ = [
+        IrVariable(span=SPAN, name="a", mutable=False, type_annotation=None, initializer=IrIdentifier(SPAN, "b")),
+        IrVariable(span=SPAN, name="b", mutable=False, type_annotation=None, initializer=IrLiteral(SPAN, 1, "1")),
+    ]
+    problems = validate_module(_module(globals_=globals_))
+    assert [problem.message for problem in problems] == ["Unresolved name 'b'"]
+
+
+def test_lambda_with_two_bodies_is_rejected() -> None:
+    lam = IrLambda(
+        span=SPAN,
+        parameters=[],
+        return_annotation=None,
+        body_expression=IrLiteral(SPAN, 1, "1"),
+        body_statements=[IrReturn(span=SPAN, value=None)],
+    )
+    problems = validate_module(_module(_function([IrExpressionStatement(span=SPAN, expression=lam)])))
+    assert [problem.message for problem in problems] == ["Lambda has both an expression body and a statement body"]
+
+
+def test_break_inside_lambda_does_not_see_enclosing_loop() -> None:
+    source = textwrap.dedent(
+        """
+        functio main() {
+            dum (verum) {
+                constans quodlibet f = functio () { redde 1; };
+                frange;
+            }
+        }
+        """
+    )
+    module = lower_module(ScriptumParser().parse(SourceFile("<test>", source)))
+    assert validate_module(module) == []
+
+    loop = module.functions[0].body[0]
+    lam = loop.body[0].initializer
+    lam.body_statements.append(IrBreak(span=SPAN))
+    assert [problem.message for problem in validate_module(module)] == ["'frange' outside of a loop"]
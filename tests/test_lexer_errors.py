@@ -4,7 +4,7 @@ import pytest
 
 from scriptum import errors
 from scriptum.lexer.lexer import ScriptumLexer
-from scriptum.text import SourceFile
+from scriptum.text import SourceFile, Span
 
 
 def _source(text: str) -> SourceFile:
@@ -25,8 +25,35 @@ def test_unterminated_string_literal() -> None:
         lexer.tokenize(_source(program))
 
 
+def test_unterminated_string_points_at_opening_quote() -> None:
+    lexer = ScriptumLexer()
+    program = 'constans textus a = "ok";\nconstans textus b = "aberta;\nconstans numerus c = 1;\n'
+    with pytest.raises(errors.LexerError) as captured:
+        lexer.tokenize(_source(program))
+    opening = program.index('"aberta')
+    assert captured.value.anchor == Span(opening, opening + 1)
+    assert captured.value.span == Span(opening, len(program))
+    assert "starting at line 2, column 21" in str(captured.value)
+    assert program[captured.value.anchor.start : captured.value.anchor.end] == '"'
+
+
+def test_unterminated_single_quoted_string_asks_for_its_own_quote() -> None:
+    lexer = ScriptumLexer()
+    with pytest.raises(errors.LexerError) as captured:
+        lexer.tokenize(_source("constans textus a = 'aberta \"ate aqui\";"))
+    assert captured.value.anchor == Span(20, 21)
+    assert str(captured.value).endswith("add a closing \"'\"")
+
+
 def test_unterminated_block_comment() -> None:
     lexer = ScriptumLexer()
     program = "mutabilis numerus a = 1 /* comentario sem fim"
     with pytest.raises(errors.LexerError):
         lexer.tokenize(_source(program))
+
+
+@pytest.mark.parametrize("literal", ["2.5i", "1e3i"])
+def test_integer_suffix_requires_a_whole_number(literal: str) -> None:
+    program = f"constans numerus a = {literal};"
+    with pytest.raises(errors.LexerError, match=f"without fraction or exponent, found '{literal}'"):
+        ScriptumLexer().tokenize(_source(program))
@@ -98,6 +98,30 @@ def test_power_operator_is_lexed_as_single_token() -> None:
     assert operators == ["**", "**"]
 
 
+def test_number_keeps_the_dot_only_before_a_digit() -> None:
+    def lexemes(snippet: str) -> list[tuple[str, str]]:
+        return [(tok.kind.name, tok.lexeme) for tok in _tokenize_inline(snippet)]
+
+    assert lexemes("2.5") == [("NUMBER_LITERAL", "2.5")]
+    assert lexemes("2.foo") == [("NUMBER_LITERAL", "2"), ("OPERATOR", "."), ("IDENTIFIER", "foo")]
+    assert lexemes("2..3") == [("NUMBER_LITERAL", "2"), ("OPERATOR", ".."), ("NUMBER_LITERAL", "3")]
+    assert lexemes("2.5.x") == [("NUMBER_LITERAL", "2.5"), ("OPERATOR", "."), ("IDENTIFIER", "x")]
+
+
+def test_number_suffix_is_recorded_without_breaking_exponents() -> None:
+    tokens_out = _tokenize_inline("5i 5f 2.5f 5e3 1_000i 5.0")
+    assert [(tok.lexeme, tok.value, tok.metadata.get("suffix")) for tok in tokens_out] == [
+        ("5i", 5, "i"),
+        ("5f", 5.0, "f"),
+        ("2.5f", 2.5, "f"),
+        ("5e3", 5000.0, None),
+        ("1_000i", 1000, "i"),
+        ("5.0", 5.0, None),
+    ]
+    assert all(tok.kind is tokens.TokenKind.NUMBER_LITERAL for tok in tokens_out)
+    assert type(tokens_out[1].value) is float
+
+
 def test_logical_and_comparison_operators() -> None:
     snippet = "a ?? b ?: c || d && e == f != g === h !== i > j >= k < l <= m"
     tokens_out = _tokenize_inline(snippet)
@@ -123,7 +147,34 @@ def test_line_and_block_comments_are_ignored() -> None:
     assert [tok.lexeme for tok in tokens_out] == ["mutabilis", "numerus", "a", "=", "1", "perge", "=", "2", ";"]
 
 
+def test_doc_comments_attach_to_next_token() -> None:
+    snippet = "/// Soma dois números.\n///\n///   recuado\nfunctio f() {}\n// solto\n/// perdido\n// outro\nperge"
+    tokens_out = _tokenize_inline(snippet)
+    assert tokens_out[0].metadata["doc"] == ["Soma dois números.", "", "  recuado"]
+    assert all("doc" not in tok.metadata for tok in tokens_out[1:])
+
+
+def test_line_comments_attach_to_the_last_token_of_their_line() -> None:
+    snippet = "a: 1, // primeiro  \nb: 2 /* bloco */ //segundo ação\n// sozinho\nc"
+    tokens_out = _tokenize_inline(snippet)
+    comments = {tok.lexeme: tok.metadata["comment"] for tok in tokens_out if "comment" in tok.metadata}
+    assert comments == {",": "primeiro", "2": "segundo ação"}
+
+
 def test_unicode_identifier_is_normalised() -> None:
     tokens_out = _tokenize_inline("impressão = 1")
     identifiers = [tok.lexeme for tok in tokens_out if tok.kind is tokens.TokenKind.IDENTIFIER]
     assert identifiers == ["impress", "o"]
+
+
+def test_string_literals_keep_characters_outside_ascii() -> None:
+    _, _, literal = _tokenize_inline('x = "ação\\t\\u00e9"')
+    assert literal.lexeme == '"ação\\t\\u00e9"'
+    assert literal.value == "ação\té"
+
+
+def test_single_and_double_quoted_strings_have_the_same_value() -> None:
+    single = _tokenize_inline("x = 'diz \"oi\" e it\\'s'")[2]
+    double = _tokenize_inline('x = "diz \\"oi\\" e it\\\'s"')[2]
+    assert single.kind is double.kind is tokens.TokenKind.STRING_LITERAL
+    assert single.value == double.value == "diz \"oi\" e it's"
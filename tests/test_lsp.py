@@ -0,0 +1,186 @@
+from __future__ import annotations
+
+import io
+import textwrap
+from typing import Any, Dict, List
+
+from click.testing import CliRunner
+
+from scriptum.cli import cli
+from scriptum.highlight import TokenClass
+from scriptum.lsp import LanguageServer, analyze_document, offset_at, position_of, read_message, write_message
+
+URI = "file:///projeto/principal.stm"
+
+
+def _session(messages: List[Dict[str, Any]]) -> tuple[int, List[Dict[str, Any]]]:
+    incoming = io.BytesIO()
+    for message in messages:
+        write_message(incoming, {"jsonrpc": "2.0", **message})
+    incoming.seek(0)
+    outgoing = io.BytesIO()
+    code = LanguageServer(incoming, outgoing).serve()
+    outgoing.seek(0)
+    replies = []
+    while (reply := read_message(outgoing)) is not None:
+        replies.append(reply)
+    return code, replies
+
+
+def _hover(document_text: str, needle: str, shift: int = 0) -> Any:
+    document = analyze_document(URI, document_text)
+    offset = document_text.index(needle) + shift
+    position = position_of(document_text, offset)
+    return document.hover(position["line"], position["character"])
+
+
+def test_positions_count_utf16_code_units() -> None:
+    text = 'constans textus s = "😀";\nconstans numerus n = 1;\n'
+    assert position_of(text, text.index('";')) == {"line": 0, "character": 23}
+    assert offset_at(text, 0, 23) == text.index('";')
+    assert offset_at(text, 1, 9) == text.index("numerus")
+    assert offset_at(text, 9, 0) == len(text)
+
+
+def test_type_and_parse_errors_become_diagnostics() -> None:
+    text = textwrap.dedent(
+        """\
+        functio dobra(n: numerus) -> numerus {
+            redde verum;
+        }
+        """
+    )
+    (diagnostic,) = analyze_document(URI, text).diagnostics
+    assert diagnostic["code"] == "T010"
+    assert diagnostic["severity"] == 1
+    assert diagnostic["range"] == {"start": {"line": 1, "character": 4}, "end": {"line": 1, "character": 16}}
+
+    (syntax,) = analyze_document(URI, "functio f( {\n}\n").diagnostics
+    assert "code" not in syntax
+    assert syntax["message"].startswith("Expected parameter name.")
+    assert syntax["range"]["start"] == {"line": 0, "character": 11}
+
+    (unclosed,) = analyze_document(URI, "functio f() {\n    redde (1;\n}\n").diagnostics
+    assert unclosed["range"]["start"] == {"line": 1, "character": 12}
+    (related,) = unclosed["relatedInformation"]
+    assert related["message"] == "'(' opened here"
+    assert related["location"] == {
+        "uri": URI,
+        "range": {"start": {"line": 1, "character": 10}, "end": {"line": 1, "character": 11}},
+    }
+
+
+def test_hover_shows_the_type_of_the_expression_under_the_cursor() -> None:
+    text = textwrap.dedent(
+        """\
+        functio media(valores: [numerus]) {
+            constans total = soma(valores);
+            redde total / 2;
+        }
+        functio soma(xs: [numerus]) -> numerus {
+            redde 0;
+        }
+        """
+    )
+    hover = _hover(text, "valores);")
+    assert hover["contents"]["value"] == "```scriptum\nvalores: [numerus]\n```"
+    assert hover["range"] == {"start": {"line": 1, "character": 26}, "end": {"line": 1, "character": 33}}
+    assert _hover(text, "soma(valores)")["contents"]["value"] == (
+        "```scriptum\nsoma: functio([numerus]) -> numerus\n```"
+    )
+    # Between the operands the enclosing expression is the innermost one with a type.
+    assert _hover(text, "/ 2")["contents"]["value"] == "```scriptum\nnumerus\n```"
+    assert _hover(text, "redde total") is None
+    assert _hover("functio f( {", "f") is None
+
+
+def test_server_session_publishes_diagnostics_and_answers_hover() -> None:
+    code, replies = _session(
+        [
+            {"id": 1, "method": "initialize", "params": {"capabilities": {}}},
+            {"method": "initialized", "params": {}},
+            {
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": {"uri": URI, "languageId": "scriptum", "version": 1, "text": "functio f() {\n"}
+                },
+            },
+            {
+                "method": "textDocument/didChange",
+                "params": {
+                    "textDocument": {"uri": URI, "version": 2},
+                    "contentChanges": [{"text": "functio f() {\n    redde 1;\n}\n"}],
+                },
+            },
+            {
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": {"textDocument": {"uri": URI}, "position": {"line": 1, "character": 10}},
+            },
+            {"id": 3, "method": "textDocument/definition", "params": {}},
+            {"method": "textDocument/didClose", "params": {"textDocument": {"uri": URI}}},
+            {"id": 4, "method": "shutdown"},
+            {"method": "exit"},
+        ]
+    )
+    assert code == 0
+    initialize, opened, changed, hover, unknown, closed, shutdown = replies
+    capabilities = initialize["result"]["capabilities"]
+    assert capabilities["textDocumentSync"] == 1
+    assert capabilities["hoverProvider"] is True
+    assert capabilities["semanticTokensProvider"]["legend"]["tokenTypes"][:3] == ["keyword", "type", "function"]
+    assert opened["method"] == "textDocument/publishDiagnostics"
+    assert len(opened["params"]["diagnostics"]) == 1
+    assert changed["params"] == {"uri": URI, "diagnostics": []}
+    assert hover["id"] == 2
+    assert hover["result"]["contents"]["value"] == "```scriptum\nnumerus\n```"
+    assert unknown["error"]["code"] == -32601
+    assert closed["params"]["diagnostics"] == []
+    assert shutdown == {"jsonrpc": "2.0", "id": 4, "result": None}
+
+
+def test_failing_notification_is_logged_and_the_server_keeps_serving() -> None:
+    code, replies = _session(
+        [
+            {"method": "textDocument/didOpen", "params": {}},
+            {"id": 1, "method": "shutdown"},
+            {"method": "exit"},
+        ]
+    )
+    assert code == 0
+    logged, shutdown = replies
+    assert logged["method"] == "window/logMessage"
+    assert logged["params"] == {"type": 1, "message": "textDocument/didOpen failed: KeyError: 'textDocument'"}
+    assert shutdown == {"jsonrpc": "2.0", "id": 1, "result": None}
+
+
+def test_exit_without_shutdown_fails() -> None:
+    code, replies = _session([{"method": "exit"}])
+    assert code == 1
+    assert replies == []
+
+
+def test_lsp_command_serves_stdio() -> None:
+    incoming = io.BytesIO()
+    write_message(incoming, {"jsonrpc": "2.0", "id": 1, "method": "shutdown"})
+    write_message(incoming, {"jsonrpc": "2.0", "method": "exit"})
+    result = CliRunner().invoke(cli, ["lsp"], input=incoming.getvalue())
+    assert result.exit_code == 0, result.output
+    assert '"id": 1, "result": null' in result.output
+
+
+def test_semantic_tokens_use_the_relative_encoding() -> None:
+    document = analyze_document(URI, "/* a\nb */ functio f(n) {\n    redde n;\n}\n")
+    legend = [token_class.value for token_class in TokenClass]
+    comment, keyword, function, parameter = (
+        legend.index(name) for name in ("comment", "keyword", "function", "parameter")
+    )
+    assert document.semantic_tokens() == [
+        *(0, 0, 4, comment, 0),
+        *(1, 0, 4, comment, 0),
+        *(0, 5, 7, keyword, 0),
+        *(0, 8, 1, function, 0),
+        *(0, 2, 1, parameter, 0),
+        *(1, 4, 5, keyword, 0),
+        *(0, 6, 1, parameter, 0),
+    ]
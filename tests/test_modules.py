@@ -0,0 +1,263 @@
+from __future__ import annotations
+
+import pathlib
+import textwrap
+
+import pytest
+
+from scriptum.driver import CompilerDriver, Stage
+from scriptum.errors import ModuleImportError
+
+
+def _write(directory: pathlib.Path, name: str, source: str) -> pathlib.Path:
+    path = directory / name
+    path.write_text(textwrap.dedent(source), encoding="utf8")
+    return path
+
+
+UTIL = """
+constans numerus base = 10;
+
+functio dobra(numerus x) -> numerus {
+    redde x * 2;
+}
+"""
+
+
+def test_imported_functions_and_globals_are_callable(tmp_path: pathlib.Path) -> None:
+    _write(
+        tmp_path,
+        "util.stm",
+        """
+        constans numerus base = 10;
+
+        functio dobra(numerus x) -> numerus {
+            redde x * 2;
+        }
+        """,
+    )
+    main = _write(
+        tmp_path,
+        "main.stm",
+        """
+        importa "util.stm";
+
+        functio main() -> numerus {
+            redde dobra(base) + 1;
+        }
+        """,
+    )
+    result = CompilerDriver().run(main, until=Stage.RUN)
+    assert result.execution.value == 21
+    assert [unit.path.name for unit in result.dependencies] == ["util.stm"]
+    assert result.formatted.startswith('importa "util.stm";\n\n')
+
+
+def test_private_items_are_not_imported(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", "privatus constans numerus segredo = 1;\n")
+    main = _write(
+        tmp_path,
+        "main.stm",
+        """
+        importa "util.stm";
+
+        functio main() {
+            redde segredo;
+        }
+        """,
+    )
+    result = CompilerDriver().run(main, until=Stage.SEMANTIC)
+    assert [diagnostic.code for diagnostic in result.diagnostics] == ["S100"]
+
+
+def test_shared_dependency_is_loaded_once(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "base.stm", "constans numerus um = 1;\n")
+    _write(tmp_path, "a.stm", 'importa "base.stm";\nfunctio a() -> numerus { redde um; }\n')
+    _write(tmp_path, "b.stm", 'importa "base.stm";\nfunctio b() -> numerus { redde um + 1; }\n')
+    main = _write(
+        tmp_path,
+        "main.stm",
+        'importa "a.stm";\nimporta "b.stm";\nfunctio main() -> numerus { redde a() + b(); }\n',
+    )
+    result = CompilerDriver().run(main, until=Stage.RUN)
+    assert result.execution.value == 3
+    assert [unit.path.name for unit in result.dependencies] == ["base.stm", "a.stm", "b.stm"]
+
+
+def test_import_cycle_is_reported(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "a.stm", 'importa "b.stm";\n')
+    _write(tmp_path, "b.stm", 'importa "a.stm";\n')
+    main = _write(tmp_path, "main.stm", 'importa "a.stm";\nfunctio main() {}\n')
+    with pytest.raises(ModuleImportError, match="Import cycle detected: a.stm -> b.stm -> a.stm"):
+        CompilerDriver().run(main, until=Stage.SEMANTIC)
+
+
+def test_imports_outside_the_current_directory_are_rejected(tmp_path: pathlib.Path) -> None:
+    main = _write(tmp_path, "main.stm", 'importa "lib/util.stm";\nfunctio main() {}\n')
+    with pytest.raises(ModuleImportError, match="same directory"):
+        CompilerDriver().run(main, until=Stage.SEMANTIC)
+
+
+def test_driver_exposes_symbols_of_the_entry_module(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", "constans numerus base = 10;\n")
+    main = _write(tmp_path, "main.stm", 'importa "util.stm";\n\nconstans numerus dobro = base * 2;\n')
+    result = CompilerDriver().run(main, until=Stage.SEMANTIC)
+    assert [(entry.name, entry.kind.value, str(entry.type)) for entry in result.symbols] == [
+        ("dobro", "global", "numerus"),
+    ]
+
+
+def test_imported_names_can_be_renamed(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", UTIL)
+    main = _write(
+        tmp_path,
+        "main.stm",
+        """
+        importa { dobra ut duplica, base } de "util.stm";
+
+        functio main() -> numerus {
+            redde duplica(base) + 1;
+        }
+        """,
+    )
+    result = CompilerDriver().run(main, until=Stage.RUN)
+    assert result.diagnostics == []
+    assert result.execution.value == 21
+    assert result.formatted.startswith('importa { dobra ut duplica, base } de "util.stm";\n\n')
+
+
+def test_module_can_be_imported_as_a_namespace(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", UTIL)
+    main = _write(
+        tmp_path,
+        "main.stm",
+        """
+        importa "util.stm" ut u;
+
+        functio main() -> numerus {
+            redde u.dobra(u.base);
+        }
+        """,
+    )
+    result = CompilerDriver().run(main, until=Stage.RUN)
+    assert result.diagnostics == []
+    assert result.execution.value == 20
+    assert result.formatted.startswith('importa "util.stm" ut u;\n\n')
+
+
+def test_names_a_module_does_not_import_directly_stay_private_to_their_module(tmp_path: pathlib.Path) -> None:
+    _write(
+        tmp_path,
+        "util.stm",
+        """
+        privatus constans numerus passo = 1;
+        constans numerus base = 10;
+
+        functio proximo(numerus x) -> numerus {
+            redde x + passo + base;
+        }
+        """,
+    )
+    main = _write(
+        tmp_path,
+        "main.stm",
+        """
+        importa "util.stm" ut u;
+        importa { proximo ut seguinte } de "util.stm";
+
+        constans numerus base = 100;
+        constans numerus passo = 1000;
+
+        functio main() -> numerus {
+            redde u.proximo(base) + seguinte(passo) + u.base;
+        }
+        """,
+    )
+    result = CompilerDriver().run(main, until=Stage.RUN)
+    assert result.diagnostics == []
+    assert result.execution.value == 111 + 1011 + 10
+
+
+def test_renamed_import_conflicts_with_local_declaration(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", UTIL)
+    main = _write(
+        tmp_path,
+        "main.stm",
+        'importa { dobra ut f } de "util.stm";\n\nfunctio f() {}\n',
+    )
+    result = CompilerDriver().run(main, until=Stage.SEMANTIC)
+    assert [diagnostic.code for diagnostic in result.diagnostics] == ["S110"]
+
+
+def test_unknown_imported_name_is_reported(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", UTIL)
+    main = _write(tmp_path, "main.stm", 'importa { triplica } de "util.stm";\n')
+    result = CompilerDriver().run(main, until=Stage.SEMANTIC)
+    assert [(d.code, d.message) for d in result.diagnostics] == [
+        ("S130", "Module 'util.stm' has no export named 'triplica'"),
+    ]
+
+
+def test_renamed_import_is_immutable(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "util.stm", "mutabilis numerus contador = 0;\n")
+    main = _write(
+        tmp_path,
+        "main.stm",
+        'importa { contador ut c } de "util.stm";\n\nfunctio main() { c = 1; }\n',
+    )
+    result = CompilerDriver().run(main, until=Stage.SEMANTIC)
+    assert [diagnostic.code for diagnostic in result.diagnostics] == ["S120"]
+
+
+CONTADOR = """
+mutabilis numerus contador = 0;
+
+functio inc() {
+    contador = contador + 1;
+}
+"""
+
+
+@pytest.mark.parametrize(
+    "source",
+    [
+        'importa "cnt.stm" ut c;\n\nfunctio main() -> numerus {\n    c.inc();\n    c.inc();\n'
+        "    redde c.contador;\n}\n",
+        'importa { contador ut k, inc } de "cnt.stm";\n\nfunctio main() -> numerus {\n    inc();\n    inc();\n'
+        "    redde k;\n}\n",
+        'importa { contador } de "cnt.stm";\nimporta "cnt.stm" ut c;\n\nfunctio main() -> numerus {\n'
+        "    c.inc();\n    c.inc();\n    redde contador;\n}\n",
+    ],
+)
+def test_imports_read_the_current_value_of_a_mutable_global(tmp_path: pathlib.Path, source: str) -> None:
+    _write(tmp_path, "cnt.stm", CONTADOR)
+    main = _write(tmp_path, "main.stm", source)
+    assert CompilerDriver().run(main, until=Stage.RUN).execution.value == 2
+
+
+def test_locals_shadow_imported_names(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "cnt.stm", CONTADOR)
+    main = _write(
+        tmp_path,
+        "main.stm",
+        """
+        importa "cnt.stm" ut c;
+        importa { contador ut k } de "cnt.stm";
+
+        functio main() -> numerus {
+            constans quodlibet c = structura { contador: 10 };
+            constans numerus k = 5;
+            redde c.contador + k;
+        }
+        """,
+    )
+    assert CompilerDriver().run(main, until=Stage.RUN).execution.value == 15
+
+
+def test_namespace_members_cannot_be_assigned(tmp_path: pathlib.Path) -> None:
+    _write(tmp_path, "cnt.stm", CONTADOR)
+    main = _write(tmp_path, "main.stm", 'importa "cnt.stm" ut c;\n\nfunctio main() { c.contador = 5; }\n')
+    result = CompilerDriver().run(main, until=Stage.SEMANTIC)
+    assert [(d.code, d.message) for d in result.diagnostics] == [
+        ("S120", "Cannot assign to 'c.contador' of an imported module"),
+    ]
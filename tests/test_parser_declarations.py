@@ -0,0 +1,170 @@
+from __future__ import annotations
+
+import pytest
+
+from scriptum.ast import nodes
+from scriptum.parser.parser import ParseError, ScriptumParser
+from scriptum.text import SourceFile, Span
+
+
+def _parse(source: str) -> nodes.Module:
+    return ScriptumParser().parse(SourceFile("<test>", source))
+
+
+def test_visibility_modifiers_on_top_level_items() -> None:
+    source = "publicus functio f() {}\nprivatus constans numerus x = 1;\nmutabilis numerus y = 2;\n"
+    module = _parse(source)
+    func, private_var, plain_var = module.declarations
+    assert isinstance(func, nodes.FunctionDeclaration)
+    assert func.visibility is nodes.Visibility.PUBLICUS
+    assert source[func.span.start : func.span.end] == "publicus functio f() {}"
+    assert isinstance(private_var, nodes.VariableDeclaration)
+    assert private_var.visibility is nodes.Visibility.PRIVATUS
+    assert plain_var.visibility is None
+
+
+def test_visibility_modifier_requires_declaration() -> None:
+    with pytest.raises(ParseError, match="after 'publicus'"):
+        _parse("publicus redde 1;")
+
+
+def test_visibility_modifier_rejected_inside_functions() -> None:
+    with pytest.raises(ParseError, match="only allowed on top-level declarations") as info:
+        _parse("functio f() { privatus constans numerus x = 1; }")
+    assert info.value.span == Span(14, 22)
+
+
+def test_import_declaration_records_path() -> None:
+    module = _parse('importa "util.stm";\nfunctio main() {}\n')
+    declaration = module.declarations[0]
+    assert isinstance(declaration, nodes.ImportDeclaration)
+    assert declaration.path == "util.stm"
+
+
+def test_import_rejected_inside_functions() -> None:
+    with pytest.raises(ParseError, match="only allowed at the top level") as info:
+        _parse('functio f() { importa "util.stm"; }')
+    assert info.value.span == Span(14, 21)
+
+
+@pytest.mark.parametrize(
+    ("source", "message"),
+    [
+        (
+            "functio f() { si (verum) { redde; } redde; aliter { redde; } }",
+            r"'aliter' without a matching 'si' at Span\(start=43, end=49\)",
+        ),
+        ("functio f() { in lista; }", "'in' outside of a 'pro' loop header"),
+        ("aliter { }", "'aliter' without a matching 'si'"),
+        ("constans x = 1;\nredde x;", r"'redde' outside of a function at Span\(start=16, end=21\)"),
+    ],
+)
+def test_orphan_keywords_get_specific_messages(source: str, message: str) -> None:
+    with pytest.raises(ParseError, match=message):
+        _parse(source)
+
+
+@pytest.mark.parametrize(
+    ("source", "message", "span"),
+    [
+        ("constans x: = 1;", r"Expected type annotation. Found '=' at Span\(start=12, end=13\)", Span(12, 13)),
+        (
+            "functio f() { pro x [1] {} }",
+            r"Expected keyword 'in', found '\[' at Span\(start=20, end=21\)",
+            Span(20, 21),
+        ),
+    ],
+)
+def test_missing_annotations_and_keywords_point_at_the_token_found(source: str, message: str, span: Span) -> None:
+    with pytest.raises(ParseError, match=message) as info:
+        _parse(source)
+    assert info.value.span == span
+
+
+def test_one_keyword_can_declare_several_variables() -> None:
+    source = "publicus constans numerus a = 1, b, textus c = \"x\", d;\nfunctio f() { mutabilis i = 0, j = i; }\n"
+    module = _parse(source)
+    a, b, c, d, func = module.declarations
+    assert [(decl.name, decl.type_annotation.name, decl.visibility) for decl in (a, b, c, d)] == [
+        ("a", "numerus", nodes.Visibility.PUBLICUS),
+        ("b", "numerus", nodes.Visibility.PUBLICUS),
+        ("c", "textus", nodes.Visibility.PUBLICUS),
+        ("d", "textus", nodes.Visibility.PUBLICUS),
+    ]
+    assert [source[decl.span.start : decl.span.end] for decl in (a, b, d)] == [
+        "publicus constans numerus a = 1",
+        "b",
+        "d;",
+    ]
+    i, j = func.body.statements
+    assert (i.name, i.type_annotation, j.name, j.initializer.name) == ("i", None, "j", "i")
+
+
+def test_label_must_precede_a_loop() -> None:
+    with pytest.raises(ParseError, match="Label 'externo' must be followed by a 'dum' or 'pro' loop"):
+        _parse("functio f() { externo: si (verum) { } }")
+
+
+def test_c_style_for_parses_its_three_header_parts() -> None:
+    source = "functio f() { pro (mutabilis numerus i = 0; i < 3; i++) { } pro (;;) { } pro (i in xs) { } }"
+    full, empty, for_in = _parse(source).declarations[0].body.statements
+    assert isinstance(full, nodes.CStyleForStatement)
+    assert (full.init.name, full.init.mutable, full.init.initializer.value) == ("i", True, 0)
+    assert source[full.condition.span.start : full.condition.span.end] == "i < 3"
+    assert isinstance(full.update, nodes.UpdateExpression)
+    assert isinstance(empty, nodes.CStyleForStatement)
+    assert (empty.init, empty.condition, empty.update) == (None, None, None)
+    assert isinstance(for_in, nodes.ForStatement)
+
+
+def test_c_style_for_initialiser_declares_one_variable() -> None:
+    with pytest.raises(ParseError, match="declares a single variable"):
+        _parse("functio f() { pro (mutabilis i = 0, j = 1; i < j; i++) { } }")
+
+
+def test_object_type_annotations() -> None:
+    module = _parse("functio f(p: {x: numerus,y : {z: textus}[]}, numerus n) -> {x: numerus} { redde p; }")
+    func = module.declarations[0]
+    assert [param.type_annotation.name for param in func.parameters] == ["{x: numerus, y: {z: textus}[]}", "numerus"]
+    assert func.return_type.name == "{x: numerus}"
+    assert len(func.body.statements) == 1
+
+    with pytest.raises(ParseError, match="Expected '}' to close the object type. Found '='"):
+        _parse("constans p: {x: numerus = 1;")
+
+
+def test_repeated_optional_markers_collapse_in_annotations() -> None:
+    module = _parse("functio f(a: numerus??, b: [textus? ?]??) -> numerus?? { redde a ut numerus? ?? 0; }")
+    func = module.declarations[0]
+    assert [param.type_annotation.name for param in func.parameters] == ["numerus?", "[textus?]?"]
+    assert func.return_type.name == "numerus?"
+    # Before an operand, '??' after a cast is still the nullish operator.
+    coalesce = func.body.statements[0].value
+    assert coalesce.operator is nodes.BinaryOperator.NULLISH
+    assert coalesce.left.target_type.name == "numerus?"
+
+
+def test_tuple_literals_types_and_destructuring() -> None:
+    module = _parse("functio f() -> (numerus, {p: (textus, numerus)}) { constans (a, b) = (1, (2)); redde (a, b); }")
+    func = module.declarations[0]
+    assert func.return_type.name == "(numerus, {p: (textus, numerus)})"
+    declaration, ret = func.body.statements
+    assert isinstance(declaration, nodes.DestructuringDeclaration)
+    assert declaration.names == ["a", "b"]
+    assert isinstance(declaration.initializer, nodes.TupleLiteral)
+    # A single parenthesised expression is only a grouping.
+    assert isinstance(declaration.initializer.elements[1], nodes.Literal)
+    assert isinstance(ret.value, nodes.TupleLiteral)
+
+    with pytest.raises(ParseError, match="Destructuring declarations are only allowed inside functions"):
+        _parse("constans (a, b) = (1, 2);")
+
+
+def test_object_keys_can_be_string_literals() -> None:
+    module = _parse('constans o = structura { "com-traço": 1, "123": 2, nome: 3, "nome": 4 };')
+    literal = module.declarations[0].initializer
+    # The key is the string's value: `"nome"` and `nome` name the same field.
+    assert [prop.key for prop in literal.properties] == ["com-traço", "123", "nome", "nome"]
+
+    with pytest.raises(ParseError, match="Expected property identifier or string"):
+        _parse("constans o = structura { 1: 2 };")
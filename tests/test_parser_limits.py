@@ -1,5 +1,7 @@
 from __future__ import annotations
 
+import re
+
 import pytest
 
 from scriptum.parser.parser import ParseError, ParserConfig, ScriptumParser
@@ -22,3 +24,55 @@ def test_parser_depth_limit_can_be_raised() -> None:
     source = SourceFile("<test>", f"functio main() {{ mutabilis numerus x = {nested}; }}")
     module = parser.parse(source)
     assert module.declarations
+
+
+def test_parser_node_limit_points_at_the_declaration_that_crosses_it() -> None:
+    text = "constans numerus a = 1;\nconstans numerus b = 1 + 2 + 3;\n"
+    source = SourceFile("<test>", text)
+    # Module 1 + first declaration 3 + second declaration 7.
+    assert len(ScriptumParser(ParserConfig(max_nodes=11)).parse(source).declarations) == 2
+    with pytest.raises(ParseError, match="Module exceeds the limit of 10 AST nodes") as info:
+        ScriptumParser(ParserConfig(max_nodes=10)).parse(source)
+    assert source.slice(info.value.span) == "constans numerus b = 1 + 2 + 3;"
+
+
+@pytest.mark.parametrize("tail", ["redde n +", "redde -", "redde !"])
+def test_expression_cut_off_at_end_of_input_is_a_parse_error(tail: str) -> None:
+    source = SourceFile("<test>", f"functio f(n) {{\n    {tail}")
+    with pytest.raises(ParseError, match="Unexpected end of input") as info:
+        ScriptumParser().parse(source)
+    assert info.value.span is not None and info.value.span.start == len(source.text)
+
+
+def test_unclosed_parenthesis_points_back_at_its_opening() -> None:
+    source = SourceFile("<test>", "functio f(n) {\n    redde (n + 1;\n}\n")
+    with pytest.raises(ParseError, match=r"The '\(' opened at line 2, column 11 is still open\.") as info:
+        ScriptumParser().parse(source)
+    # The error sits where ')' was expected; the note points at the '('.
+    assert source.slice(info.value.span) == ";"
+    [(label, opening)] = info.value.notes
+    assert label == "'(' opened here"
+    assert opening.start == source.text.index("(n +")
+
+
+@pytest.mark.parametrize(
+    ("text", "message"),
+    [
+        ("functio f() {\n    redde [1, 2;\n}\n", "The '[' opened at line 2, column 11"),
+        ("functio f() {\n    redde 1;\n", "The '{' opened at line 1, column 13"),
+        ("functio f() {\n    redde g((1)", "The '(' opened at line 2, column 12"),
+    ],
+)
+def test_unclosed_brackets_and_braces_point_back_at_their_opening(text: str, message: str) -> None:
+    source = SourceFile("<test>", text)
+    with pytest.raises(ParseError, match=re.escape(message)) as info:
+        ScriptumParser().parse(source)
+    [(_, span)] = info.value.notes
+    assert source.slice(span) == message[5]
+
+
+def test_closed_delimiters_leave_no_notes() -> None:
+    source = SourceFile("<test>", "functio f() {\n    redde (1 + 2) +;\n}\n")
+    with pytest.raises(ParseError) as info:
+        ScriptumParser().parse(source)
+    assert info.value.notes == []
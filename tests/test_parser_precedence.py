@@ -1,7 +1,9 @@
 from __future__ import annotations
 
+import pytest
+
 from scriptum.ast import nodes
-from scriptum.parser.parser import ScriptumParser
+from scriptum.parser.parser import ParseError, ScriptumParser
 from scriptum.text import SourceFile
 
 
@@ -58,3 +60,149 @@ def test_nested_ternary_is_right_associative() -> None:
     assert isinstance(expr.alternate, nodes.ConditionalExpression)
     inner = expr.alternate
     assert isinstance(inner.alternate, nodes.Identifier)
+
+
+def test_range_binds_looser_than_addition() -> None:
+    expr = _parse_expression_snippet("0..n + 1")
+    assert isinstance(expr, nodes.RangeExpression)
+    assert isinstance(expr.start, nodes.Literal)
+    assert isinstance(expr.end, nodes.BinaryExpression)
+    assert expr.end.operator is nodes.BinaryOperator.ADD
+
+
+def test_inclusive_range_is_flagged() -> None:
+    inclusive = _parse_expression_snippet("0..=n")
+    exclusive = _parse_expression_snippet("0..n")
+    assert isinstance(inclusive, nodes.RangeExpression) and inclusive.inclusive
+    assert isinstance(exclusive, nodes.RangeExpression) and not exclusive.inclusive
+
+
+def test_prefix_and_postfix_updates() -> None:
+    expr = _parse_expression_snippet("i++ + --j")
+    assert isinstance(expr, nodes.BinaryExpression)
+    assert isinstance(expr.left, nodes.UpdateExpression)
+    assert expr.left.operator is nodes.UpdateOperator.INCREMENT
+    assert not expr.left.prefix
+    assert isinstance(expr.right, nodes.UpdateExpression)
+    assert expr.right.operator is nodes.UpdateOperator.DECREMENT
+    assert expr.right.prefix
+
+
+def test_explicit_type_arguments_on_call() -> None:
+    expr = _parse_expression_snippet("mapeia<numerus, textus>(lista, f)")
+    assert isinstance(expr, nodes.CallExpression)
+    assert [argument.name for argument in expr.type_arguments] == ["numerus", "textus"]
+    assert len(expr.arguments) == 2
+
+
+def test_less_than_is_not_mistaken_for_type_arguments() -> None:
+    expr = _parse_expression_snippet("a < b && c > (d)")
+    assert isinstance(expr, nodes.BinaryExpression)
+    assert expr.operator is nodes.BinaryOperator.AND
+    assert isinstance(expr.left, nodes.BinaryExpression)
+    assert expr.left.operator is nodes.BinaryOperator.LT
+    assert isinstance(expr.right, nodes.BinaryExpression)
+    assert expr.right.operator is nodes.BinaryOperator.GT
+
+
+def test_trailing_lambda_becomes_last_argument() -> None:
+    expr = _parse_expression_snippet("repete(3) functio () { redde 1; }")
+    assert isinstance(expr, nodes.CallExpression)
+    assert expr.trailing_lambda
+    assert isinstance(expr.arguments[0], nodes.Literal)
+    assert isinstance(expr.arguments[1], nodes.LambdaExpression)
+
+
+def test_trailing_lambda_without_parentheses() -> None:
+    expr = _parse_expression_snippet("lista.forEach functio (x) => x + 1")
+    assert isinstance(expr, nodes.CallExpression)
+    assert expr.trailing_lambda
+    assert isinstance(expr.callee, nodes.MemberExpression)
+    assert len(expr.arguments) == 1
+    assert isinstance(expr.arguments[0].body, nodes.BinaryExpression)
+
+
+def test_keywords_are_member_names_after_a_dot() -> None:
+    expr = _parse_expression_snippet("obj.redde + obj?.si.numerus")
+    assert isinstance(expr, nodes.BinaryExpression)
+    assert isinstance(expr.left, nodes.MemberExpression) and expr.left.property == "redde"
+    chain = expr.right
+    assert isinstance(chain, nodes.MemberExpression) and chain.property == "numerus"
+    assert chain.object.property == "si" and chain.object.optional
+
+
+def test_keyword_after_a_dot_does_not_start_a_statement() -> None:
+    source = SourceFile("<test>", "functio f(obj) {\n    redde obj.redde;\n}\n")
+    [function] = ScriptumParser().parse(source).declarations
+    [statement] = function.body.statements
+    assert isinstance(statement, nodes.ReturnStatement)
+    assert isinstance(statement.value, nodes.MemberExpression) and statement.value.property == "redde"
+
+
+def test_postfix_chain_spans_cover_target_to_final_token() -> None:
+    prefix = "mutabilis numerus tmp = "
+    source = f"{prefix}a.b.c()[0];"
+    declaration = ScriptumParser().parse(SourceFile("<test>", source)).declarations[0]
+    index = declaration.initializer
+    call = index.collection
+    member_c = call.callee
+    member_b = member_c.object
+    start = len(prefix)
+    assert [
+        (type(node).__name__, node.span.start - start, node.span.end - start)
+        for node in (index, call, member_c, member_b, member_b.object, index.index)
+    ] == [
+        ("IndexExpression", 0, 10),
+        ("CallExpression", 0, 7),
+        ("MemberExpression", 0, 5),
+        ("MemberExpression", 0, 3),
+        ("Identifier", 0, 1),
+        ("Literal", 8, 9),
+    ]
+
+
+def test_shift_binds_between_comparison_and_addition() -> None:
+    expr = _parse_expression_snippet("a < b << c + d")
+    assert isinstance(expr, nodes.BinaryExpression)
+    assert expr.operator is nodes.BinaryOperator.LT
+    shift = expr.right
+    assert isinstance(shift, nodes.BinaryExpression)
+    assert shift.operator is nodes.BinaryOperator.SHL
+    assert isinstance(shift.right, nodes.BinaryExpression)
+    assert shift.right.operator is nodes.BinaryOperator.ADD
+
+
+def test_optional_member_links_are_flagged() -> None:
+    expr = _parse_expression_snippet("a?.b.c?.d()")
+    assert isinstance(expr, nodes.CallExpression)
+    links = [expr.callee, expr.callee.object, expr.callee.object.object]
+    assert [(link.property, link.optional) for link in links] == [("d", True), ("c", False), ("b", True)]
+
+
+def test_optional_index_links_are_flagged() -> None:
+    expr = _parse_expression_snippet("a?[0][1]")
+    assert isinstance(expr, nodes.IndexExpression)
+    assert (expr.optional, expr.collection.optional) == (False, True)
+
+
+def test_ternary_with_a_spaced_array_branch_is_not_an_optional_index() -> None:
+    expr = _parse_expression_snippet("c ? [1] : [2]")
+    assert isinstance(expr, nodes.ConditionalExpression)
+
+
+def test_question_after_a_cast_opens_a_conditional_when_a_branch_follows() -> None:
+    expr = _parse_expression_snippet("x ut booleanum ? a : b")
+    assert isinstance(expr, nodes.ConditionalExpression)
+    assert isinstance(expr.condition, nodes.CastExpression)
+    assert expr.condition.target_type.name == "booleanum"
+    optional = _parse_expression_snippet("c ? x ut numerus? : (y ut textus?)")
+    assert isinstance(optional, nodes.ConditionalExpression)
+    assert optional.consequent.target_type.name == "numerus?"
+    assert optional.alternate.target_type.name == "textus?"
+
+
+def test_optional_chain_is_not_an_assignment_target() -> None:
+    with pytest.raises(ParseError, match=r"Cannot assign to an optional chain \('\?\.' or '\?\['\)"):
+        _parse_expression_snippet("a?.b.c = 1")
+    with pytest.raises(ParseError, match="Cannot assign to an optional chain"):
+        _parse_expression_snippet("a?[0] = 1")
@@ -4,7 +4,9 @@ from pathlib import Path
 
 import pytest
 
+from scriptum import tokens
 from scriptum.ast import nodes
+from scriptum.lexer.lexer import LexerConfig, ScriptumLexer
 from scriptum.parser.parser import ScriptumParser
 from scriptum.text import SourceFile
 
@@ -39,3 +41,19 @@ def test_parser_detects_assignments_and_call() -> None:
     assign_stmt = next(stmt for stmt in init_func.body.statements if isinstance(stmt, nodes.ExpressionStatement))
     assert isinstance(assign_stmt.expression, nodes.AssignmentExpression)
     assert isinstance(assign_stmt.expression.value, nodes.CallExpression)
+
+
+def test_parse_tokens_reuses_existing_tokens() -> None:
+    path = EXAMPLES_ROOT / "ok/avancados/sistema_bancario.stm"
+    source = SourceFile(str(path), path.read_text(encoding="utf8"))
+    expected = ScriptumParser().parse(source)
+
+    lexed = ScriptumLexer().tokenize(source)
+    assert ScriptumParser().parse_tokens(source, lexed) == expected
+
+    # Tokens kept for highlighting still carry whitespace and comments.
+    highlighted = ScriptumLexer(LexerConfig(skip_whitespace=False)).tokenize(source)
+    assert any(token.kind is tokens.TokenKind.WHITESPACE for token in highlighted)
+    without_eof = highlighted[:-1]
+    assert ScriptumParser().parse_tokens(source, without_eof) == expected
+    assert len(without_eof) == len(highlighted) - 1
@@ -5,7 +5,8 @@ from pathlib import Path
 import pytest
 
 from scriptum.parser.parser import ScriptumParser
-from scriptum.sema.analyzer import SemanticAnalyzer
+from scriptum.sema import types
+from scriptum.sema.analyzer import AnalyzerOptions, SemanticAnalyzer, Severity
 from scriptum.text import SourceFile
 
 EXAMPLES_ROOT = Path(__file__).resolve().parents[1] / "examples"
@@ -39,10 +40,10 @@ def test_semantics_detects_type_mismatch() -> None:
     assert all(diag.span is not None for diag in diagnostics)
 
 
-def _analyze_snippet(source: str):
+def _analyze_snippet(source: str, options: AnalyzerOptions | None = None):
     parser = ScriptumParser()
     module = parser.parse(SourceFile("<test>", source))
-    analyzer = SemanticAnalyzer()
+    analyzer = SemanticAnalyzer(options)
     return analyzer.analyze(module)
 
 
@@ -58,6 +59,27 @@ def test_use_before_declaration_reports_s100() -> None:
     assert diagnostics[0].span is not None
 
 
+def test_use_before_declaration_in_the_same_block_has_its_own_message() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans a = b;
+            constans b = 1;
+            si (c) {
+                redde;
+            }
+            mutabilis c = verum;
+            redde d;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("S100", "Identifier 'b' is used before its declaration"),
+        ("S100", "Identifier 'c' is used before its declaration"),
+        ("S100", "Undeclared identifier 'd'"),
+    ]
+
+
 def test_assignment_to_immutable_symbol_reports_s120() -> None:
     diagnostics = _analyze_snippet(
         """
@@ -98,6 +120,22 @@ def test_break_and_continue_require_loop_context() -> None:
     assert "T041" in codes
 
 
+def test_perge_in_a_lambda_does_not_see_the_enclosing_loop() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(booleanum ativo) {
+            dum (ativo) {
+                constans quodlibet f = functio () {
+                    perge;
+                };
+                perge;
+            }
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T041"]
+
+
 def test_function_call_checks_arity_and_argument_types() -> None:
     diagnostics = _analyze_snippet(
         """
@@ -148,3 +186,1404 @@ def test_ternary_condition_must_be_boolean() -> None:
         """
     )
     assert any(diag.code == "T130" for diag in diagnostics)
+
+
+def test_integer_literal_beyond_2_53_warns_w100() -> None:
+    source = """
+        constans numerus exato = 9007199254740992;
+        constans numerus grande = 9007199254740993;
+        """
+    diagnostics = _analyze_snippet(source)
+    assert [diag.code for diag in diagnostics] == ["W100"]
+    warning = diagnostics[0]
+    assert warning.severity is Severity.WARNING
+    assert source[warning.span.start : warning.span.end] == "9007199254740993"
+    assert "9007199254740992" in warning.message
+
+
+def test_integer_literal_beyond_the_largest_double_warns_w100() -> None:
+    digits = "1" * 400
+    diagnostics = _analyze_snippet(f"constans numerus enorme = {digits};")
+    assert [diag.code for diag in diagnostics] == ["W100"]
+    assert diagnostics[0].message.endswith("(nearest value is infinitum)")
+
+
+def test_constant_power_that_overflows_or_rounds_warns_w105() -> None:
+    source = """
+        constans numerus infinito = 2 ** 1024;
+        constans numerus arredondado = 3 ** 40;
+        constans numerus exato = 2 ** 60;
+        constans numerus variavel = infinito ** 2;
+        """
+    diagnostics = _analyze_snippet(source)
+    assert [(diag.code, source[diag.span.start : diag.span.end]) for diag in diagnostics] == [
+        ("W105", "2 ** 1024"),
+        ("W105", "3 ** 40"),
+    ]
+    assert all(diag.severity is Severity.WARNING for diag in diagnostics)
+    assert "overflows numerus" in diagnostics[0].message
+    assert "nearest value is 12157665459056928768" in diagnostics[1].message
+
+
+def test_w105_spells_operands_as_scriptum_numbers() -> None:
+    source = """
+        constans numerus enorme = (-2) ** 1e300;
+        constans numerus real = 2.0 ** 1024.0;
+        """
+    assert [diag.message for diag in _analyze_snippet(source)] == [
+        "Constant (-2) ** 1e+300 overflows numerus, whose largest finite value is about 1.8e308",
+        "Constant 2 ** 1024 overflows numerus, whose largest finite value is about 1.8e308",
+    ]
+
+
+def test_constant_operations_on_integers_beyond_the_largest_double_only_warn_about_the_literal() -> None:
+    digits = "1" * 400
+    source = f"""
+        constans numerus potencia = {digits} ** 2;
+        constans numerus expoente = 2 ** {digits};
+        constans numerus deslocado = {digits} >> 1;
+        """
+    assert [diag.code for diag in _analyze_snippet(source)] == ["W100", "W100", "W100"]
+
+
+def test_constant_shift_beyond_32_bits_warns_w105() -> None:
+    source = """
+        constans numerus largo = 1 << 60;
+        constans numerus sinal = 1 << 31;
+        constans numerus cortado = 5000000000 >> 1;
+        constans numerus cabe = 1 << 30;
+        constans numerus negativo = -1 << 31;
+        constans numerus zerado = 8 >> 40;
+        """
+    messages = [diag.message for diag in _analyze_snippet(source)]
+    assert messages == [
+        "Shift amount of constant 1 << 60 wraps to 28; the result is 268435456",
+        "Constant 1 << 31 does not fit in 32 bits; the result is -2147483648, not 2147483648",
+        "Constant 5000000000 >> 1 does not fit in 32 bits; the result is 352516352, not 2500000000",
+    ]
+
+
+def test_logical_operands_accept_quodlibet_without_t110() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans booleanum ok = desconhecido && verum;
+            constans booleanum outro = falsum || !desconhecido;
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["S100", "S100"]
+
+
+def test_update_and_range_operands_are_checked() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans numerus fixo = 1;
+            mutabilis textus nome = "a";
+            fixo++;
+            --nome;
+            pro i in 0.."dez" {
+                perge;
+            }
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["S120", "T100", "T103"]
+
+
+def test_power_accepts_negative_and_fractional_exponents() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        constans numerus metade = 2 ** -1;
+        constans numerus raiz = 9 ** 0.5;
+        constans errado = 2 ** "meio";
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T100"]
+
+
+def test_iterating_an_optional_array_reports_t032() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio soma(talvez: numerus[]?, padrao: numerus[]) -> numerus {
+            mutabilis numerus total = 0;
+            pro n in talvez {
+                total = total + n;
+            }
+            pro n in talvez ?? padrao {
+                total = total + n;
+            }
+            pro n in talvez ut numerus[] {
+                total = total + n;
+            }
+            redde total;
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T032"]
+    assert diagnostics[0].message.startswith("Expression in 'pro' may be nullum (type [numerus]?); coalesce it")
+
+
+@pytest.mark.parametrize("options", [AnalyzerOptions(), AnalyzerOptions(strict_any=True)])
+def test_invalid_iterable_is_reported_once(options: AnalyzerOptions) -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio soma(limite: numerus) -> numerus {
+            mutabilis numerus total = 0;
+            pro x in limite {
+                constans textus rotulo = x;
+                total = total + x.valor + x[0] + x(1);
+                pro y in x {
+                    total = total + y;
+                }
+                redde x;
+            }
+            pro numerus n in verum {
+                total = total + n;
+            }
+            redde total;
+        }
+        """,
+        options,
+    )
+    assert [diag.code for diag in diagnostics] == ["T030", "T030"]
+
+
+def test_undeclared_iterable_is_reported_once() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio primeiro() -> numerus {
+            pro item in itens {
+                constans textus nome = item.nome;
+                redde item;
+            }
+            redde 0;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [("S100", "Undeclared identifier 'itens'")]
+
+
+def test_empty_array_takes_the_annotated_element_type() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio primeiros(talvez: [numerus]?) -> [numerus] {
+            mutabilis xs: [numerus] = [];
+            mutabilis matriz: [[textus]] = [[], []];
+            constans caixa: {itens: [numerus], par: (numerus, [textus])} = structura { itens: [], par: (1, []) };
+            xs = talvez ?? [];
+            xs = verum ? [] : xs;
+            constans textus errado = xs[0];
+            constans numerus tambem = matriz[0][0];
+            constans vazio = [];
+            constans textus livre = vazio[0];
+            primeiros([]);
+            redde [];
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Type mismatch: cannot initialise 'errado' of type textus with numerus",
+        "Type mismatch: cannot initialise 'tambem' of type numerus with textus",
+        # Without an annotation `[]` holds quodlibet, which needs `ut` to become textus.
+        "Type mismatch: cannot initialise 'livre' of type textus with quodlibet",
+    ]
+
+
+def test_empty_array_type_is_recorded_for_the_literal() -> None:
+    module = ScriptumParser().parse(SourceFile("<test>", "constans xs: [numerus] = [];\n"))
+    analyzer = SemanticAnalyzer()
+    assert analyzer.analyze(module) == []
+    literal = module.declarations[0].initializer
+    assert str(analyzer.expression_types[literal.node_id]) == "[numerus]"
+
+
+def test_optional_member_makes_the_rest_of_the_chain_optional() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(p: {dados: {x: numerus, itens: numerus[]}}?, q: {dados: {x: numerus}}) -> numerus {
+            constans textus a = p?.dados.x;
+            constans textus b = p?.dados.itens[0];
+            constans textus c = q?.dados?.x;
+            constans textus d = q.dados.x;
+            constans inteiro: numerus? = p?.dados.x;
+            redde p?.dados.x ?? q.dados.x;
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Type mismatch: cannot initialise 'a' of type textus with numerus?",
+        "Type mismatch: cannot initialise 'b' of type textus with numerus?",
+        "Type mismatch: cannot initialise 'c' of type textus with numerus?",
+        "Type mismatch: cannot initialise 'd' of type textus with numerus",
+    ]
+
+
+def test_optional_index_reads_the_element_of_the_unwrapped_collection() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(a: numerus[]?, m: numerus[][]) -> numerus {
+            constans textus x = a?[0];
+            constans textus y = m?[0][1];
+            constans primeiro: numerus? = a?[0];
+            redde a?[0] ?? 0;
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Type mismatch: cannot initialise 'x' of type textus with numerus?",
+        "Type mismatch: cannot initialise 'y' of type textus with numerus?",
+    ]
+
+
+def test_arrays_of_vacuum_report_t230() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio f() -> vacuum {
+        }
+
+        functio demo(itens: [vacuum]) -> numerus {
+            constans chamadas = [f()];
+            constans validos = [1, 2];
+            redde 0;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("T230", "Array type [vacuum] cannot hold values; vacuum is only valid as a return type"),
+        ("T230", "Array element has type vacuum, so it holds no value"),
+    ]
+
+
+def test_read_of_possibly_unassigned_local_reports_s150() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(booleanum c, itens: numerus[]) -> numerus {
+            mutabilis numerus parcial;
+            si (c) {
+                parcial = 1;
+            }
+            mutabilis numerus laco;
+            pro item in itens {
+                laco = item;
+            }
+            mutabilis numerus curto;
+            constans booleanum ok = c && (curto = 1) > 0;
+            redde parcial + laco + curto + parcial;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("S150", "Variable 'parcial' may be read before it is assigned"),
+        ("S150", "Variable 'laco' may be read before it is assigned"),
+        ("S150", "Variable 'curto' may be read before it is assigned"),
+    ]
+
+
+def test_assignment_on_every_path_satisfies_s150() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(booleanum c) -> numerus {
+            mutabilis numerus ambos;
+            si (c) {
+                ambos = 1;
+            } aliter {
+                ambos = 2;
+            }
+            mutabilis numerus saida;
+            si (c) {
+                redde 0;
+            } aliter {
+                saida = 3;
+            }
+            mutabilis numerus infinito;
+            dum (verum) {
+                infinito = 4;
+                frange;
+            }
+            mutabilis numerus ternario;
+            c ? (ternario = 1) : (ternario = 2);
+            redde ambos + saida + infinito + ternario;
+        }
+        """
+    )
+    assert diagnostics == []
+
+
+def test_c_style_for_update_runs_after_the_body_and_may_never_run() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio depois() -> numerus {
+            mutabilis numerus x;
+            pro (mutabilis i = 0; i < 3; i = i + x) {
+                x = 1;
+            }
+            redde 0;
+        }
+
+        functio continua(booleanum c) -> numerus {
+            mutabilis numerus y;
+            pro (mutabilis i = 0; i < 3; i = i + y) {
+                si (c) {
+                    perge;
+                }
+                y = 1;
+            }
+            redde 0;
+        }
+
+        functio nunca() -> numerus {
+            mutabilis numerus z;
+            pro (mutabilis i = 0; i < 0; z = 1) {}
+            redde z;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("S150", "Variable 'y' may be read before it is assigned"),
+        ("S150", "Variable 'z' may be read before it is assigned"),
+    ]
+
+
+def test_global_colliding_with_function_reports_s111() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        constans numerus valor = 1;
+
+        functio valor() -> numerus {
+            redde 2;
+        }
+
+        functio demo() -> numerus {
+            redde valor();
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["S111"]
+    assert "resolve to the function" in diagnostics[0].message
+
+
+def test_generic_call_substitutes_type_arguments() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio identidade<T>(T x) -> T {
+            redde x;
+        }
+
+        functio demo() {
+            constans textus inferido = identidade("a");
+            constans numerus errado = identidade("b");
+            constans quodlibet explicito = identidade<numerus>("c");
+            constans quodlibet demais = identidade<numerus, textus>(1);
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T200", "T301", "T303"]
+    assert diagnostics[2].message == "Expected 1 type arguments, got 2"
+
+
+STRICT_ANY_SNIPPET = """
+    functio demo(quodlibet valorQuodlibet) {
+        constans numerus x = valorQuodlibet;
+        constans numerus y = valorQuodlibet ut numerus;
+        demo(y);
+        constans quodlibet w = y ut quodlibet;
+    }
+    """
+
+
+def test_strict_any_requires_explicit_cast() -> None:
+    diagnostics = _analyze_snippet(STRICT_ANY_SNIPPET, AnalyzerOptions(strict_any=True))
+    declaration = STRICT_ANY_SNIPPET.index("constans numerus x = valorQuodlibet;")
+    argument = STRICT_ANY_SNIPPET.index("demo(y)") + len("demo(")
+    assert [(diag.code, diag.span.start, diag.span.end) for diag in diagnostics] == [
+        ("T210", declaration, declaration + len("constans numerus x = valorQuodlibet;")),
+        ("T210", argument, argument + 1),
+    ]
+    assert "from quodlibet to numerus" in diagnostics[0].message
+    assert "from numerus to quodlibet" in diagnostics[1].message
+
+
+def test_explicit_quodlibet_variables_are_not_checked_under_strict_any() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio complexo() -> numerus[] {
+            redde [1, 2];
+        }
+
+        functio demo() {
+            constans quodlibet valor = complexo();
+            mutabilis quodlibet outro = 3;
+            outro = "texto";
+            constans numerus primeiro = valor[0];
+            constans textus nome = valor.dados.nome;
+            constans numerus chamado = valor(2);
+            constans numerus copia = outro;
+            pro item in valor {
+                constans numerus dobro = item * 2;
+            }
+        }
+        """,
+        AnalyzerOptions(strict_any=True),
+    )
+    assert diagnostics == []
+
+
+def test_default_mode_keeps_quodlibet_rules() -> None:
+    assert [diag.code for diag in _analyze_snippet(STRICT_ANY_SNIPPET)] == ["T200"]
+
+
+MIXED_ARRAY_SNIPPET = """
+    functio demo() {
+        constans misto = [1, "a"];
+        constans aninhado = [[1], [2, 3]];
+        constans vazio = [];
+        constans comVazio = [[], [1]];
+        constans iguais = [1, 2];
+        constans vaziosMistos = [[], [1], ["a"]];
+        constans guiado: [quodlibet] = [1, "a"];
+        constans explicito = [1 ut quodlibet, "a"];
+    }
+    """
+
+
+def test_strict_any_rejects_arrays_of_incompatible_elements() -> None:
+    diagnostics = _analyze_snippet(MIXED_ARRAY_SNIPPET, AnalyzerOptions(strict_any=True))
+    assert [diag.code for diag in diagnostics] == ["T212", "T212"]
+    assert diagnostics[0].message == (
+        "Array elements have incompatible types numerus and textus; annotate the array or cast elements with 'ut'"
+    )
+    start = MIXED_ARRAY_SNIPPET.index('[1, "a"]')
+    assert (diagnostics[0].span.start, diagnostics[0].span.end) == (start, start + len('[1, "a"]'))
+    assert "[numerus] and [textus]" in diagnostics[1].message
+
+
+def test_default_mode_widens_mixed_arrays_to_quodlibet() -> None:
+    assert _analyze_snippet(MIXED_ARRAY_SNIPPET) == []
+
+
+def test_strict_params_requires_annotations_on_top_level_functions() -> None:
+    snippet = """
+        functio f(x) {}
+
+        functio g(numerus y) {
+            constans quodlibet h = functio (z) => z;
+        }
+        """
+    strict = _analyze_snippet(snippet, AnalyzerOptions(strict_params=True))
+    assert [diag.code for diag in strict] == ["T220"]
+    assert strict[0].message == "Parameter 'x' of 'f' needs a type annotation"
+    assert _analyze_snippet(snippet) == []
+
+
+def test_cast_between_unrelated_types_reports_t211() -> None:
+    diagnostics = _analyze_snippet('constans numerus n = "7" ut numerus;')
+    assert [diag.code for diag in diagnostics] == ["T211"]
+
+
+
+NUMERUS = types.PRIMITIVE_TYPES["numerus"]
+THUNK = types.function_type([], NUMERUS)
+
+
+@pytest.mark.parametrize(
+    ("type_obj", "expected"),
+    [
+        (types.function_type([], THUNK), "functio() -> (functio() -> numerus)"),
+        (THUNK.with_optional(), "(functio() -> numerus)?"),
+        (types.function_type([], NUMERUS.with_optional()), "functio() -> numerus?"),
+        (types.function_type([THUNK], types.PRIMITIVE_TYPES["vacuum"]), "functio(functio() -> numerus) -> vacuum"),
+        (types.Type(types.TypeKind.ARRAY, element=THUNK), "[functio() -> numerus]"),
+    ],
+)
+def test_nested_type_names_are_unambiguous(type_obj, expected) -> None:
+    assert str(type_obj) == expected
+
+
+def test_trailing_lambda_needs_function_parameter() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio repete(numerus n, corpo) {
+        }
+
+        functio duplica(numerus n, numerus m) -> numerus {
+            redde n * m;
+        }
+
+        functio demo() {
+            repete(2) functio () { };
+            duplica(2) functio () { };
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T304"]
+    assert diagnostics[0].message == "Trailing lambda passed to parameter 2, which expects numerus"
+
+
+def test_unused_type_parameter_warns_w110() -> None:
+    source = """
+        functio primeiro<T, U>(T a, b) -> T {
+            redde a;
+        }
+
+        functio converte<V>(valor) {
+            redde valor ut V;
+        }
+        """
+    diagnostics = _analyze_snippet(source)
+    assert [(diag.code, diag.severity) for diag in diagnostics] == [("W110", Severity.WARNING)]
+    assert diagnostics[0].message == "Type parameter 'U' of 'primeiro' is never used"
+    assert source[diagnostics[0].span.start : diagnostics[0].span.end] == "U"
+
+
+@pytest.mark.parametrize(
+    ("operator", "expected"),
+    [
+        ("===", ("T104", Severity.ERROR, "Strict comparison of numerus and textus is always falsum")),
+        ("!==", ("T104", Severity.ERROR, "Strict comparison of numerus and textus is always verum")),
+        ("==", ("W101", Severity.WARNING, "Comparison of numerus and textus is always falsum")),
+        ("!=", ("W101", Severity.WARNING, "Comparison of numerus and textus is always verum")),
+    ],
+)
+def test_equality_between_unrelated_types(operator, expected) -> None:
+    diagnostics = _analyze_snippet(f'constans booleanum x = 1 {operator} "1";')
+    assert [(diag.code, diag.severity, diag.message) for diag in diagnostics] == [expected]
+
+
+@pytest.mark.parametrize("operator", ["==", "!=", "===", "!=="])
+def test_equality_between_related_types_is_silent(operator) -> None:
+    source = f"""
+        functio demo(quodlibet q) {{
+            constans booleanum a = 1 {operator} 2;
+            constans booleanum b = q {operator} "x";
+            constans booleanum c = verum {operator} falsum;
+        }}
+        """
+    assert _analyze_snippet(source) == []
+
+
+@pytest.mark.parametrize("operator", ["==", "!=", "===", "!=="])
+def test_optionals_compare_with_nullum_and_indefinitum_silently(operator) -> None:
+    source = f"""
+        functio demo(x: numerus?, t: textus?) {{
+            constans booleanum a = x {operator} nullum;
+            constans booleanum b = nullum {operator} x;
+            constans booleanum c = t {operator} indefinitum;
+        }}
+        """
+    assert _analyze_snippet(source) == []
+
+
+def test_repeated_annotations_are_resolved_once(monkeypatch) -> None:
+    calls = []
+    original = types.type_from_annotation
+
+    def counting(name, type_params=()):
+        calls.append(name)
+        return original(name, type_params)
+
+    monkeypatch.setattr(types, "type_from_annotation", counting)
+
+    def resolutions(copies: int) -> int:
+        calls.clear()
+        source = "\n".join(
+            f"functio f{index}(numerus n, textus rotulo) -> numerus[] {{ redde [n]; }}"
+            for index in range(copies)
+        )
+        assert _analyze_snippet(source) == []
+        return len(calls)
+
+    assert resolutions(50) == resolutions(1)
+
+
+def test_return_inside_lambda_is_checked_against_the_lambda() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio rotulo() -> textus {
+            constans quodlibet contar = functio () -> numerus {
+                redde 1;
+            };
+            constans quodlibet errado = functio () -> numerus {
+                redde "um";
+            };
+            constans quodlibet curto = functio (numerus x) -> booleanum => x + 1;
+            constans quodlibet livre = functio () {
+                redde verum;
+            };
+            redde "ok";
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T010", "T010"]
+    assert diagnostics[0].message == "Return type mismatch: expected numerus, got textus"
+    assert diagnostics[1].message == "Return type mismatch: expected booleanum, got numerus"
+
+
+def test_lambda_body_sees_parameters_and_enclosing_scope() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(numerus base) {
+            constans quodlibet soma = functio (x) => x + base + fantasma;
+            dum (verum) {
+                constans quodlibet sair = functio () {
+                    frange;
+                };
+                frange;
+            }
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["S100", "T040"]
+
+
+def test_errors_inside_lambda_bodies_are_reported() -> None:
+    source = """
+        functio demo() {
+            constans quodlibet f = functio (numerus x) {
+                constans textus rotulo = x;
+                redde inexistente;
+            };
+            constans quodlibet g = functio () => functio () => -"a";
+        }
+        """
+    diagnostics = _analyze_snippet(source)
+    assert [diag.code for diag in diagnostics] == ["T200", "S100", "T100"]
+    assert diagnostics[1].message == "Undeclared identifier 'inexistente'"
+    start = source.index("inexistente")
+    assert (diagnostics[1].span.start, diagnostics[1].span.end) == (start, start + len("inexistente"))
+
+
+def test_bitwise_negation_requires_numerus() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(quodlibet dinamico) {
+            constans numerus a = ~7;
+            constans numerus b = ~dinamico;
+            constans numerus c = ~"texto";
+            constans textus d = ~a;
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T100", "T200"]
+    assert diagnostics[0].message == "Bitwise negation requires numerus"
+
+
+def test_special_float_literals_are_numerus() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans numerus a = infinitum;
+            constans numerus b = -infinitum + nan;
+            constans textus c = nan;
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T200"]
+
+
+def test_shift_by_fractional_or_negative_literal_warns() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(numerus x, numerus n) {
+            constans numerus a = x << 1.5;
+            constans numerus b = x >> -2;
+            constans numerus c = x << 3;
+            constans numerus d = x >> n;
+            constans numerus e = x << 2.0;
+            constans quodlibet f = "a" << 1;
+            constans numerus g = x << 1e999;
+            constans numerus h = x >> infinitum;
+            constans numerus i = x << nan;
+        }
+        """
+    )
+    assert [(diag.code, diag.severity) for diag in diagnostics] == [
+        ("W102", Severity.WARNING),
+        ("W102", Severity.WARNING),
+        ("T100", Severity.ERROR),
+        ("W102", Severity.WARNING),
+        ("W102", Severity.WARNING),
+        ("W102", Severity.WARNING),
+    ]
+    assert diagnostics[0].message == "Shift amount 1.5 is not a non-negative integer"
+    assert diagnostics[1].message == "Shift amount -2 is not a non-negative integer"
+    assert diagnostics[3].message == "Shift amount infinitum is not a non-negative integer"
+
+
+def test_symbol_index_lists_declarations_with_types() -> None:
+    source = """
+        constans numerus limite = 10;
+
+        functio dobra(numerus x) -> numerus {
+            mutabilis numerus total = x;
+            pro i in 0..limite {
+                total = total + i;
+            }
+            redde total * 2;
+        }
+        """
+    analyzer = SemanticAnalyzer()
+    assert analyzer.analyze(ScriptumParser().parse(SourceFile("<test>", source))) == []
+    entries = [entry.as_json() for entry in analyzer.symbol_index]
+    assert [(entry["name"], entry["kind"], entry["type"]) for entry in entries] == [
+        ("dobra", "function", "functio(numerus) -> numerus"),
+        ("limite", "global", "numerus"),
+        ("x", "parameter", "numerus"),
+        ("total", "local", "numerus"),
+        ("i", "local", "numerus"),
+    ]
+    start, end = entries[1]["span"]
+    assert source[start:end] == "constans numerus limite = 10;"
+
+
+def test_symbol_index_records_inferred_return_types() -> None:
+    source = """
+        functio dobra(numerus x) {
+            si (x > 10) {
+                redde 0;
+            }
+            redde x * 2;
+        }
+
+        functio nada() {
+            constans f = functio (numerus y) { redde "texto"; };
+        }
+
+        functio anotada() -> numerus {
+            redde 1;
+        }
+        """
+    analyzer = SemanticAnalyzer()
+    assert analyzer.analyze(ScriptumParser().parse(SourceFile("<test>", source))) == []
+    functions = {
+        entry.name: (str(entry.type), entry.inferred)
+        for entry in analyzer.symbol_index
+        if entry.kind.value == "function"
+    }
+    assert functions == {
+        "dobra": ("functio(numerus) -> numerus", True),
+        "nada": ("functio() -> vacuum", True),
+        "anotada": ("functio() -> numerus", False),
+    }
+
+
+def test_returns_of_a_type_and_nullum_infer_an_optional() -> None:
+    source = """
+        functio talvez(numerus x) {
+            si (x > 0) {
+                redde x;
+            }
+            redde nullum;
+        }
+
+        functio lista(booleanum vazia) {
+            si (vazia) {
+                redde nullum;
+            }
+            redde [1, 2];
+        }
+
+        functio nulo() {
+            redde nullum;
+        }
+
+        functio misto(booleanum b) {
+            si (b) {
+                redde nullum;
+            }
+            si (!b) {
+                redde "texto";
+            }
+            redde 1;
+        }
+        """
+    analyzer = SemanticAnalyzer()
+    assert analyzer.analyze(ScriptumParser().parse(SourceFile("<test>", source))) == []
+    returns = {entry.name: str(entry.type.ret) for entry in analyzer.symbol_index if entry.kind.value == "function"}
+    assert returns == {"talvez": "numerus?", "lista": "[numerus]?", "nulo": "nullum", "misto": "quodlibet"}
+
+
+def test_member_assignment_checks_field_types() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans ponto = structura { x: 1, nome: "a" };
+            ponto.x = 2;
+            ponto.nome = 3;
+            ponto.z = 0;
+            constans quodlibet livre = ponto;
+            livre.z = 0;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("T200", "Type mismatch: cannot assign numerus to textus"),
+        ("T201", "Object type {x: numerus, nome: textus} has no field 'z'"),
+    ]
+
+
+def test_ipse_in_a_method_has_the_type_of_its_object() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans conta = structura {
+                saldo: 10,
+                titular: "Ana",
+                depositar: functio (numerus valor) -> numerus {
+                    ipse = ipse;
+                    redde ipse.saldo + ipse.titular * valor;
+                }
+            };
+            ipse.saldo;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("S120", "Cannot assign to immutable symbol 'ipse'"),
+        ("T100", "Arithmetic operands must be numerus"),
+        (
+            "S160",
+            "'ipse' is only available inside a method, a 'functio' written as a property of a 'structura'",
+        ),
+    ]
+
+
+@pytest.mark.parametrize(
+    ("body", "warns"),
+    [
+        ("dum (verum) { }", True),
+        ("dum (!falsum) { x = x + 1; }", True),
+        ("dum (verum) { frange; }", False),
+        ("dum (verum) { si (x > 3) { redde; } }", False),
+        ("dum (verum) { pro i in 0..3 { frange; } }", True),
+        ("dum (x < 3) { }", False),
+        ("pro (;;) { }", True),
+        ("pro (; verum; x++) { frange; }", False),
+    ],
+)
+def test_trivially_infinite_loop_warns_w103(body: str, warns: bool) -> None:
+    diagnostics = _analyze_snippet(f"functio demo() {{ mutabilis numerus x = 0; {body} }}")
+    assert [(diag.code, diag.severity) for diag in diagnostics] == ([("W103", Severity.WARNING)] if warns else [])
+
+
+def test_c_style_for_checks_its_condition_and_scopes_its_variable() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() -> numerus {
+            pro (mutabilis numerus i = 0; i; i++) { }
+            pro (constans numerus j = 0; j < 3; j++) { }
+            redde i;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("T022", "Condition for a C-style 'pro' must be booleanum"),
+        ("S120", "Cannot assign to immutable symbol 'j'"),
+        ("S100", "Undeclared identifier 'i'"),
+    ]
+
+
+def test_overwritten_assignment_warns_w104() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(numerus n) -> numerus {
+            mutabilis numerus x = 0;
+            x = 1;
+            si (n > 0) {
+                x = 2;
+            } aliter {
+                x = 3;
+            }
+            redde x;
+        }
+        """
+    )
+    assert [(diag.code, diag.severity, diag.message) for diag in diagnostics] == [
+        ("W104", Severity.WARNING, "Value assigned to 'x' is overwritten before it is read"),
+    ]
+
+
+@pytest.mark.parametrize(
+    "body",
+    [
+        "x = 1; si (n > 0) { x = 2; } redde x;",
+        "x = le(); x = 2; redde x;",
+        "x = 1; constans f = functio () => x; x = 2; f(); redde x;",
+        "pro (mutabilis i = 0; i < n; i++) { x = i; si (i == 2) { perge; } x = 5; } redde x;",
+        "x = 1; dum (x < n) { x = x + 1; } redde x;",
+    ],
+)
+def test_dead_store_check_keeps_values_that_may_be_read(body: str) -> None:
+    source = (
+        "functio le() -> numerus { redde 1; }\n"
+        f"functio demo(numerus n) -> numerus {{ mutabilis numerus x = 0; {body} }}"
+    )
+    assert _analyze_snippet(source) == []
+
+
+def test_second_function_with_the_same_name_reports_s112() -> None:
+    source = """
+        functio area(numerus largura, numerus altura) -> numerus {
+            redde largura * altura;
+        }
+
+        functio area(numerus lado, numerus repeticoes) -> numerus {
+            redde lado * repeticoes;
+        }
+    """
+    diagnostics = _analyze_snippet(source)
+    assert [(diag.code, diag.message) for diag in diagnostics] == [("S112", "Function 'area' is already defined")]
+    (diagnostic,) = diagnostics
+    assert source[diagnostic.span.start : diagnostic.span.end].startswith("functio area(numerus lado")
+    ((label, first),) = diagnostic.notes
+    assert label == "'area' is first defined here"
+    assert source[first.start : first.end].startswith("functio area(numerus largura")
+
+
+def test_multiple_declarations_are_checked_one_by_one() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() -> numerus {
+            constans numerus a = 1, b = "dois";
+            mutabilis x = a, x = 2;
+            redde a + x;
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T200", "S110"]
+
+
+def test_function_values_report_signature_mismatches() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio soma(numerus a, numerus b = 2) -> numerus { redde a + b; }
+        functio dobra(numerus a) -> numerus { redde a * 2; }
+        functio junta(numerus a, numerus b) -> numerus { redde a + b; }
+        functio mede(textus a) -> numerus { redde 1; }
+        functio escolhe<T>(T a, T b) -> T { redde a; }
+
+        functio main() -> numerus {
+            mutabilis callback = dobra;
+            callback = soma;
+            callback(1);
+            callback = junta;
+            escolhe(dobra, mede);
+            escolhe(soma, dobra);
+            redde soma(1) + soma(1, 2);
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        (
+            "T200",
+            "Type mismatch: cannot assign functio(numerus, numerus) -> numerus to functio(numerus) -> numerus "
+            "(takes 2 arguments, but is called with 1 argument)",
+        ),
+        (
+            "T301",
+            "Argument 2 type mismatch: expected functio(numerus) -> numerus, got functio(textus) -> numerus "
+            "(parameter 1 is textus, expected numerus)",
+        ),
+        (
+            "T301",
+            "Argument 2 type mismatch: expected functio(numerus, numerus = ...) -> numerus, "
+            "got functio(numerus) -> numerus (takes 1 argument, but is called with 1 to 2 arguments)",
+        ),
+    ]
+
+
+def test_calls_may_omit_defaulted_arguments() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio soma(numerus a, numerus b = 2) -> numerus { redde a + b; }
+
+        functio main() -> numerus {
+            redde soma() + soma(1, 2, 3);
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Expected 1 to 2 arguments, got 0",
+        "Expected 1 to 2 arguments, got 3",
+    ]
+
+
+def test_defaults_before_a_parameter_without_one_do_not_make_it_optional() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio f(numerus a = 1, numerus b, numerus c = 3) -> numerus { redde a + b + c; }
+
+        functio main() -> numerus {
+            redde f() + f(1) + f(1, 2);
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Expected 2 to 3 arguments, got 0",
+        "Expected 2 to 3 arguments, got 1",
+    ]
+
+
+def test_imprime_takes_any_arguments_and_returns_vacuum() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio main() {
+            imprime();
+            imprime("oi", 42, [verum], structura { x: 1 });
+            constans numerus n = imprime(1);
+            constans quodlibet f = imprime;
+        }
+        """
+    )
+    [diagnostic] = diagnostics
+    assert diagnostic.message == "Type mismatch: cannot initialise 'n' of type numerus with vacuum"
+
+
+def test_module_names_shadow_builtins() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio imprime(textus s) -> numerus { redde 1; }
+
+        functio main() -> numerus {
+            redde imprime("oi") + imprime(1);
+        }
+        """
+    )
+    assert [diag.code for diag in diagnostics] == ["T301"]
+
+
+def test_keyword_member_names_resolve_to_quoted_fields() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio main() -> numerus {
+            constans o = structura { "redde": 1, "si": "x" };
+            constans textus t = o.si;
+            constans numerus n = o.si;
+            redde o.redde + n;
+        }
+        """
+    )
+    [diagnostic] = diagnostics
+    assert diagnostic.message == "Type mismatch: cannot initialise 'n' of type numerus with textus"
+
+
+def test_loop_labels_must_match_an_enclosing_loop() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            externo: dum (verum) {
+                constans quodlibet f = functio () {
+                    pro i in 0..3 {
+                        frange externo;
+                    }
+                };
+                externo: pro j in 0..3 {
+                    perge externo;
+                }
+                frange externo;
+            }
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("S140", "No enclosing loop is labelled 'externo'"),
+        ("S141", "Label 'externo' is already used by an enclosing loop"),
+    ]
+
+
+@pytest.mark.parametrize(
+    ("annotation", "expected"),
+    [
+        ("(numerus)?", "numerus?"),
+        ("(numerus[])?", "[numerus]?"),
+        ("(numerus?)[]", "[numerus?]"),
+        ("((textus))", "textus"),
+    ],
+)
+def test_grouping_parens_in_annotations_add_no_type(annotation: str, expected: str) -> None:
+    assert str(types.type_from_annotation(annotation)) == expected
+
+
+@pytest.mark.parametrize("annotation", ["numerus??", "numerus? ?", "(numerus?)?", "((numerus)?)?"])
+def test_nested_optionals_collapse(annotation: str) -> None:
+    collapsed = types.type_from_annotation(annotation)
+    optional = types.type_from_annotation("numerus?")
+    assert collapsed == optional
+    assert str(collapsed) == str(optional) == "numerus?"
+    assert collapsed.is_assignable_from(optional) and optional.is_assignable_from(collapsed)
+    assert optional.with_optional() == optional
+
+
+def test_double_optional_annotation_is_checked_as_optional() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(entrada: numerus??) -> numerus?? {
+            mutabilis valor: numerus? = entrada;
+            constans textus errado = entrada;
+            redde valor;
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Type mismatch: cannot initialise 'errado' of type textus with numerus?",
+    ]
+
+
+def test_grouped_annotations_are_checked() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(entrada: (numerus[])?, lista: (numerus?)[]) -> numerus {
+            constans textus errado = entrada;
+            redde lista[0] ?? 0;
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Type mismatch: cannot initialise 'errado' of type textus with [numerus]?",
+    ]
+
+
+@pytest.mark.parametrize(
+    ("annotation", "expected"),
+    [
+        ("{x: numerus, y: textus?}", "{x: numerus, y: textus?}"),
+        ("{ponto: {x: numerus}, tags: textus[]}", "{ponto: {x: numerus}, tags: [textus]}"),
+        ("{}", "{}"),
+        ("{x: desconhecido}", "None"),
+    ],
+)
+def test_object_type_annotations(annotation: str, expected: str) -> None:
+    assert str(types.type_from_annotation(annotation)) == expected
+
+
+def test_large_object_types_are_printed_over_several_lines() -> None:
+    fields = ", ".join(f"c{index}: numerus" for index in range(9))
+    large = types.type_from_annotation(f"{{{fields}, extra: {{x: numerus}}[]}}")
+    expected_lines = [f"    c{index}: numerus," for index in range(9)]
+    assert large.pretty() == "\n".join(["{", *expected_lines, "    extra: [{x: numerus}]", "}"])
+    assert str(large) == f"{{{fields}, extra: [{{x: numerus}}]}}"
+    assert large.with_optional().pretty(1).endswith("\n    }?")
+
+    small = types.type_from_annotation("{x: numerus, y: textus}")
+    assert small.pretty() == str(small) == "{x: numerus, y: textus}"
+
+    diagnostics = _analyze_snippet(
+        f"""
+        functio demo() {{
+            constans ficha: {{{fields}, extra: numerus}} = structura {{ c0: 1 }};
+            ficha.nada = 1;
+        }}
+        """
+    )
+    assert diagnostics[0].code == "T200"
+    assert diagnostics[0].message.startswith("Type mismatch: cannot initialise 'ficha' of type {\n    c0: numerus,\n")
+    missing = ", ".join(f"'c{index}'" for index in range(1, 9))
+    tail = f"\n    extra: numerus\n}} with {{c0: numerus}} (missing fields {missing}, 'extra')"
+    assert diagnostics[0].message.endswith(tail)
+    # Short messages keep the one-line form.
+    assert diagnostics[1].code == "T201"
+    assert "\n" not in diagnostics[1].message
+
+
+def test_object_literal_must_have_every_annotated_field() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio norma(p: {x: numerus, y: numerus}) -> numerus {
+            redde p.x + p.y;
+        }
+
+        functio demo() {
+            constans a: {x: numerus, y: numerus} = structura { x: 1 };
+            constans b: {x: numerus, y: numerus, z: textus} = structura {};
+            constans c: {x: numerus} = structura { x: "um" };
+            constans numerus d = norma(structura { y: 1 });
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        (
+            "T200",
+            "Type mismatch: cannot initialise 'a' of type {x: numerus, y: numerus} with {x: numerus} "
+            "(missing field 'y')",
+        ),
+        (
+            "T200",
+            "Type mismatch: cannot initialise 'b' of type {x: numerus, y: numerus, z: textus} with {} "
+            "(missing fields 'x', 'y', 'z')",
+        ),
+        (
+            "T200",
+            "Type mismatch: cannot initialise 'c' of type {x: numerus} with {x: textus} "
+            "(field 'x' is textus, expected numerus)",
+        ),
+        (
+            "T301",
+            "Argument 1 type mismatch: expected {x: numerus, y: numerus}, got {y: numerus} (missing field 'x')",
+        ),
+    ]
+
+
+def test_object_literal_may_have_extra_fields() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() -> numerus {
+            constans p: {x: numerus, y: numerus} = structura { y: 2, x: 1, rotulo: "origem" };
+            mutabilis q: {x: numerus} = p;
+            q = structura { x: 3, y: 4 };
+            redde q.x + p.y;
+        }
+        """
+    )
+    assert diagnostics == []
+
+
+def test_nested_objects_match_regardless_of_field_order() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(flag: booleanum) -> textus {
+            constans a: {p: {x: numerus, y: {m: textus, n: [numerus]}}, q: numerus} =
+                structura { q: 1, p: structura { y: structura { n: [1, 2], m: "m" }, x: 2 } };
+            constans xs = [
+                structura { a: structura { m: 1, n: "x" }, b: 2 },
+                structura { b: 3, a: structura { n: "y", m: 4 } }
+            ];
+            constans k: numerus = xs[0].a.m;
+            constans t = flag
+                ? structura { a: structura { m: 1, n: "x" } }
+                : structura { a: structura { n: "y", m: 2 } };
+            mutabilis b: {p: {y: {m: textus}}} = a;
+            b = structura { p: structura { extra: verum, y: structura { m: "z" } } };
+            constans m: textus = b.p.y.m;
+            redde t.a.n;
+        }
+        """
+    )
+    assert diagnostics == []
+
+
+def test_nested_object_mismatch_names_the_innermost_field() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans c: {p: {q: {x: numerus}}} = structura { p: structura { q: structura { x: "errado" } } };
+            constans d: {p: {x: numerus, y: textus}} = structura { p: structura { x: 1 } };
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        (
+            "T200",
+            "Type mismatch: cannot initialise 'c' of type {p: {q: {x: numerus}}} with {p: {q: {x: textus}}} "
+            "(field 'p.q.x' is textus, expected numerus)",
+        ),
+        (
+            "T200",
+            "Type mismatch: cannot initialise 'd' of type {p: {x: numerus, y: textus}} with {p: {x: numerus}} "
+            "(missing field 'p.y')",
+        ),
+    ]
+
+
+def test_indexing_textus_yields_textus() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio main() {
+            constans textus nome = "Scriptum";
+            constans textus inicial = nome[0];
+            constans textus meio = nome[2..=4];
+            constans numerus errado = nome[1];
+            constans chave = nome["S"];
+            constans xs = [1, 2, 3];
+            constans fatia = xs[0..2];
+            constans par = (1, "um")[0..=1];
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("T200", "Type mismatch: cannot initialise 'errado' of type numerus with textus"),
+        ("T241", "A textus is indexed by numerus or a range, not textus"),
+        ("T242", "Only a textus can be indexed by a range, not [numerus]"),
+        ("T242", "Only a textus can be indexed by a range, not (numerus, textus)"),
+    ]
+
+
+def test_tuple_returns_check_size_and_positions() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio divmod(numerus a, numerus b) -> (numerus, numerus) {
+            si a < 0 { redde (a, b, 1); }
+            si b == 0 { redde (a, "zero"); }
+            redde ((a - a % b) / b, a % b);
+        }
+
+        functio main() {
+            constans (q, r) = divmod(17, 5);
+            constans numerus soma = q + r;
+            constans t = divmod(9, 2);
+            constans textus errado = t[1];
+            constans (x, y, z) = t;
+            constans fora = t[2];
+            constans (n, m) = soma;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        (
+            "T010",
+            "Return type mismatch: expected (numerus, numerus), got (numerus, numerus, numerus) "
+            "(expected 2 values, got 3)",
+        ),
+        (
+            "T010",
+            "Return type mismatch: expected (numerus, numerus), got (numerus, textus) "
+            "(value 2 is textus, expected numerus)",
+        ),
+        ("T200", "Type mismatch: cannot initialise 'errado' of type textus with numerus"),
+        ("T240", "Cannot destructure (numerus, numerus) into 3 names; it holds 2 values"),
+        ("T240", "Index 2 is out of range for tuple (numerus, numerus)"),
+        ("T240", "Only tuples and arrays can be destructured, found numerus"),
+    ]
+
+
+def test_non_finite_tuple_indexes_are_out_of_range() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo() {
+            constans t = (1, "a");
+            constans a = t[1e999];
+            constans b = t[-infinitum];
+            constans c = t[nan];
+            constans d = t[0.5];
+        }
+        """
+    )
+    assert [diag.message for diag in diagnostics] == [
+        "Index infinitum is out of range for tuple (numerus, textus)",
+        "Index -infinitum is out of range for tuple (numerus, textus)",
+        "Index nan is out of range for tuple (numerus, textus)",
+    ]
+
+
+def test_member_access_on_types_without_members_reports_t202() -> None:
+    diagnostics = _analyze_snippet(
+        """
+        functio demo(numerus n, textus nome, lista: numerus[], booleanum ok, quodlibet livre) {
+            constans a = n.valor;
+            constans b = nome.comprimento;
+            constans c = lista.comprimento;
+            ok.marcado = verum;
+            constans d = livre.qualquer;
+        }
+        """
+    )
+    assert [(diag.code, diag.message) for diag in diagnostics] == [
+        ("T202", "Type numerus has no members, so '.valor' does not exist"),
+        ("T202", "Type textus has no members, so '.comprimento' does not exist; read a character with [indice]"),
+        (
+            "T202",
+            "Type [numerus] has no members, so '.comprimento' does not exist; "
+            "read an element with [indice] or visit them with 'pro'",
+        ),
+        ("T202", "Type booleanum has no members, so '.marcado' does not exist"),
+    ]